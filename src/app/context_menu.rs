@@ -1,8 +1,10 @@
 //! Shared context menu for map items (used by both grid and list views)
 
 use super::App;
+use crate::constants::{APP_VERSION, ISSUE_URL};
 use crate::theme;
 use eframe::egui;
+use tracing::warn;
 
 pub(crate) struct MapAction {
     pub preview: Option<Vec<String>>,
@@ -20,19 +22,23 @@ impl App {
         ui.spacing_mut().item_spacing.y = 2.0;
         let selected_count = self.selected_indices.len();
 
-        let labels: Vec<String> = if selected_count > 1 {
+        let authors = self.maps.get(map_idx).map(|m| m.authors.clone()).unwrap_or_default();
+        let mut labels: Vec<String> = if selected_count > 1 {
             vec![
                 format!("{}  Preview {} maps", egui_phosphor::regular::EYE, selected_count),
                 format!("{}  Download {} maps", egui_phosphor::regular::DOWNLOAD_SIMPLE, selected_count),
+                format!("{}  Report issue", egui_phosphor::regular::BUG),
                 format!("{}  Deselect All", egui_phosphor::regular::X_SQUARE),
             ]
         } else {
             vec![
                 format!("{}  Preview", egui_phosphor::regular::EYE),
                 format!("{}  Download", egui_phosphor::regular::DOWNLOAD_SIMPLE),
+                format!("{}  Report issue", egui_phosphor::regular::BUG),
                 format!("{}  Deselect All", egui_phosphor::regular::X_SQUARE),
             ]
         };
+        labels.extend(authors.iter().map(|a| format!("{}  About {}", egui_phosphor::regular::USER, a)));
         let label_refs: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
         theme::set_menu_width(ui, &label_refs);
 
@@ -60,7 +66,13 @@ impl App {
                 action.preview = Some(vec![map_name.to_string()]);
                 ui.close_menu();
             }
-            if theme::menu_item(ui, egui_phosphor::regular::DOWNLOAD_SIMPLE, "Download") {
+            // A map dropped from the manifest has no working URL
+            let removed = self.maps.get(map_idx).is_some_and(|m| m.removed_at.is_some());
+            if ui
+                .add_enabled_ui(!removed, |ui| theme::menu_item(ui, egui_phosphor::regular::DOWNLOAD_SIMPLE, "Download"))
+                .inner
+            {
+                self.remember_selection();
                 self.selected_indices.clear();
                 self.selected_indices.insert(map_idx);
                 action.download = true;
@@ -68,7 +80,22 @@ impl App {
             }
         }
         ui.separator();
+        for author in authors {
+            if theme::menu_item(ui, egui_phosphor::regular::USER, &format!("About {}", author)) {
+                self.author_popup = Some(author);
+                ui.close_menu();
+            }
+        }
+        if theme::menu_item(ui, egui_phosphor::regular::BUG, "Report issue") {
+            if let Some(url) = self.issue_report_url(map_idx) {
+                if let Err(e) = open::that(&url) {
+                    warn!(error = %e, "Failed to open issue tracker");
+                }
+            }
+            ui.close_menu();
+        }
         if theme::menu_item(ui, egui_phosphor::regular::X_SQUARE, "Deselect All") {
+            self.remember_selection();
             self.selected_indices.clear();
             self.last_selected = None;
             ui.close_menu();
@@ -76,4 +103,30 @@ impl App {
 
         action
     }
+
+    /// New-issue URL prefilled with the map's details, so reports arrive in one format
+    fn issue_report_url(&self, map_idx: usize) -> Option<String> {
+        let map = self.maps.get(map_idx)?;
+        let db_version = self.db.get_db_version().ok().flatten().unwrap_or_else(|| "unknown".to_string());
+        let title = format!("[Map] {}: ", map.name);
+        let body = format!(
+            "**Map:** {}\n\
+             **Category:** {}\n\
+             **Stars:** {}\n\
+             **URL:** {}\n\
+             **App version:** {}\n\
+             **DB version:** {}\n\
+             \n\
+             **What's wrong?** (broken link, wrong metadata, ...)\n\n",
+            map.name,
+            map.category,
+            map.stars,
+            self.get_map_url(map),
+            APP_VERSION,
+            db_version,
+        );
+        reqwest::Url::parse_with_params(ISSUE_URL, &[("title", title), ("body", body)])
+            .ok()
+            .map(String::from)
+    }
 }
@@ -1,39 +1,119 @@
 //! Download logic
 
 use super::App;
+use crate::constants::{APP_VERSION, CONFIRM_DOWNLOAD_THRESHOLD};
+use crate::db::Map;
+use crate::tray::{Tray, TrayCommand};
 use crate::types::*;
+use crate::utils::write_atomic;
 use eframe::egui;
 use futures::StreamExt;
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tokio_util::sync::CancellationToken;
-use tracing::info;
+use tracing::{debug, info, warn};
+
+/// Describe a request error with its HTTP status and URL.
+/// The first line is a short summary; following lines hold the details.
+fn describe_error(e: &reqwest::Error, url: &str) -> String {
+    let summary = match e.status() {
+        Some(status) => format!("HTTP {}", status),
+        None if e.is_timeout() => "Timed out".to_string(),
+        None if e.is_connect() => "Connection failed".to_string(),
+        None => "Request failed".to_string(),
+    };
+    let final_url = e.url().map(|u| u.as_str()).unwrap_or(url);
+    format!("{}\nURL: {}\nError: {}", summary, final_url, e)
+}
+
+/// Where to write a downloaded map that may collide with an existing file.
+/// Identical content is simply rewritten; with `Rename`, differing content goes to the
+/// first free "name (n).map". Returns the path and whether it differs from `dest`.
+fn resolve_collision(dest: PathBuf, bytes: &[u8], collision: CollisionStrategy) -> (PathBuf, bool) {
+    if collision != CollisionStrategy::Rename {
+        return (dest, false);
+    }
+    match std::fs::read(&dest) {
+        Ok(existing) if existing != bytes => {}
+        _ => return (dest, false),
+    }
+    let stem = dest.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let ext = dest.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    let free = (1..)
+        .map(|n| dest.with_file_name(format!("{} ({}){}", stem, n, ext)))
+        .find(|path| match std::fs::read(path) {
+            Ok(existing) => existing == bytes, // already saved under this name
+            Err(_) => !path.exists(),
+        })
+        .unwrap_or_else(|| dest.clone());
+    (free, true)
+}
+
+/// Fetch a map's companion files next to it. Failures are logged and counted
+/// but don't fail the map itself.
+async fn download_companions(
+    companions: Vec<CompanionJob>,
+    overwrite: bool,
+    state: &Arc<Mutex<DownloadState>>,
+    client: &reqwest::Client,
+    token: &CancellationToken,
+) {
+    for (url, dest, size) in companions {
+        if !overwrite && dest.exists() {
+            let mut s = state.lock().unwrap();
+            s.companions_done += 1;
+            s.downloaded_bytes += size.max(0) as u64;
+            continue;
+        }
+        let fetch = async { client.get(&url).send().await?.error_for_status()?.bytes().await };
+        let result = tokio::select! {
+            _ = token.cancelled() => return,
+            result = fetch => result,
+        };
+        let saved = match result {
+            Ok(bytes) => write_atomic(&dest, &bytes).map_err(|e| e.to_string()),
+            Err(e) => Err(describe_error(&e, &url).replace('\n', " · ")),
+        };
+        let mut s = state.lock().unwrap();
+        match saved {
+            Ok(()) => {
+                s.companions_done += 1;
+                s.downloaded_bytes += size.max(0) as u64;
+            }
+            Err(e) => {
+                warn!(file = %dest.display(), error = %e, "Failed to download companion file");
+                s.companions_failed += 1;
+            }
+        }
+    }
+}
 
 /// Download a single map file with progress tracking and cancellation support.
 async fn download_map(
-    idx: usize,
-    url: String,
-    dest: PathBuf,
-    map_size: i64,
-    skip_existing: bool,
+    job: DownloadJob,
     state: Arc<Mutex<DownloadState>>,
     client: &reqwest::Client,
     ctx: &egui::Context,
     token: &CancellationToken,
 ) {
+    let DownloadJob { idx, url, dest, size: map_size, collision, companions } = job;
     if token.is_cancelled() {
         let mut s = state.lock().unwrap();
         if matches!(s.downloads.get(&idx), Some(DownloadStatus::Pending)) {
-            s.downloads.insert(idx, DownloadStatus::Cancelled);
+            s.finish(idx, DownloadStatus::Cancelled);
             s.cancelled_count += 1;
         }
         ctx.request_repaint();
         return;
     }
 
-    if skip_existing && dest.exists() {
+    let overwrite = collision == CollisionStrategy::Overwrite;
+    if collision == CollisionStrategy::Skip && dest.exists() {
+        // Still pending while companions fetch, so the batch isn't reported finished early
+        download_companions(companions, overwrite, &state, client, token).await;
         let mut s = state.lock().unwrap();
-        s.downloads.insert(idx, DownloadStatus::Skipped);
+        s.finish(idx, DownloadStatus::Skipped);
         s.skipped_count += 1;
         s.downloaded_bytes += map_size as u64;
         ctx.request_repaint();
@@ -51,6 +131,7 @@ async fn download_map(
 
     match result {
         Ok(response) if response.status().is_success() => {
+            let final_url = response.url().to_string();
             let total_size = response.content_length().unwrap_or(0);
             let mut downloaded: u64 = 0;
             let mut bytes_vec = Vec::with_capacity(total_size as usize);
@@ -61,7 +142,7 @@ async fn download_map(
                 tokio::select! {
                     _ = token.cancelled() => {
                         let mut s = state.lock().unwrap();
-                        s.downloads.insert(idx, DownloadStatus::Cancelled);
+                        s.finish(idx, DownloadStatus::Cancelled);
                         s.cancelled_count += 1;
                         s.active_count -= 1;
                         ctx.request_repaint();
@@ -82,7 +163,7 @@ async fn download_map(
                             }
                             Some(Err(e)) => {
                                 let mut s = state.lock().unwrap();
-                                s.downloads.insert(idx, DownloadStatus::Failed(e.to_string()));
+                                s.finish(idx, DownloadStatus::Failed(describe_error(&e, &url)));
                                 s.failed_count += 1;
                                 s.active_count -= 1;
                                 ctx.request_repaint();
@@ -94,28 +175,67 @@ async fn download_map(
                 }
             }
 
-            if std::fs::write(&dest, &bytes_vec).is_ok() {
-                let mut s = state.lock().unwrap();
-                s.downloads.insert(idx, DownloadStatus::Complete);
-                s.completed_count += 1;
-                s.active_count -= 1;
-                s.downloaded_bytes += map_size as u64;
-            } else {
-                let mut s = state.lock().unwrap();
-                s.downloads.insert(idx, DownloadStatus::Failed("Write failed".into()));
-                s.failed_count += 1;
-                s.active_count -= 1;
+            let (dest, renamed) = resolve_collision(dest, &bytes_vec, collision);
+            match write_atomic(&dest, &bytes_vec) {
+                Ok(()) => {
+                    download_companions(companions, overwrite, &state, client, token).await;
+                    let command = {
+                        let mut s = state.lock().unwrap();
+                        if renamed {
+                            let name = dest.file_name().unwrap_or_default().to_string_lossy().to_string();
+                            info!(file = %name, "Existing file differs, saved under a new name");
+                            s.saved_as.insert(idx, name);
+                        }
+                        s.finish(idx, DownloadStatus::Complete);
+                        s.completed_count += 1;
+                        s.active_count -= 1;
+                        s.downloaded_bytes += map_size as u64;
+                        s.post_download_command.clone()
+                    };
+                    if let Some(command) = command {
+                        let name = dest.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                        let token = token.clone();
+                        let result = tokio::task::spawn_blocking(move || {
+                            crate::utils::run_post_download_command(&command, &dest, &name, &token)
+                        })
+                        .await;
+                        if let Ok(Err(e)) = result {
+                            state.lock().unwrap().command_failures.push(e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    let mut s = state.lock().unwrap();
+                    s.finish(
+                        idx,
+                        DownloadStatus::Failed(format!(
+                            "Write failed\nURL: {}\nPath: {}\nError: {}",
+                            final_url,
+                            dest.display(),
+                            e
+                        )),
+                    );
+                    s.failed_count += 1;
+                    s.active_count -= 1;
+                }
             }
         }
         Ok(response) => {
             let mut s = state.lock().unwrap();
-            s.downloads.insert(idx, DownloadStatus::Failed(format!("HTTP {}", response.status())));
+            s.finish(
+                idx,
+                DownloadStatus::Failed(format!(
+                    "HTTP {}\nURL: {}",
+                    response.status(),
+                    response.url()
+                )),
+            );
             s.failed_count += 1;
             s.active_count -= 1;
         }
         Err(e) => {
             let mut s = state.lock().unwrap();
-            s.downloads.insert(idx, DownloadStatus::Failed(e.to_string()));
+            s.finish(idx, DownloadStatus::Failed(describe_error(&e, &url)));
             s.failed_count += 1;
             s.active_count -= 1;
         }
@@ -123,29 +243,73 @@ async fn download_map(
     ctx.request_repaint();
 }
 
-/// Spawn a batch of download tasks with a shared semaphore.
+/// Number of maps downloaded concurrently
+const MAX_CONCURRENT_DOWNLOADS: usize = 4;
+
+/// Number of HEAD requests in flight during a URL precheck
+const PRECHECK_CONCURRENCY: usize = 8;
+
+/// How long a single precheck HEAD request may take before its link counts as dead
+const PRECHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// How often the download folder is re-checked (e.g. for a reconnected drive)
+const DOWNLOAD_PATH_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Minimum time between failure sounds
+const FAILURE_SOUND_COOLDOWN: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// How often paused workers check whether they may continue
+const PAUSE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// How long closing the app waits for cancelled downloads to stop
+const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Queue a batch of downloads and spawn workers that pull from `DownloadState::queue`.
+/// The queue and `DownloadState::jobs` are only touched under the state lock, so
+/// reordering from the UI while workers run can't duplicate or skip entries, and
+/// workers from an earlier spawn still find every job.
 fn spawn_download_batch(
-    maps: Vec<(usize, String, PathBuf, i64, bool)>,
+    maps: Vec<DownloadJob>,
     state: Arc<Mutex<DownloadState>>,
     cancel_token: CancellationToken,
     ctx: egui::Context,
     runtime: &tokio::runtime::Runtime,
 ) {
+    {
+        let mut s = state.lock().unwrap();
+        s.queue.extend(maps.iter().map(|job| job.idx));
+        s.jobs.extend(maps.into_iter().map(|job| (job.idx, job)));
+        s.workers += MAX_CONCURRENT_DOWNLOADS;
+    }
+
     runtime.spawn(async move {
-        let semaphore = Arc::new(tokio::sync::Semaphore::new(4));
         let client = reqwest::Client::new();
         let mut handles = vec![];
 
-        for (idx, url, dest, map_size, skip_existing) in maps {
-            let sem = semaphore.clone();
+        for _ in 0..MAX_CONCURRENT_DOWNLOADS {
             let state = state.clone();
             let client = client.clone();
             let ctx = ctx.clone();
             let token = cancel_token.clone();
 
             handles.push(tokio::spawn(async move {
-                let _permit = sem.acquire().await.unwrap();
-                download_map(idx, url, dest, map_size, skip_existing, state, &client, &ctx, &token).await;
+                loop {
+                    // Hold queued maps while paused; files already in flight finish
+                    while state.lock().unwrap().paused && !token.is_cancelled() {
+                        tokio::time::sleep(PAUSE_POLL_INTERVAL).await;
+                    }
+                    let next = {
+                        let mut s = state.lock().unwrap();
+                        let next = s.queue.pop_front().map(|idx| s.jobs.get(&idx).cloned());
+                        if next.is_none() {
+                            s.workers -= 1;
+                        }
+                        next
+                    };
+                    let Some(job) = next else { break };
+                    let Some(job) = job else { continue };
+                    download_map(job, state.clone(), &client, &ctx, &token).await;
+                }
             }));
         }
 
@@ -157,40 +321,177 @@ fn spawn_download_batch(
 
 impl App {
     pub fn download_selected(&mut self, ctx: &egui::Context) {
-        let selected: Vec<usize> = self.selected_indices.iter().copied().collect();
-        if selected.is_empty() {
+        // Queue in visible (sorted) order, then any selected maps hidden by filters
+        let mut selected: Vec<usize> = self
+            .filtered_indices
+            .iter()
+            .copied()
+            .filter(|idx| self.selected_indices.contains(idx))
+            .collect();
+        let queued: HashSet<usize> = selected.iter().copied().collect();
+        let mut hidden: Vec<usize> = self
+            .selected_indices
+            .difference(&queued)
+            .copied()
+            .collect();
+        hidden.sort_unstable();
+        selected.extend(hidden);
+        self.download_indices(ctx, selected);
+    }
+
+    /// Download `selected` (in order) without touching the selection, e.g. a single
+    /// map from a preview tab or the map of the day banner
+    pub fn download_indices(&mut self, ctx: &egui::Context, mut selected: Vec<usize>) {
+        if selected.is_empty() || self.precheck_order.is_some() || self.download_confirm.is_some() {
+            return;
+        }
+        // Maps dropped from the manifest have no working URL
+        let before = selected.len();
+        selected.retain(|&idx| self.maps.get(idx).is_some_and(|m| m.removed_at.is_none()));
+        let removed = before - selected.len();
+        if removed > 0 {
+            let noun = if removed == 1 { "map" } else { "maps" };
+            self.push_toast(
+                format!("Skipped {} removed {}: no longer available for download", removed, noun),
+                ToastSeverity::Warning,
+            );
+        }
+        if selected.is_empty() || self.refuse_while_downloading() {
+            return;
+        }
+        if !self.download_path_ok {
+            self.push_toast("Download folder is unreachable".to_string(), ToastSeverity::Error);
+            return;
+        }
+
+        if self.confirm_downloads && selected.len() > CONFIRM_DOWNLOAD_THRESHOLD {
+            self.download_confirm_overwrite = self.collision_strategy == CollisionStrategy::Overwrite;
+            self.download_confirm = Some(selected);
+        } else {
+            self.begin_download(ctx, selected);
+        }
+    }
+
+    /// Start `selected` once confirmed, via the URL precheck if enabled
+    pub fn begin_download(&mut self, ctx: &egui::Context, selected: Vec<usize>) {
+        if self.refuse_while_downloading() {
+            self.batch_collision = None;
             return;
         }
+        if self.precheck_urls {
+            self.start_url_precheck(ctx, selected);
+        } else {
+            self.start_download_batch(ctx, selected);
+        }
+    }
+
+    /// A new batch would replace the running one's state, so only one runs at a time.
+    /// Returns true (after telling the user) if one is still running or being prechecked.
+    fn refuse_while_downloading(&mut self) -> bool {
+        if !self.downloads_in_progress() && self.precheck_order.is_none() && self.next_batch.is_none() {
+            return false;
+        }
+        if self.precheck_order.is_some() && self.precheck_dead.is_empty() {
+            self.push_toast_with_button(
+                "Still checking map links for the previous download",
+                ToastSeverity::Warning,
+                ToastAction::CancelPrecheck,
+            );
+            return true;
+        }
+        self.push_toast(
+            "Wait for the current downloads to finish before starting more".to_string(),
+            ToastSeverity::Warning,
+        );
+        true
+    }
 
+    /// Queue `selected` (in order) as a new download batch
+    pub fn start_download_batch(&mut self, ctx: &egui::Context, selected: Vec<usize>) {
+        let collision = self.batch_collision.take().unwrap_or(self.collision_strategy);
+        if selected.is_empty() {
+            return;
+        }
         std::fs::create_dir_all(&self.download_path).ok();
 
-        let maps: Vec<(usize, String, PathBuf, i64, bool)> = selected
+        let maps: Vec<DownloadJob> = selected
             .iter()
             .filter_map(|&idx| {
                 let map = self.maps.get(idx)?;
-                let url = Self::get_map_url(map);
+                let url = self.get_map_url(map);
                 let dest = self.download_path.join(format!("{}.map", map.name));
-                Some((idx, url, dest, map.size, true)) // skip_existing = true
+                let companions = self.companion_jobs(map, &url);
+                Some(DownloadJob { idx, url, dest, size: map.size, collision, companions })
             })
             .collect();
 
         info!(count = maps.len(), path = %self.download_path.display(), "Starting download batch");
 
+        // Workers of the previous batch may still be running a post-download command.
+        // They share the state reset below, so stop them and wait until they're gone.
+        if let Some(old) = self.cancel_token.take() {
+            old.cancel();
+        }
+        if self.download_state.lock().unwrap().workers > 0 {
+            debug!("Waiting for the previous batch to stop");
+            self.next_batch = Some(maps);
+            self.show_download_modal = true;
+            return;
+        }
+        self.launch_download_batch(ctx, maps);
+    }
+
+    /// Start a batch that was waiting for the previous one's workers to stop
+    pub fn poll_next_batch(&mut self, ctx: &egui::Context) {
+        if self.next_batch.is_none() {
+            return;
+        }
+        if self.download_state.lock().unwrap().workers > 0 {
+            ctx.request_repaint_after(PAUSE_POLL_INTERVAL);
+            return;
+        }
+        if let Some(maps) = self.next_batch.take() {
+            self.launch_download_batch(ctx, maps);
+        }
+    }
+
+    /// Reset the download state for `maps` and spawn their workers. The previous
+    /// batch's workers must be gone.
+    fn launch_download_batch(&mut self, ctx: &egui::Context, maps: Vec<DownloadJob>) {
         let cancel_token = CancellationToken::new();
         self.cancel_token = Some(cancel_token.clone());
 
         {
             let mut s = self.download_state.lock().unwrap();
+            s.downloads.clear();
+            s.finished_at.clear();
+            s.queue.clear();
+            s.jobs.clear();
+            s.retry_attempts.clear();
+            s.saved_as.clear();
+            s.paused = false;
+            s.active_count = 0;
             s.total_queued = maps.len();
             s.completed_count = 0;
             s.failed_count = 0;
             s.skipped_count = 0;
             s.cancelled_count = 0;
-            s.total_bytes = maps.iter().map(|(_, _, _, size, _)| *size as u64).sum();
+            s.run_start = (0, 0);
+            s.total_bytes = maps
+                .iter()
+                .map(|job| {
+                    job.size as u64 + job.companions.iter().map(|(_, _, size)| (*size).max(0) as u64).sum::<u64>()
+                })
+                .sum();
             s.downloaded_bytes = 0;
-            s.download_order = maps.iter().map(|(idx, _, _, _, _)| *idx).collect();
-            for &(idx, _, _, _, _) in &maps {
-                s.downloads.insert(idx, DownloadStatus::Pending);
+            s.companions_total = maps.iter().map(|job| job.companions.len()).sum();
+            s.companions_done = 0;
+            s.companions_failed = 0;
+            s.post_download_command = self.post_download_command.clone().filter(|c| !c.trim().is_empty());
+            s.command_failures.clear();
+            s.download_order = maps.iter().map(|job| job.idx).collect();
+            for job in &maps {
+                s.downloads.insert(job.idx, DownloadStatus::Pending);
             }
         }
 
@@ -199,17 +500,45 @@ impl App {
         spawn_download_batch(maps, self.download_state.clone(), cancel_token, ctx.clone(), &self.runtime);
     }
 
-    pub fn retry_failed_downloads(&mut self, ctx: &egui::Context) {
-        let failed_maps: Vec<(usize, String, PathBuf, i64, bool)> = {
+    /// Companion files to fetch with `map`, resolved next to its URL. File names are
+    /// reduced to their last component so a manifest entry can't write outside the folder.
+    fn companion_jobs(&self, map: &Map, map_url: &str) -> Vec<CompanionJob> {
+        if !self.download_companion_files {
+            return Vec::new();
+        }
+        let base = map_url.rsplit_once('/').map_or(map_url, |(dir, _)| dir);
+        map.companions
+            .iter()
+            .filter_map(|c| {
+                let name = std::path::Path::new(&c.file).file_name()?;
+                let url = format!("{}/{}", base, c.file.trim_start_matches('/'));
+                Some((url, self.download_path.join(name), c.size))
+            })
+            .collect()
+    }
+
+    /// Re-queue failed maps. Maps that already used `max_retries` attempts are
+    /// left alone unless `force` is set, so a dead mirror can't cause endless retries.
+    pub fn retry_failed_downloads(&mut self, ctx: &egui::Context, force: bool) {
+        // A new batch is about to replace this one
+        if self.precheck_order.is_some() || self.next_batch.is_some() {
+            return;
+        }
+        let failed_maps: Vec<DownloadJob> = {
             let s = self.download_state.lock().unwrap();
             s.download_order
                 .iter()
                 .filter_map(|&idx| {
-                    if matches!(s.downloads.get(&idx), Some(DownloadStatus::Failed(_))) {
+                    if matches!(s.downloads.get(&idx), Some(DownloadStatus::Failed(_)))
+                        && (force || !s.retries_exhausted(idx, self.max_retries))
+                    {
                         let map = self.maps.get(idx)?;
-                        let url = Self::get_map_url(map);
+                        let url = self.get_map_url(map);
                         let dest = self.download_path.join(format!("{}.map", map.name));
-                        Some((idx, url, dest, map.size, false)) // skip_existing = false
+                        let companions = self.companion_jobs(map, &url);
+                        // Same collision handling as when the map was first queued
+                        let collision = s.jobs.get(&idx).map_or(self.collision_strategy, |job| job.collision);
+                        Some(DownloadJob { idx, url, dest, size: map.size, collision, companions })
                     } else {
                         None
                     }
@@ -221,17 +550,522 @@ impl App {
             return;
         }
 
+        let cancelled = self.cancel_token.as_ref().is_none_or(|token| token.is_cancelled());
+        let workers_running = {
+            let mut s = self.download_state.lock().unwrap();
+            // Cancelled workers still winding down would cancel the retries too
+            if s.workers > 0 && cancelled {
+                drop(s);
+                self.push_toast("Wait for the cancelled downloads to stop".to_string(), ToastSeverity::Warning);
+                return;
+            }
+            s.failed_count = s.failed_count.saturating_sub(failed_maps.len());
+            for job in &failed_maps {
+                s.downloads.insert(job.idx, DownloadStatus::Pending);
+                s.finished_at.remove(&job.idx);
+                *s.retry_attempts.entry(job.idx).or_insert(0) += 1;
+                self.expanded_failures.remove(&job.idx);
+            }
+            // Workers still running pick the retries up from the shared queue
+            if s.workers > 0 {
+                s.queue.extend(failed_maps.iter().map(|job| job.idx));
+                s.jobs.extend(failed_maps.iter().map(|job| (job.idx, job.clone())));
+            } else {
+                s.run_start = (s.completed_count, s.cancelled_count);
+            }
+            s.workers > 0
+        };
+        debug!(count = failed_maps.len(), force, "Retrying failed downloads");
+        if workers_running {
+            return;
+        }
+
         let cancel_token = CancellationToken::new();
         self.cancel_token = Some(cancel_token.clone());
+        spawn_download_batch(failed_maps, self.download_state.clone(), cancel_token, ctx.clone(), &self.runtime);
+    }
 
-        {
-            let mut s = self.download_state.lock().unwrap();
-            s.failed_count = 0;
-            for &(idx, _, _, _, _) in &failed_maps {
-                s.downloads.insert(idx, DownloadStatus::Pending);
+    /// Take a map that hasn't started yet out of the running batch
+    pub fn cancel_queued_download(&mut self, idx: usize) {
+        let Some(map) = self.maps.get(idx) else {
+            return;
+        };
+        let (bytes, companions) = if self.download_companion_files {
+            let extra: u64 = map.companions.iter().map(|c| c.size.max(0) as u64).sum();
+            (map.size.max(0) as u64 + extra, map.companions.len())
+        } else {
+            (map.size.max(0) as u64, 0)
+        };
+        if self.download_state.lock().unwrap().cancel_queued(idx, bytes, companions) {
+            debug!(map = %map.name, "Removed map from download queue");
+        }
+    }
+
+    /// Format failed downloads as Markdown, ready to paste into a GitHub issue.
+    pub fn failure_report(&self, indices: &[usize]) -> String {
+        let s = self.download_state.lock().unwrap();
+        let entries: Vec<String> = indices
+            .iter()
+            .filter_map(|&idx| {
+                let Some(DownloadStatus::Failed(err)) = s.downloads.get(&idx) else {
+                    return None;
+                };
+                let map = self.maps.get(idx)?;
+                Some(format!(
+                    "**{}** ({}, {}★)\n```\n{}\n```",
+                    map.name, map.category, map.stars, err
+                ))
+            })
+            .collect();
+        format!(
+            "### Failed downloads ({})\nApp version: {}\n\n{}",
+            entries.len(),
+            APP_VERSION,
+            entries.join("\n\n")
+        )
+    }
+
+    /// Detect the end of a batch: play the completion sound and push a summary toast.
+    /// Runs every frame so it fires even when the download modal is hidden.
+    pub fn poll_download_completion(&mut self) {
+        let command_failures = std::mem::take(&mut self.download_state.lock().unwrap().command_failures);
+        if let Some(first) = command_failures.first() {
+            let message = match command_failures.len() {
+                1 => format!("Post-download command failed: {}", first),
+                n => format!("Post-download command failed for {} maps: {}", n, first),
+            };
+            self.push_toast(message, ToastSeverity::Error);
+        }
+        let (is_downloading, completed, failed, skipped, run_completed, run_cancelled) = {
+            let s = self.download_state.lock().unwrap();
+            let active = s.active_count > 0
+                || s.downloads.values().any(|st| matches!(st, DownloadStatus::Pending));
+            let (completed_before, cancelled_before) = s.run_start;
+            (
+                active,
+                s.completed_count,
+                s.failed_count,
+                s.skipped_count,
+                s.completed_count.saturating_sub(completed_before),
+                s.cancelled_count.saturating_sub(cancelled_before),
+            )
+        };
+        // New failures get a sound, throttled so a dead mirror doesn't spam it
+        if failed > self.seen_failed_count && self.sound_on_failure {
+            let quiet = self
+                .failure_sound_at
+                .is_none_or(|at| at.elapsed() >= FAILURE_SOUND_COOLDOWN);
+            if quiet {
+                self.play_notification(true);
+                self.failure_sound_at = Some(std::time::Instant::now());
             }
         }
+        self.seen_failed_count = failed;
 
-        spawn_download_batch(failed_maps, self.download_state.clone(), cancel_token, ctx.clone(), &self.runtime);
+        let finished = self.was_downloading && !is_downloading;
+        self.was_downloading = is_downloading;
+        if !finished {
+            return;
+        }
+
+        if self.play_sound_on_complete {
+            self.play_notification(false);
+        }
+        // Pick up the new files for the downloaded filter and count
+        self.rescan_downloaded();
+
+        // Nothing to report for a batch that was cancelled before anything finished
+        if completed + failed + skipped == 0 {
+            return;
+        }
+        info!(completed, failed, skipped, "Download batch finished");
+
+        // Only after a run that went to the end and produced at least one map, so
+        // retrying the failures of an earlier batch doesn't count its maps again
+        if self.open_folder_after_download && run_completed > 0 && run_cancelled == 0 {
+            if let Err(e) = open::that(&self.download_path) {
+                warn!(error = %e, "Failed to open download folder");
+            }
+        }
+
+        let mut details = Vec::new();
+        if failed > 0 {
+            details.push(format!("{} failed", failed));
+        }
+        if skipped > 0 {
+            details.push(format!("{} skipped", skipped));
+        }
+        let mut message = format!(
+            "Downloaded {} map{}",
+            completed,
+            if completed == 1 { "" } else { "s" }
+        );
+        if !details.is_empty() {
+            message.push_str(&format!(" ({})", details.join(", ")));
+        }
+
+        let (severity, filter) = match (completed, failed) {
+            (_, 0) => (ToastSeverity::Success, None),
+            (0, _) => (ToastSeverity::Error, Some("failed")),
+            _ => (ToastSeverity::Warning, Some("failed")),
+        };
+        let retry = (failed > 0).then_some(ToastAction::RetryFailed);
+        self.push_toast_with_action(message, severity, Some(ToastAction::OpenDownloads(filter)), retry);
+    }
+
+    /// Whether any map in the current batch is downloading or still queued
+    pub fn downloads_in_progress(&self) -> bool {
+        let s = self.download_state.lock().unwrap();
+        s.active_count > 0 || s.downloads.values().any(|st| matches!(st, DownloadStatus::Pending))
+    }
+
+    /// Re-check that `download_path` is reachable, off the UI thread since a dead
+    /// network drive can block for seconds. Runs on a timer, or right away after
+    /// the path changes (`check_download_path_now`).
+    pub fn poll_download_path(&mut self, ctx: &egui::Context) {
+        if let Some((path, ok)) = ctx.memory(|mem| mem.data.get_temp::<(PathBuf, bool)>("download_path_check".into())) {
+            ctx.memory_mut(|mem| mem.data.remove::<(PathBuf, bool)>("download_path_check".into()));
+            self.download_path_check_pending = false;
+            // A result for a path that was since replaced is stale
+            if path == self.download_path && ok != self.download_path_ok {
+                if ok {
+                    info!(path = %path.display(), "Download folder reachable again");
+                } else {
+                    warn!(path = %path.display(), "Download folder unreachable");
+                }
+                self.download_path_ok = ok;
+                // Downloaded/not-downloaded filters depend on the folder
+                self.rescan_downloaded();
+            }
+        }
+
+        let due = self
+            .download_path_checked_at
+            .is_none_or(|at| at.elapsed() >= DOWNLOAD_PATH_CHECK_INTERVAL);
+        if due && !self.download_path_check_pending {
+            self.download_path_check_pending = true;
+            self.download_path_checked_at = Some(std::time::Instant::now());
+            let path = self.download_path.clone();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                let ok = crate::utils::download_path_reachable(&path);
+                ctx.memory_mut(|mem| mem.data.insert_temp("download_path_check".into(), (path, ok)));
+                ctx.request_repaint();
+            });
+        }
+        // Keep checking while unreachable so a reconnected drive is noticed without input
+        if !self.download_path_ok {
+            ctx.request_repaint_after(DOWNLOAD_PATH_CHECK_INTERVAL);
+        }
+    }
+
+    /// Check the download folder on the next frame instead of waiting for the timer,
+    /// and rescan it for downloaded maps (call after the path changes)
+    pub fn check_download_path_now(&mut self) {
+        self.download_path_checked_at = None;
+        self.rescan_downloaded();
+    }
+
+    /// Stop or resume starting queued maps (files in flight always finish)
+    pub fn toggle_downloads_paused(&mut self) {
+        let mut s = self.download_state.lock().unwrap();
+        s.paused = !s.paused;
+        info!(paused = s.paused, "Toggled download pause");
+    }
+
+    /// Intercept window close while downloads run: hide to the tray when enabled,
+    /// otherwise ask first. Also closes the window once a drain (finish current
+    /// files, then exit) completes.
+    pub fn handle_close_request(&mut self, ctx: &egui::Context) {
+        if self.exit_confirmed {
+            return;
+        }
+        let in_progress = self.downloads_in_progress();
+        if self.exit_when_idle && !in_progress {
+            info!("Downloads drained, exiting");
+            self.exit_confirmed = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+        if ctx.input(|i| i.viewport().close_requested()) && in_progress {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            if self.tray.is_shown() {
+                info!("Hiding to tray while downloads continue");
+                self.hidden_to_tray = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+            } else {
+                self.show_exit_confirm = true;
+            }
+        }
+    }
+
+    /// Create/remove the tray icon to match the setting and act on its menu
+    pub fn poll_tray(&mut self, ctx: &egui::Context) {
+        self.tray.set_enabled(self.minimize_to_tray && Tray::supported());
+        let (progress, paused) = {
+            let s = self.download_state.lock().unwrap();
+            let running = s.active_count > 0 || s.downloads.values().any(|st| matches!(st, DownloadStatus::Pending));
+            let finished = s.completed_count + s.skipped_count + s.failed_count + s.cancelled_count;
+            (running.then_some((finished, s.total_queued)), s.paused)
+        };
+        self.tray.set_status(progress, paused);
+
+        while let Some(command) = self.tray.poll() {
+            match command {
+                TrayCommand::Open => self.restore_from_tray(ctx),
+                TrayCommand::TogglePause => self.toggle_downloads_paused(),
+                TrayCommand::SyncDatabase => {
+                    if !self.update_in_progress {
+                        self.perform_db_update(ctx);
+                    }
+                }
+                TrayCommand::Quit => {
+                    // Settings are saved in on_exit like any other close
+                    info!("Quit from tray");
+                    if let Some(token) = &self.cancel_token {
+                        token.cancel();
+                    }
+                    self.exit_confirmed = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+            }
+        }
+
+        // Hidden windows only repaint on request; keep polling so the tooltip
+        // and completion toast stay current
+        if self.hidden_to_tray {
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        }
+    }
+
+    /// Bring the window back from the tray with the search box focused
+    pub fn restore_from_tray(&mut self, ctx: &egui::Context) {
+        self.hidden_to_tray = false;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        self.focus_search = true;
+    }
+
+    /// Cancel everything and close. Maps are only written once fully received,
+    /// so cancelled downloads leave no partial files behind.
+    pub fn cancel_downloads_and_exit(&mut self, ctx: &egui::Context) {
+        if let Some(token) = &self.cancel_token {
+            token.cancel();
+        }
+        info!("Exit confirmed, cancelling downloads");
+        self.show_exit_confirm = false;
+        self.exit_confirmed = true;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+
+    /// Called from `on_exit`: cancel whatever still runs and give the workers a moment
+    /// to stop, so no write is cut off when the runtime is dropped. Temp files left
+    /// by an interrupted write (see `write_atomic`) are removed.
+    pub fn shutdown_downloads(&mut self) {
+        let Some(token) = &self.cancel_token else {
+            return;
+        };
+        token.cancel();
+        let deadline = std::time::Instant::now() + SHUTDOWN_GRACE;
+        loop {
+            // Workers outlive their downloads while a post-download command runs
+            let (active, workers) = {
+                let s = self.download_state.lock().unwrap();
+                (s.active_count, s.workers)
+            };
+            if active == 0 && workers == 0 {
+                break;
+            }
+            if std::time::Instant::now() >= deadline {
+                warn!(active, "Downloads still running at exit");
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+
+        let leftovers: Vec<PathBuf> = std::fs::read_dir(&self.download_path)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.to_string_lossy().ends_with(".map.tmp"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        for path in leftovers {
+            match std::fs::remove_file(&path) {
+                Ok(()) => debug!(file = %path.display(), "Removed partial map"),
+                Err(e) => warn!(file = %path.display(), error = %e, "Failed to remove partial map"),
+            }
+        }
+    }
+
+    /// Stop starting new files, let the ones in flight finish, then exit
+    pub fn drain_downloads_then_exit(&mut self) {
+        let mut s = self.download_state.lock().unwrap();
+        let remaining: Vec<usize> = s.queue.drain(..).collect();
+        for idx in &remaining {
+            s.finish(*idx, DownloadStatus::Cancelled);
+        }
+        s.cancelled_count += remaining.len();
+        info!(active = s.active_count, dropped = remaining.len(), "Draining downloads before exit");
+        self.exit_when_idle = true;
+    }
+
+    /// HEAD every map URL in `order` (bounded concurrency) before downloading,
+    /// so maps whose category/star path doesn't exist can be reviewed first.
+    /// Under `CollisionStrategy::Skip`, maps already on disk won't be fetched and
+    /// aren't checked; that lookup runs on the runtime, not the UI thread.
+    fn start_url_precheck(&mut self, ctx: &egui::Context, order: Vec<usize>) {
+        let collision = self.batch_collision.unwrap_or(self.collision_strategy);
+        let skip_existing = collision == CollisionStrategy::Skip;
+        let targets: Vec<(usize, String, PathBuf)> = order
+            .iter()
+            .filter_map(|&idx| {
+                let map = self.maps.get(idx)?;
+                let dest = self.download_path.join(format!("{}.map", map.name));
+                Some((idx, self.get_map_url(map), dest))
+            })
+            .collect();
+        if targets.is_empty() {
+            self.start_download_batch(ctx, order);
+            return;
+        }
+
+        info!(count = targets.len(), skip_existing, "Prechecking map URLs");
+        self.push_toast_with_button(
+            format!("Checking links for {} map{}…", targets.len(), if targets.len() == 1 { "" } else { "s" }),
+            ToastSeverity::Info,
+            ToastAction::CancelPrecheck,
+        );
+        self.precheck_order = Some(order);
+        let token = CancellationToken::new();
+        self.precheck_cancel = Some(token.clone());
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let client = reqwest::Client::builder()
+                .timeout(PRECHECK_TIMEOUT)
+                .build()
+                .unwrap_or_default();
+            let semaphore = Arc::new(tokio::sync::Semaphore::new(PRECHECK_CONCURRENCY));
+            let checks = targets.into_iter().map(|(idx, url, dest)| {
+                let client = client.clone();
+                let semaphore = semaphore.clone();
+                async move {
+                    let _permit = semaphore.acquire().await.ok();
+                    if skip_existing && tokio::fs::try_exists(&dest).await.unwrap_or(false) {
+                        return None;
+                    }
+                    let reason = match client.head(&url).send().await {
+                        Ok(r) if r.status().is_success() => return None,
+                        Ok(r) => format!("HTTP {}", r.status()),
+                        Err(e) => describe_error(&e, &url).lines().next().unwrap_or_default().to_string(),
+                    };
+                    Some((idx, reason))
+                }
+            });
+            let dead: Vec<(usize, String)> = tokio::select! {
+                _ = token.cancelled() => return,
+                results = futures::future::join_all(checks) => results.into_iter().flatten().collect(),
+            };
+            debug!(dead = dead.len(), "URL precheck finished");
+            ctx.memory_mut(|mem| mem.data.insert_temp("url_precheck_done".into(), dead));
+            ctx.request_repaint();
+        });
+    }
+
+    /// Start the batch once the precheck is back, or ask about likely-dead links
+    pub fn poll_url_precheck(&mut self, ctx: &egui::Context) {
+        let Some(dead) =
+            ctx.memory(|mem| mem.data.get_temp::<Vec<(usize, String)>>("url_precheck_done".into()))
+        else {
+            return;
+        };
+        ctx.memory_mut(|mem| mem.data.remove::<Vec<(usize, String)>>("url_precheck_done".into()));
+        self.precheck_cancel = None;
+        if self.precheck_order.is_none() {
+            return;
+        }
+        if dead.is_empty() {
+            if let Some(order) = self.precheck_order.take() {
+                self.start_download_batch(ctx, order);
+            }
+        } else {
+            self.precheck_dead = dead;
+        }
+    }
+
+    /// Stop a running URL precheck; its batch isn't started
+    pub fn cancel_url_precheck(&mut self) {
+        let Some(token) = self.precheck_cancel.take() else {
+            return;
+        };
+        token.cancel();
+        self.precheck_order = None;
+        self.batch_collision = None;
+        info!("URL precheck cancelled");
+        self.push_toast("Link check cancelled, nothing was downloaded".to_string(), ToastSeverity::Info);
+    }
+
+    /// Resolve the precheck dialog: download everything, or leave out the dead links
+    pub fn finish_url_precheck(&mut self, ctx: &egui::Context, include_dead: bool) {
+        let Some(mut order) = self.precheck_order.take() else {
+            return;
+        };
+        if !include_dead {
+            let dead: HashSet<usize> = self.precheck_dead.iter().map(|(idx, _)| *idx).collect();
+            order.retain(|idx| !dead.contains(idx));
+        }
+        self.precheck_dead.clear();
+        self.start_download_batch(ctx, order);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_and_overwrite_keep_the_destination() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("Map.map");
+        std::fs::write(&dest, b"old").unwrap();
+        for collision in [CollisionStrategy::Skip, CollisionStrategy::Overwrite] {
+            assert_eq!(resolve_collision(dest.clone(), b"new", collision), (dest.clone(), false));
+        }
+    }
+
+    #[test]
+    fn rename_keeps_identical_or_missing_files_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("Map.map");
+        assert_eq!(resolve_collision(dest.clone(), b"new", CollisionStrategy::Rename), (dest.clone(), false));
+        std::fs::write(&dest, b"same").unwrap();
+        assert_eq!(resolve_collision(dest.clone(), b"same", CollisionStrategy::Rename), (dest.clone(), false));
+    }
+
+    #[test]
+    fn rename_picks_the_first_free_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("Map.map");
+        std::fs::write(&dest, b"old").unwrap();
+        let first = dir.path().join("Map (1).map");
+        assert_eq!(resolve_collision(dest.clone(), b"new", CollisionStrategy::Rename), (first.clone(), true));
+
+        // "Map (1).map" already holds other content
+        std::fs::write(&first, b"other").unwrap();
+        let second = dir.path().join("Map (2).map");
+        assert_eq!(resolve_collision(dest.clone(), b"new", CollisionStrategy::Rename), (second, true));
+    }
+
+    #[test]
+    fn rename_reuses_a_renamed_copy_with_the_same_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("Map.map");
+        std::fs::write(&dest, b"old").unwrap();
+        let first = dir.path().join("Map (1).map");
+        std::fs::write(&first, b"new").unwrap();
+        assert_eq!(resolve_collision(dest, b"new", CollisionStrategy::Rename), (first, true));
     }
 }
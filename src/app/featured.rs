@@ -0,0 +1,118 @@
+//! Map of the day: a banner over the map list on the first start of each day
+
+use super::App;
+use crate::theme;
+use crate::ui::components::render_stars;
+use eframe::egui;
+
+impl App {
+    /// Feature a map if the banner is enabled and hasn't appeared today. The pick
+    /// depends only on the date and the map list, so it's the same all day.
+    pub fn pick_map_of_the_day(&mut self) {
+        let today = chrono::Local::now().date_naive();
+        let date = today.format("%Y-%m-%d").to_string();
+        if !self.map_of_the_day || self.map_of_the_day_shown.as_deref() == Some(date.as_str()) {
+            return;
+        }
+        // Maps come sorted by name, so the same day picks the same one
+        let candidates: Vec<&str> = self
+            .maps
+            .iter()
+            .filter(|m| m.removed_at.is_none())
+            .map(|m| m.name.as_str())
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+        let seed = (chrono::Datelike::num_days_from_ce(&today) as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        let pick = (seed >> 32) as usize % candidates.len();
+        self.featured_map = Some(candidates[pick].to_string());
+        self.map_of_the_day_shown = Some(date);
+    }
+
+    /// Featured map with Preview and Download, until dismissed
+    pub fn render_map_of_the_day_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(name) = self.featured_map.clone() else {
+            return;
+        };
+        let Some(idx) = self.maps.iter().position(|m| m.name == name) else {
+            self.featured_map = None;
+            return;
+        };
+        let thumbnail = self.load_thumbnail(&ui.ctx().clone(), &name);
+        let map = &self.maps[idx];
+        let details = format!("{} · {} · by {}", map.category, render_stars(map.stars), map.author);
+        let downloaded = self.downloaded_names.contains(&name);
+        let busy = self.downloads_in_progress();
+        let mut preview = false;
+        let mut download = false;
+        let mut dismiss = false;
+
+        egui::Frame::new()
+            .fill(theme::with_alpha(theme::accent(), 20))
+            .stroke(egui::Stroke::new(1.0, theme::with_alpha(theme::accent(), 90)))
+            .corner_radius(6.0)
+            .inner_margin(egui::Margin::symmetric(10, 8))
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| {
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(64.0, 36.0), egui::Sense::hover());
+                    match &thumbnail {
+                        Some(texture) => {
+                            egui::Image::from_texture(texture).corner_radius(3.0).paint_at(ui, rect);
+                        }
+                        None => {
+                            ui.painter().rect_filled(rect, 3.0, theme::BG_ELEVATED);
+                        }
+                    }
+                    // Leave room for the buttons on the right
+                    let text_width = (ui.available_width() - 240.0).max(80.0);
+                    ui.vertical(|ui| {
+                        ui.set_max_width(text_width);
+                        ui.add(egui::Label::new(
+                            egui::RichText::new(format!("{}  MAP OF THE DAY", egui_phosphor::regular::STAR))
+                                .size(11.0)
+                                .color(theme::accent()),
+                        ).selectable(false));
+                        ui.add(egui::Label::new(egui::RichText::new(&name).strong()).truncate().selectable(false));
+                        ui.add(egui::Label::new(
+                            egui::RichText::new(&details).size(12.0).color(theme::TEXT_MUTED),
+                        ).truncate().selectable(false));
+                    });
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        dismiss = ui
+                            .add(egui::Button::new(egui_phosphor::regular::X).frame(false))
+                            .on_hover_text("Dismiss until tomorrow")
+                            .clicked();
+                        let label = if downloaded {
+                            format!("{}  Downloaded", egui_phosphor::regular::CHECK)
+                        } else {
+                            format!("{}  Download", egui_phosphor::regular::DOWNLOAD_SIMPLE)
+                        };
+                        download = ui
+                            .add_enabled(!downloaded && !busy, theme::button_accent(label))
+                            .on_disabled_hover_text(if downloaded {
+                                "Already in your download folder"
+                            } else {
+                                "Wait for the current downloads to finish"
+                            })
+                            .clicked();
+                        preview = ui
+                            .add(theme::button(format!("{}  Preview", egui_phosphor::regular::EYE)))
+                            .clicked();
+                    });
+                });
+            });
+
+        let ctx = ui.ctx().clone();
+        if preview {
+            self.open_preview_multi(&ctx, vec![name]);
+        }
+        if download {
+            self.download_indices(&ctx, vec![idx]);
+        }
+        if dismiss {
+            self.featured_map = None;
+        }
+    }
+}
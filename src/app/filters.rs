@@ -1,13 +1,710 @@
 //! Filtering and sorting logic
 
 use super::App;
+use crate::authors::AuthorAliases;
+use crate::db::Map;
+use crate::constants::{ASYNC_SEARCH_THRESHOLD, SEARCH_DEBOUNCE};
 use crate::types::*;
+use eframe::egui;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Memory key for a finished background search: (generation, priority per map)
+const SEARCH_SCORES_KEY: &str = "search_scores";
+
+/// Search result sections in order, as (label, jump marker); see `match_tier`
+const MATCH_TIERS: [(&str, &str); 3] = [("Name matches", "NM"), ("Author matches", "AU"), ("Other matches", "OT")];
+
+/// Per-option match counts shown on the filter buttons: how many maps each
+/// option would show given all the other active filters
+#[derive(Default)]
+pub struct FilterCounts {
+    pub categories: [usize; 8],
+    pub stars: [usize; 5],
+    pub years: HashMap<i32, usize>,
+}
+
+/// The sidebar filter settings, stashed by "Search everywhere" while its search lasts
+#[derive(Clone, PartialEq)]
+pub struct SidebarFilters {
+    categories: [bool; 8],
+    category_mode_range: bool,
+    category_range: (u8, u8),
+    stars: [bool; 5],
+    stars_mode_range: bool,
+    stars_range: (u8, u8),
+    per_category_stars: bool,
+    downloaded: u8,
+    year_mode_range: bool,
+    year_range: Option<(i32, i32)>,
+    month_range: Option<(i32, i32)>,
+    include_undated: bool,
+    years: HashSet<i32>,
+}
+
+impl SidebarFilters {
+    /// What "Clear Filters" sets: every category, star count and year
+    fn cleared(available_years: &[i32]) -> Self {
+        Self {
+            categories: [true; 8],
+            category_mode_range: true,
+            category_range: (0, 4),
+            stars: [true; 5],
+            stars_mode_range: true,
+            stars_range: (1, 5),
+            per_category_stars: false,
+            downloaded: 0,
+            year_mode_range: true,
+            year_range: None,
+            month_range: None,
+            include_undated: true,
+            years: available_years.iter().copied().collect(),
+        }
+    }
+}
+
+/// A search query split into plain text and numeric field filters.
+/// Tokens that don't parse as a filter stay part of the text.
+#[derive(Default, Clone)]
+struct ParsedQuery {
+    text: String,
+    /// Words of `text` as typed and lowercased ("quoted phrases" stay whole)
+    terms: Vec<(String, String)>,
+    points: Option<(i32, i32)>,
+    stars: Option<(i32, i32)>,
+    /// `author:Name` / `author:"Full Name"`: exact author, any case
+    author: Option<String>,
+    case_sensitive: bool,
+    whole_word: bool,
+}
+
+impl ParsedQuery {
+    fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut words = Vec::new();
+        for (token, quoted) in tokenize(query) {
+            if quoted {
+                words.push(token);
+                continue;
+            }
+            let lower = token.to_ascii_lowercase();
+            if lower.starts_with("author:") {
+                let value = &token["author:".len()..];
+                let name = match value.strip_prefix('"') {
+                    Some(quoted) => unescape_quoted(quoted.strip_suffix('"').unwrap_or(quoted)),
+                    None => value.to_string(),
+                };
+                if !name.trim().is_empty() {
+                    parsed.author = Some(name.trim().to_lowercase());
+                    continue;
+                }
+            }
+            let (field, rest) = if let Some(rest) = lower.strip_prefix("points") {
+                (Some(true), rest)
+            } else if let Some(rest) = lower.strip_prefix("pts") {
+                (Some(true), rest)
+            } else if let Some(rest) = lower.strip_prefix("stars") {
+                (Some(false), rest)
+            } else if lower.starts_with(['>', '<']) {
+                (Some(true), lower.as_str()) // bare comparison means points
+            } else {
+                (None, "")
+            };
+            let bounds = field.and_then(|_| parse_bounds(rest));
+            match (field, bounds) {
+                (Some(true), Some(b)) => parsed.points = Some(intersect(parsed.points, b)),
+                (Some(false), Some(b)) => parsed.stars = Some(intersect(parsed.stars, b)),
+                _ => words.push(token),
+            }
+        }
+        parsed.terms = words.iter().map(|w| (w.to_string(), w.to_lowercase())).collect();
+        parsed.text = words.join(" ");
+        parsed
+    }
+
+    fn matches(&self, m: &Map) -> bool {
+        let within = |range: Option<(i32, i32)>, v: i32| range.is_none_or(|(lo, hi)| v >= lo && v <= hi);
+        let by_author = self.author.as_ref().is_none_or(|wanted| {
+            std::iter::once(&m.author).chain(&m.authors).any(|a| a.to_lowercase() == *wanted)
+        });
+        within(self.points, m.points) && within(self.stars, m.stars) && by_author
+    }
+
+    /// Whether `needle` occurs in `haystack`, only between word boundaries when
+    /// whole-word matching is on
+    fn contains(&self, haystack: &str, needle: &str) -> bool {
+        if !self.whole_word {
+            return haystack.contains(needle);
+        }
+        haystack.match_indices(needle).any(|(start, found)| {
+            let before = haystack[..start].chars().next_back();
+            let after = haystack[start + found.len()..].chars().next();
+            !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+        })
+    }
+
+    /// Relevance of `query` as one match in the name or authors (lower is better):
+    /// exact-case name, exact-case author, then (unless case-sensitive) any-case name and author
+    fn text_priority(&self, m: &Map, query: &str, query_lower: &str) -> Option<u8> {
+        let authors = || std::iter::once(&m.author).chain(&m.authors);
+        if self.contains(&m.name, query) {
+            return Some(0);
+        }
+        if authors().any(|a| self.contains(a, query)) {
+            return Some(1);
+        }
+        if self.case_sensitive {
+            return None;
+        }
+        if self.contains(&m.name.to_lowercase(), query_lower) {
+            return Some(2);
+        }
+        if authors().any(|a| self.contains(&a.to_lowercase(), query_lower)) {
+            return Some(3);
+        }
+        None
+    }
+}
+
+/// Search text that filters to exactly `author` (see `ParsedQuery::author`),
+/// quoted with `\"` and `\\` escapes so any name survives the round trip
+pub(crate) fn author_query(author: &str) -> String {
+    format!("author:\"{}\"", author.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Undo the escapes `author_query` adds: `\x` stands for `x`
+fn unescape_quoted(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        out.push(if c == '\\' { chars.next().unwrap_or(c) } else { c });
+    }
+    out
+}
+
+/// Split on whitespace, keeping "quoted phrases" as one token (flagged `true`).
+/// A quote right after `field:` (`author:"Full Name"`) stays part of that token,
+/// up to the next quote not escaped with `\`.
+/// An unclosed quote runs to the end of the query.
+fn tokenize(query: &str) -> Vec<(&str, bool)> {
+    let mut tokens = Vec::new();
+    let mut rest = query.trim_start();
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted.find('"').unwrap_or(quoted.len());
+            let phrase = quoted[..end].trim();
+            if !phrase.is_empty() {
+                tokens.push((phrase, true));
+            }
+            rest = quoted.get(end + 1..).unwrap_or("");
+        } else {
+            let mut end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            if let Some(open) = rest[..end].find(":\"") {
+                let value = open + 2;
+                let mut escaped = false;
+                let close = rest[value..].char_indices().find(|&(_, c)| {
+                    let closes = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    closes
+                });
+                end = close.map_or(rest.len(), |(close, _)| value + close + 1);
+            }
+            tokens.push((&rest[..end], false));
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start();
+    }
+    tokens
+}
+
+/// Parse `>=N`, `<=N`, `>N`, `<N`, `=N`, `:N` or `:N-M` into inclusive bounds
+fn parse_bounds(s: &str) -> Option<(i32, i32)> {
+    let num = |v: &str| v.trim().parse::<i32>().ok();
+    if let Some(v) = s.strip_prefix(">=") {
+        Some((num(v)?, i32::MAX))
+    } else if let Some(v) = s.strip_prefix("<=") {
+        Some((i32::MIN, num(v)?))
+    } else if let Some(v) = s.strip_prefix('>') {
+        Some((num(v)?.saturating_add(1), i32::MAX))
+    } else if let Some(v) = s.strip_prefix('<') {
+        Some((i32::MIN, num(v)?.saturating_sub(1)))
+    } else if let Some(v) = s.strip_prefix(':').or_else(|| s.strip_prefix('=')) {
+        match v.split_once('-') {
+            Some((lo, hi)) => {
+                let (lo, hi) = (num(lo)?, num(hi)?);
+                Some((lo.min(hi), lo.max(hi)))
+            }
+            None => num(v).map(|n| (n, n)),
+        }
+    } else {
+        None
+    }
+}
+
+/// Names of the .map files directly in `dir`, from one directory listing instead
+/// of a stat per map. An unreadable folder has none.
+fn scan_map_names(dir: &std::path::Path) -> HashSet<String> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "map") && path.is_file())
+                .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Combine repeated filters on the same field, e.g. `points>=100 points<=500`
+fn intersect(current: Option<(i32, i32)>, (lo, hi): (i32, i32)) -> (i32, i32) {
+    match current {
+        Some((cur_lo, cur_hi)) => (cur_lo.max(lo), cur_hi.min(hi)),
+        None => (lo, hi),
+    }
+}
 
 impl App {
+    /// Fill each map's canonical author list from the shipped and user alias tables
+    pub fn normalize_authors(&mut self) {
+        self.search_corpus = None;
+        let aliases = AuthorAliases::new(&self.author_aliases);
+        for map in &mut self.maps {
+            map.authors = aliases.normalize(&map.author);
+        }
+    }
+
+    /// Select the named maps and scroll to the first one the filters show
+    pub fn show_maps(&mut self, names: &[String]) {
+        let wanted: HashSet<&str> = names.iter().map(String::as_str).collect();
+        self.remember_selection();
+        self.selected_indices = (0..self.maps.len()).filter(|&i| wanted.contains(self.maps[i].name.as_str())).collect();
+        self.last_selected = None;
+        match self.filtered_indices.iter().position(|i| self.selected_indices.contains(i)) {
+            Some(row) => self.scroll_target_row = Some(row),
+            None if !self.selected_indices.is_empty() => {
+                self.push_toast("The new maps are hidden by the current filters", ToastSeverity::Info);
+            }
+            None => {}
+        }
+    }
+
+    /// Select a map and scroll it into view, or explain why it can't be shown
+    pub fn jump_to_map(&mut self, map_idx: usize) {
+        let Some(mut row) = self.filtered_indices.iter().position(|&i| i == map_idx) else {
+            let name = self.maps.get(map_idx).map(|m| m.name.clone()).unwrap_or_default();
+            self.push_toast(format!("{} is hidden by the current filters", name), ToastSeverity::Info);
+            return;
+        };
+        if self.list_pos_hidden(row) {
+            // Expand its group so there is a row to scroll to
+            self.collapsed_groups.remove(&self.group_of(map_idx).1);
+            self.apply_filters();
+            row = self.filtered_indices.iter().position(|&i| i == map_idx).unwrap_or(0);
+        }
+        self.remember_selection();
+        self.selected_indices.clear();
+        self.selected_indices.insert(map_idx);
+        self.last_selected = Some(map_idx);
+        self.scroll_target_row = Some(row);
+    }
+
+    /// Apply a `--filter` spec such as `category=Insane,Solo stars=1..2 status=not-downloaded`.
+    /// Other tokens (including `points>=...`) become the search query. Nothing is
+    /// changed unless the whole spec is valid.
+    pub fn apply_filter_spec(&mut self, spec: &str) -> Result<(), String> {
+        let mut categories: Option<[bool; 8]> = None;
+        let mut stars = None;
+        let mut status = None;
+        let mut years = None;
+        let mut search = Vec::new();
+        for (token, quoted) in tokenize(spec) {
+            let Some((key, value)) = token.split_once('=').filter(|_| !quoted) else {
+                search.push(if quoted { format!("\"{}\"", token) } else { token.to_string() });
+                continue;
+            };
+            // Ranges accept 1..2 as well as the search syntax's 1-2
+            let range = || parse_bounds(&format!("={}", value.replace("..", "-")));
+            match key.to_ascii_lowercase().as_str() {
+                "category" | "cat" => {
+                    let mut picked = [false; 8];
+                    for name in value.split(',') {
+                        let idx = Self::CATEGORY_NAMES
+                            .iter()
+                            .position(|c| c.eq_ignore_ascii_case(name.trim()))
+                            .ok_or_else(|| format!("unknown category '{}'", name))?;
+                        picked[idx] = true;
+                    }
+                    categories = Some(picked);
+                }
+                "stars" => {
+                    let (lo, hi) = range().ok_or_else(|| format!("invalid star range '{}'", value))?;
+                    if lo < 1 || hi > 5 {
+                        return Err(format!("stars must be between 1 and 5, got '{}'", value));
+                    }
+                    stars = Some((lo as u8, hi as u8));
+                }
+                "status" => {
+                    status = Some(match value.to_ascii_lowercase().as_str() {
+                        "all" => 0,
+                        "downloaded" => 1,
+                        "not-downloaded" | "missing" => 2,
+                        "outdated" => 3,
+                        "removed" => 4,
+                        _ => return Err(format!("unknown status '{}'", value)),
+                    });
+                }
+                "year" => years = Some(range().ok_or_else(|| format!("invalid year range '{}'", value))?),
+                "points" | "pts" => search.push(token.to_string()),
+                _ => return Err(format!("unknown filter '{}'", key)),
+            }
+        }
+
+        if let Some(picked) = categories {
+            self.category_mode_range = false;
+            self.filter_categories = picked;
+        }
+        if let Some(range) = stars {
+            self.per_category_stars = false;
+            self.stars_mode_range = true;
+            self.stars_range = range;
+        }
+        if let Some(status) = status {
+            self.filter_downloaded = status;
+        }
+        if let Some(range) = years {
+            self.year_mode_range = true;
+            self.year_by_month = false;
+            self.year_range = Some(range);
+        }
+        if !search.is_empty() {
+            self.search_query = search.join(" ");
+        }
+        self.apply_filters();
+        Ok(())
+    }
+
+    /// Reload maps after a database update and re-run the filters
+    pub fn reload_maps(&mut self) {
+        if let Ok(maps) = self.db.get_all_maps() {
+            self.maps = maps;
+            self.search_corpus = None;
+            self.normalize_authors();
+            self.outdated_check_due = true;
+            self.apply_filters();
+        }
+    }
+
+    /// Forget maps the manifest dropped longer ago than the purge setting allows
+    pub fn purge_removed_maps(&mut self) {
+        if self.purge_removed_months == 0 {
+            return;
+        }
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        let before = now - i64::from(self.purge_removed_months) * 30 * 24 * 60 * 60;
+        match self.db.purge_removed(before) {
+            Ok(0) => {}
+            Ok(purged) => {
+                info!(purged, months = self.purge_removed_months, "Forgot maps removed from the manifest");
+                self.reload_maps();
+            }
+            Err(e) => warn!(error = %e, "Failed to purge removed maps"),
+        }
+    }
+
+    /// Rebuild the set of maps present in the download folder on the next frame.
+    /// Call after files may have changed on disk.
+    pub fn rescan_downloaded(&mut self) {
+        self.downloaded_scan_due = true;
+    }
+
+    /// Run a due rescan off the UI thread (a slow network drive would stall it) and
+    /// re-run the filters once it's back. One scan runs at a time; a rescan asked for
+    /// meanwhile starts after it.
+    /// The onboarding scan page waits for a scan started after its button was
+    /// pressed, so each result carries whether it was one.
+    pub fn poll_downloaded_scan(&mut self, ctx: &egui::Context) {
+        type ScanResult = (HashSet<String>, bool);
+        let result = ctx.memory(|mem| mem.data.get_temp::<ScanResult>("downloaded_scan".into()));
+        if let Some((names, for_onboarding)) = result {
+            ctx.memory_mut(|mem| mem.data.remove::<ScanResult>("downloaded_scan".into()));
+            self.downloaded_scan_running = false;
+            self.downloaded_names = names;
+            self.outdated_check_due = true;
+            self.apply_filters();
+            if for_onboarding {
+                self.onboarding_scan_pending = false;
+                self.onboarding_scanned = Some(self.downloaded_count);
+            }
+        }
+        if !self.downloaded_scan_due || self.downloaded_scan_running {
+            return;
+        }
+        self.downloaded_scan_due = false;
+        self.downloaded_scan_running = true;
+        let for_onboarding = self.onboarding_scan_pending;
+        let path = self.download_path.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let names = scan_map_names(&path);
+            ctx.memory_mut(|mem| mem.data.insert_temp("downloaded_scan".into(), (names, for_onboarding)));
+            ctx.request_repaint();
+        });
+    }
+
+    fn map_year(m: &Map) -> Option<i32> {
+        m.release_date.split('-').next().and_then(|y| y.parse::<i32>().ok())
+    }
+
+    /// Release month as YYYYMM, from a `YYYY-MM...` date
+    pub(crate) fn map_month(m: &Map) -> Option<i32> {
+        let mut parts = m.release_date.split('-');
+        let year = parts.next()?.parse::<i32>().ok()?;
+        let month = parts.next()?.get(..2)?.parse::<i32>().ok()?;
+        (1..=12).contains(&month).then_some(year * 100 + month)
+    }
+
+    fn sidebar_filters(&self) -> SidebarFilters {
+        SidebarFilters {
+            categories: self.filter_categories,
+            category_mode_range: self.category_mode_range,
+            category_range: self.category_range,
+            stars: self.filter_stars,
+            stars_mode_range: self.stars_mode_range,
+            stars_range: self.stars_range,
+            per_category_stars: self.per_category_stars,
+            downloaded: self.filter_downloaded,
+            year_mode_range: self.year_mode_range,
+            year_range: self.year_range,
+            month_range: self.month_range,
+            include_undated: self.include_undated,
+            years: self.filter_years.clone(),
+        }
+    }
+
+    fn set_sidebar_filters(&mut self, filters: SidebarFilters) {
+        self.filter_categories = filters.categories;
+        self.category_mode_range = filters.category_mode_range;
+        self.category_range = filters.category_range;
+        self.filter_stars = filters.stars;
+        self.stars_mode_range = filters.stars_mode_range;
+        self.stars_range = filters.stars_range;
+        self.per_category_stars = filters.per_category_stars;
+        self.filter_downloaded = filters.downloaded;
+        self.year_mode_range = filters.year_mode_range;
+        self.year_range = filters.year_range;
+        self.month_range = filters.month_range;
+        self.include_undated = filters.include_undated;
+        self.filter_years = filters.years;
+    }
+
+    /// Reset every sidebar filter (the search text is left alone)
+    pub fn clear_sidebar_filters(&mut self) {
+        self.set_sidebar_filters(SidebarFilters::cleared(&self.available_years));
+    }
+
+    /// Clear the sidebar filters for the current search only; they come back once
+    /// the search is cleared, unless changed in the meantime
+    pub fn search_everywhere(&mut self) {
+        let saved = self.sidebar_filters();
+        self.clear_sidebar_filters();
+        self.relaxed_filters.get_or_insert(saved);
+        self.apply_filters();
+    }
+
+    /// Downloaded filter - files found by the last rescan_downloaded. Maps the
+    /// manifest dropped only show under the Removed status.
+    fn passes_downloaded(&self, m: &Map) -> bool {
+        if (self.filter_downloaded == 4) != m.removed_at.is_some() {
+            return false;
+        }
+        let downloaded = self.downloaded_names.contains(&m.name);
+        match self.filter_downloaded {
+            1 => downloaded,
+            2 => !downloaded,
+            3 => self.outdated_names.contains(&m.name),
+            _ => true,
+        }
+    }
+
+    fn passes_year(&self, m: &Map) -> bool {
+        let Some(year) = Self::map_year(m) else {
+            return self.include_undated;
+        };
+        if self.year_mode_range && self.year_by_month {
+            let Some((min_month, max_month)) = self.month_range else {
+                return true;
+            };
+            // A year without a month can't be placed in the range
+            Self::map_month(m).map_or(self.include_undated, |month| (min_month..=max_month).contains(&month))
+        } else if self.year_mode_range {
+            self.year_range.is_none_or(|(min_year, max_year)| (min_year..=max_year).contains(&year))
+        } else {
+            self.filter_years.contains(&year)
+        }
+    }
+
+    fn passes_category(&self, m: &Map) -> bool {
+        let Some(cat_idx) = Self::category_index(&m.category) else {
+            return true;
+        };
+        if self.category_mode_range {
+            cat_idx <= 4
+                && (self.category_range.0..=self.category_range.1).contains(&(cat_idx as u8))
+        } else {
+            self.filter_categories[cat_idx]
+        }
+    }
+
+    fn passes_stars(&self, m: &Map) -> bool {
+        let stars = m.stars as u8;
+        if self.per_category_stars {
+            // Star counts mean different things per category; unknown categories pass
+            return Self::category_index(&m.category).is_none_or(|cat_idx| {
+                let (min, max) = self.category_stars[cat_idx];
+                (min..=max).contains(&stars)
+            });
+        }
+        if self.stars_mode_range {
+            (self.stars_range.0..=self.stars_range.1).contains(&stars)
+        } else {
+            !(1..=5).contains(&stars) || self.filter_stars[(stars - 1) as usize]
+        }
+    }
+
+    /// Search relevance (lower is better), or None if the search box excludes the map
+    fn search_priority(m: &Map, parsed: &ParsedQuery, query: &str, query_lower: &str) -> Option<u8> {
+        // Field operators from the search box (points>=N, stars:N, author:Name, ...)
+        if !parsed.matches(m) {
+            return None;
+        }
+        if query.is_empty() {
+            return Some(4);
+        }
+        if let Some(priority) = parsed.text_priority(m, query, query_lower) {
+            return Some(priority);
+        }
+        // Several words: each must appear somewhere in name, authors or category,
+        // ranked by how the first word matches
+        if parsed.terms.len() < 2 {
+            return None;
+        }
+        let haystack = format!("{} {} {} {}", m.name, m.author, m.authors.join(" "), m.category);
+        let all_match = if parsed.case_sensitive {
+            parsed.terms.iter().all(|(term, _)| parsed.contains(&haystack, term))
+        } else {
+            let haystack = haystack.to_lowercase();
+            parsed.terms.iter().all(|(_, lower)| parsed.contains(&haystack, lower))
+        };
+        if !all_match {
+            return None;
+        }
+        let (primary, primary_lower) = &parsed.terms[0];
+        Some(parsed.text_priority(m, primary, primary_lower).unwrap_or(4))
+    }
+
+    /// Re-run the filters after the search text changed. Large libraries score the
+    /// query on a background thread after a short debounce; a newer query cancels
+    /// the pass in flight, and `poll_search_scores` applies the result.
+    pub fn search_changed(&mut self, ctx: &egui::Context) {
+        let generation = self.search_generation.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.maps.len() <= ASYNC_SEARCH_THRESHOLD || self.search_query.trim().is_empty() {
+            self.search_pending = false;
+            self.apply_filters();
+            return;
+        }
+
+        let mut parsed = ParsedQuery::parse(&self.search_query);
+        parsed.case_sensitive = self.search_case_sensitive;
+        parsed.whole_word = self.search_whole_word;
+        let maps = &self.maps;
+        let corpus = self.search_corpus.get_or_insert_with(|| Arc::new(maps.clone())).clone();
+        let current = self.search_generation.clone();
+        let ctx = ctx.clone();
+        self.search_pending = true;
+
+        self.runtime.spawn(async move {
+            tokio::time::sleep(SEARCH_DEBOUNCE).await;
+            if current.load(Ordering::Relaxed) != generation {
+                return;
+            }
+            let scores = tokio::task::spawn_blocking(move || {
+                let query = parsed.text.clone();
+                let query_lower = query.to_lowercase();
+                let mut scores = Vec::with_capacity(corpus.len());
+                for chunk in corpus.chunks(1024) {
+                    if current.load(Ordering::Relaxed) != generation {
+                        return None;
+                    }
+                    scores.extend(chunk.iter().map(|m| Self::search_priority(m, &parsed, &query, &query_lower)));
+                }
+                Some(scores)
+            })
+            .await
+            .ok()
+            .flatten();
+            if let Some(scores) = scores {
+                ctx.memory_mut(|mem| mem.data.insert_temp(SEARCH_SCORES_KEY.into(), (generation, Arc::new(scores))));
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    /// Apply a finished background search if it's for the latest query
+    pub fn poll_search_scores(&mut self, ctx: &egui::Context) {
+        type Scores = (u64, Arc<Vec<Option<u8>>>);
+        let Some((generation, scores)) = ctx.memory(|mem| mem.data.get_temp::<Scores>(SEARCH_SCORES_KEY.into())) else {
+            return;
+        };
+        ctx.memory_mut(|mem| mem.data.remove::<Scores>(SEARCH_SCORES_KEY.into()));
+        if !self.search_pending || generation != self.search_generation.load(Ordering::Relaxed) {
+            return;
+        }
+        self.search_pending = false;
+        if scores.len() == self.maps.len() {
+            self.run_filters(Some(&scores));
+        } else {
+            // Maps were reloaded while it ran
+            self.apply_filters();
+        }
+    }
+
     pub fn apply_filters(&mut self) {
-        let query = self.search_query.trim();
+        // Scores the current query itself, so a pass in flight is no longer needed
+        if self.search_pending {
+            self.search_pending = false;
+            self.search_generation.fetch_add(1, Ordering::Relaxed);
+        }
+        self.run_filters(None);
+    }
+
+    /// One filter pass; `scores` are search priorities already computed per map
+    fn run_filters(&mut self, scores: Option<&[Option<u8>]>) {
+        self.downloaded_count = self
+            .maps
+            .iter()
+            .filter(|m| self.downloaded_names.contains(&m.name))
+            .count();
+        let mut parsed = ParsedQuery::parse(&self.search_query);
+        parsed.case_sensitive = self.search_case_sensitive;
+        parsed.whole_word = self.search_whole_word;
+        let query = parsed.text.as_str();
         let query_lower = query.to_lowercase();
         let is_empty = query.is_empty();
+        let searching = !self.search_query.trim().is_empty();
+
+        // The search that relaxed the filters is over
+        if !searching {
+            if let Some(saved) = self.relaxed_filters.take() {
+                if self.sidebar_filters() == SidebarFilters::cleared(&self.available_years) {
+                    self.set_sidebar_filters(saved);
+                }
+            }
+        }
 
         // Save sort when starting to search, clear sort to use search relevance
         if !is_empty && self.saved_sort.is_none() {
@@ -25,164 +722,351 @@ impl App {
             }
         }
 
-        let mut scored: Vec<(usize, u8)> = self
-            .maps
-            .iter()
-            .enumerate()
-            .filter_map(|(i, m)| {
-                // Downloaded filter - check actual file existence
-                match self.filter_downloaded {
-                    1 => {
-                        let path = self.download_path.join(format!("{}.map", m.name));
-                        if !path.exists() {
-                            return None;
-                        }
-                    }
-                    2 => {
-                        let path = self.download_path.join(format!("{}.map", m.name));
-                        if path.exists() {
-                            return None;
-                        }
-                    }
-                    _ => {}
-                }
-
-                // Year filter
-                if self.year_mode_range {
-                    if let Some((min_year, max_year)) = self.year_range {
-                        let map_year = m
-                            .release_date
-                            .split('-')
-                            .next()
-                            .and_then(|y| y.parse::<i32>().ok());
-                        if let Some(year) = map_year {
-                            if year < min_year || year > max_year {
-                                return None;
-                            }
-                        } else {
-                            return None;
-                        }
-                    }
-                } else {
-                    let map_year = m
-                        .release_date
-                        .split('-')
-                        .next()
-                        .and_then(|y| y.parse::<i32>().ok());
-                    if let Some(year) = map_year {
-                        if !self.filter_years.contains(&year) {
-                            return None;
-                        }
-                    } else {
-                        return None;
-                    }
-                }
+        // One pass yields both the results and, per filter option, how many maps it
+        // would show given the other active filters (drawn on the filter buttons)
+        let mut counts = FilterCounts::default();
+        let mut scored: Vec<(usize, u8)> = Vec::new();
+        let mut searched: Vec<usize> = Vec::new();
+        for (i, m) in self.maps.iter().enumerate() {
+            let priority = match scores {
+                Some(scores) => scores[i],
+                None => Self::search_priority(m, &parsed, query, &query_lower),
+            };
+            let Some(priority) = priority else {
+                continue;
+            };
+            searched.push(i);
+            if !self.passes_downloaded(m) {
+                continue;
+            }
+            let year_ok = self.passes_year(m);
+            let category_ok = self.passes_category(m);
+            let stars_ok = self.passes_stars(m);
 
-                // Category filter
+            if year_ok && stars_ok {
                 if let Some(cat_idx) = Self::category_index(&m.category) {
-                    if self.category_mode_range {
-                        if cat_idx <= 4 {
-                            if (cat_idx as u8) < self.category_range.0
-                                || (cat_idx as u8) > self.category_range.1
-                            {
-                                return None;
-                            }
-                        } else {
-                            return None;
-                        }
-                    } else {
-                        if !self.filter_categories[cat_idx] {
-                            return None;
-                        }
-                    }
+                    counts.categories[cat_idx] += 1;
                 }
-
-                // Stars filter
-                let stars = m.stars as u8;
-                if self.stars_mode_range {
-                    if stars < self.stars_range.0 || stars > self.stars_range.1 {
-                        return None;
-                    }
-                } else if stars >= 1 && stars <= 5 && !self.filter_stars[(stars - 1) as usize] {
-                    return None;
-                }
-
-                // Search filter with priority scoring
-                if query.is_empty() {
-                    return Some((i, 4));
+            }
+            if year_ok && category_ok && (1..=5).contains(&m.stars) {
+                counts.stars[(m.stars - 1) as usize] += 1;
+            }
+            if category_ok && stars_ok {
+                if let Some(year) = Self::map_year(m) {
+                    *counts.years.entry(year).or_default() += 1;
                 }
+            }
+            if year_ok && category_ok && stars_ok {
+                scored.push((i, priority));
+            }
+        }
+        self.filter_counts = counts;
 
-                if m.name.contains(query) {
-                    return Some((i, 0));
-                }
-                if m.author.contains(query) {
-                    return Some((i, 1));
-                }
-                if m.name.to_lowercase().contains(&query_lower) {
-                    return Some((i, 2));
-                }
-                if m.author.to_lowercase().contains(&query_lower) {
-                    return Some((i, 3));
-                }
-                None
-            })
-            .collect();
+        // Nothing here: count what the search finds with the sidebar filters cleared
+        self.search_elsewhere = 0;
+        if searching && scored.is_empty() && !searched.is_empty() {
+            let current = self.sidebar_filters();
+            self.clear_sidebar_filters();
+            self.search_elsewhere = searched
+                .iter()
+                .map(|&i| &self.maps[i])
+                .filter(|m| {
+                    self.passes_downloaded(m) && self.passes_year(m) && self.passes_category(m) && self.passes_stars(m)
+                })
+                .count();
+            self.set_sidebar_filters(current);
+        }
 
         scored.sort_by_key(|(_, priority)| *priority);
+        self.match_tiers = if is_empty { HashMap::new() } else { scored.iter().copied().collect() };
         self.filtered_indices = scored.into_iter().map(|(i, _)| i).collect();
 
         // Apply column sorting
         if let Some(col) = self.sort_column {
             let maps = &self.maps;
             let dir = self.sort_direction;
-            self.filtered_indices.sort_by(|&a, &b| {
-                let cmp = match col {
-                    SortColumn::Name => maps[a]
-                        .name
-                        .to_lowercase()
-                        .cmp(&maps[b].name.to_lowercase()),
-                    SortColumn::Category => {
-                        let ca = Self::category_index(&maps[a].category).unwrap_or(99);
-                        let cb = Self::category_index(&maps[b].category).unwrap_or(99);
-                        ca.cmp(&cb)
-                    }
-                    SortColumn::Stars => maps[a].stars.cmp(&maps[b].stars),
-                    SortColumn::Points => maps[a].points.cmp(&maps[b].points),
-                    SortColumn::Author => maps[a]
-                        .author
-                        .to_lowercase()
-                        .cmp(&maps[b].author.to_lowercase()),
-                    SortColumn::ReleaseDate => {
-                        let a_valid = maps[a].release_date.len() >= 4
-                            && maps[a]
-                                .release_date
-                                .chars()
-                                .take(4)
-                                .all(|c| c.is_ascii_digit());
-                        let b_valid = maps[b].release_date.len() >= 4
-                            && maps[b]
-                                .release_date
-                                .chars()
-                                .take(4)
-                                .all(|c| c.is_ascii_digit());
-                        match (a_valid, b_valid) {
-                            (false, true) => std::cmp::Ordering::Less,
-                            (true, false) => std::cmp::Ordering::Greater,
-                            _ => maps[a].release_date.cmp(&maps[b].release_date),
-                        }
-                    }
+            self.filtered_indices.sort_by(|&a, &b| Self::sort_order(&maps[a], &maps[b], col, dir));
+        }
+
+        let matched = self.filtered_indices.len();
+        if let Some(previous) = self.match_count.replace(matched) {
+            if previous != matched {
+                self.match_count_change = Some((matched as i64 - previous as i64, std::time::Instant::now()));
+            }
+        }
+
+        self.build_list_groups();
+        self.build_scroll_index();
+    }
+
+    /// Order of two maps in the list sorted by `col`; equal keys fall back to the name
+    fn sort_order(a: &Map, b: &Map, col: SortColumn, dir: SortDirection) -> std::cmp::Ordering {
+        let cmp = match col {
+            SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortColumn::Category => {
+                let ca = Self::category_index(&a.category).unwrap_or(99);
+                let cb = Self::category_index(&b.category).unwrap_or(99);
+                ca.cmp(&cb)
+            }
+            SortColumn::Stars => a.stars.cmp(&b.stars),
+            SortColumn::Points => a.points.cmp(&b.points),
+            SortColumn::Author => {
+                let primary = |m: &Map| m.authors.first().unwrap_or(&m.author).to_lowercase();
+                primary(a).cmp(&primary(b))
+            }
+            SortColumn::ReleaseDate => {
+                let valid = |m: &Map| {
+                    m.release_date.len() >= 4 && m.release_date.chars().take(4).all(|c| c.is_ascii_digit())
                 };
-                if dir == SortDirection::Descending {
-                    cmp.reverse()
+                let (a_valid, b_valid) = (valid(a), valid(b));
+                match (a_valid, b_valid) {
+                    (false, true) => std::cmp::Ordering::Less,
+                    (true, false) => std::cmp::Ordering::Greater,
+                    _ => a.release_date.cmp(&b.release_date),
+                }
+            }
+        };
+        let cmp = if dir == SortDirection::Descending {
+            cmp.reverse()
+        } else {
+            cmp
+        };
+        // Equal keys always fall back to A-Z by name, so ties keep one order
+        // across frames and sessions regardless of the sort direction
+        cmp.then_with(|| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| a.name.cmp(&b.name))
+        })
+    }
+
+    /// Whether results are sectioned by how they matched the search
+    pub fn grouping_by_relevance(&self) -> bool {
+        self.group_search_matches && !self.match_tiers.is_empty()
+    }
+
+    /// Section of a search priority (see `search_priority`), as (order, label)
+    fn match_tier(priority: u8) -> (i64, &'static str) {
+        let tier = match priority {
+            0 | 2 => 0,
+            1 | 3 => 1,
+            _ => 2,
+        };
+        (tier as i64, MATCH_TIERS[tier].0)
+    }
+
+    /// Group of map `idx` under the active grouping, as (order, key)
+    fn group_of(&self, idx: usize) -> (i64, String) {
+        if self.grouping_by_relevance() {
+            let (order, label) = Self::match_tier(self.match_tiers.get(&idx).copied().unwrap_or(4));
+            return (order, label.to_string());
+        }
+        let m = &self.maps[idx];
+        match self.group_by {
+            GroupBy::None => (0, String::new()),
+            GroupBy::Category => match Self::category_index(&m.category) {
+                Some(idx) => (idx as i64, m.category.clone()),
+                None => (i64::MAX, "Other".to_string()),
+            },
+            GroupBy::Author => {
+                let author = m.authors.first().unwrap_or(&m.author);
+                if author.is_empty() {
+                    (1, "Unknown".to_string())
                 } else {
-                    cmp
+                    (0, author.clone())
                 }
-            });
+            }
+            // Newest first
+            GroupBy::Year => match Self::map_year(m) {
+                Some(year) => (-(year as i64), year.to_string()),
+                None => (i64::MAX, "Undated".to_string()),
+            },
+            GroupBy::Stars => match m.stars {
+                1 => (-1, "1 star".to_string()),
+                stars @ 2..=5 => (-(stars as i64), format!("{} stars", stars)),
+                _ => (0, "Unrated".to_string()),
+            },
         }
+    }
 
-        self.build_scroll_index();
+    /// Reorder the list into contiguous groups (stable, so the column sort holds within
+    /// each group), drop the maps of collapsed groups and lay out the header rows
+    fn build_list_groups(&mut self) {
+        self.list_groups.clear();
+        self.list_rows.clear();
+        self.list_row_of_pos.clear();
+        self.grid_sections.clear();
+        let relevance = self.grouping_by_relevance();
+        // Outside a search, the grid has no headers and shows the flat list
+        if !relevance && (self.group_by == GroupBy::None || !self.compact_view) {
+            return;
+        }
+
+        let mut keyed: Vec<(usize, (i64, String))> = self
+            .filtered_indices
+            .iter()
+            .map(|&i| (i, self.group_of(i)))
+            .collect();
+        keyed.sort_by(|(_, (oa, ka)), (_, (ob, kb))| {
+            oa.cmp(ob)
+                .then_with(|| ka.to_lowercase().cmp(&kb.to_lowercase()))
+                .then_with(|| ka.cmp(kb))
+        });
+
+        if !self.compact_view {
+            // Grid: match tiers in order, labelled on the first card of each
+            for (pos, (_, (order, key))) in keyed.iter().enumerate() {
+                if pos == 0 || keyed[pos - 1].1 .1 != *key {
+                    let count = keyed[pos..].iter().take_while(|(_, (_, k))| k == key).count();
+                    self.grid_sections.push((pos, *order, format!("{} ({})", key, count)));
+                }
+            }
+            self.filtered_indices = keyed.into_iter().map(|(i, _)| i).collect();
+            return;
+        }
+
+        // Maps in collapsed groups stay filtered (counts, Select All) but get no row;
+        // their position points at the group header instead
+        let mut ordered = Vec::with_capacity(keyed.len());
+        for (i, (order, key)) in keyed {
+            if self.list_groups.last().is_none_or(|g| g.key != key) {
+                self.list_rows.push(ListRow::Header(self.list_groups.len()));
+                let collapsed = self.collapsed_groups.contains(&key);
+                self.list_groups.push(ListGroup { key, order, count: 0, collapsed });
+            }
+            let group = self.list_groups.last_mut().expect("group pushed above");
+            group.count += 1;
+            if group.collapsed {
+                self.list_row_of_pos.push(self.list_rows.len() - 1);
+            } else {
+                self.list_row_of_pos.push(self.list_rows.len());
+                self.list_rows.push(ListRow::Map(ordered.len()));
+            }
+            ordered.push(i);
+        }
+        self.filtered_indices = ordered;
+    }
+
+    /// Whether `filtered_indices[pos]` sits in a collapsed group of the list view
+    pub fn list_pos_hidden(&self, pos: usize) -> bool {
+        !self.list_groups.is_empty()
+            && matches!(self.list_rows.get(self.list_row_of(pos)), Some(ListRow::Header(_)))
     }
 
+    /// Position `delta` maps away from `pos`, skipping maps in collapsed list groups
+    pub fn step_list_pos(&self, pos: usize, delta: i32) -> Option<usize> {
+        let visible: Vec<usize> = (0..self.filtered_indices.len())
+            .filter(|&p| !self.list_pos_hidden(p))
+            .collect();
+        let last = visible.len().checked_sub(1)?;
+        let from = visible.iter().position(|&p| p >= pos).unwrap_or(last);
+        let to = (from as i64 + delta as i64).clamp(0, last as i64) as usize;
+        Some(visible[to])
+    }
+
+    /// Number of rows in the list view, group headers included
+    pub fn list_row_count(&self) -> usize {
+        if self.list_groups.is_empty() {
+            self.filtered_indices.len()
+        } else {
+            self.list_rows.len()
+        }
+    }
+
+    /// List view row showing `filtered_indices[pos]`
+    pub fn list_row_of(&self, pos: usize) -> usize {
+        if self.list_groups.is_empty() {
+            pos
+        } else {
+            self.list_row_of_pos.get(pos).copied().unwrap_or(pos)
+        }
+    }
+
+    /// Position in `filtered_indices` of the map at or after list row `row`
+    pub fn list_pos_of(&self, row: usize) -> usize {
+        if self.list_groups.is_empty() {
+            return row;
+        }
+        self.list_rows[row.min(self.list_rows.len())..]
+            .iter()
+            .find_map(|r| match r {
+                ListRow::Map(pos) => Some(*pos),
+                ListRow::Header(_) => None,
+            })
+            .unwrap_or(self.filtered_indices.len().saturating_sub(1))
+    }
+
+    /// Collapse or expand a group of the list view
+    pub fn toggle_group(&mut self, group: usize) {
+        let Some(key) = self.list_groups.get(group).map(|g| g.key.clone()) else {
+            return;
+        };
+        if !self.collapsed_groups.remove(&key) {
+            self.collapsed_groups.insert(key);
+        }
+        self.apply_filters();
+    }
+
+    /// Change the list grouping; collapsed state only applies to the old groups
+    pub fn set_group_by(&mut self, group_by: GroupBy) {
+        if self.group_by == group_by {
+            return;
+        }
+        self.group_by = group_by;
+        self.collapsed_groups.clear();
+        self.apply_filters();
+        self.save_column_settings();
+    }
+
+    /// Toggle between list and grid, swapping in the sort remembered for the new view.
+    /// `top_row` is the first visible row; returns the row of the same map afterwards.
+    pub fn switch_view(&mut self, top_row: usize) -> usize {
+        let top_map = self.filtered_indices.get(top_row).copied();
+        let current = self.saved_sort.unwrap_or((self.sort_column, self.sort_direction));
+        let to_list = !self.compact_view;
+        self.compact_view = to_list;
+
+        // Grouping only applies to the list, so the order changes with the view
+        let regroup = self.group_by != GroupBy::None;
+        if self.link_view_sorts {
+            self.list_sort = current;
+            self.grid_sort = current;
+            if !regroup {
+                return top_row;
+            }
+            self.apply_filters();
+            return top_map
+                .and_then(|m| self.filtered_indices.iter().position(|&i| i == m))
+                .unwrap_or(0);
+        }
+
+        // Remember the outgoing view's sort, then restore the incoming one
+        let incoming = if to_list {
+            self.grid_sort = current;
+            self.list_sort
+        } else {
+            self.list_sort = current;
+            self.grid_sort
+        };
+        if incoming == current && !regroup {
+            return top_row;
+        }
+        if self.saved_sort.is_some() {
+            // Searching: relevance order stays, restore applies once search clears
+            self.saved_sort = Some(incoming);
+        } else {
+            (self.sort_column, self.sort_direction) = incoming;
+        }
+        self.apply_filters();
+
+        top_map
+            .and_then(|m| self.filtered_indices.iter().position(|&i| i == m))
+            .unwrap_or(0)
+    }
+
+    /// Rebuild the jump markers for the current order: one per group when the list is
+    /// grouped, otherwise sections of the sort column. Relevance order gets none.
     pub fn build_scroll_index(&mut self) {
         self.scroll_index_markers.clear();
 
@@ -193,134 +1077,203 @@ impl App {
         let maps = &self.maps;
         let indices = &self.filtered_indices;
 
-        match self.sort_column {
-            Some(SortColumn::Name) | Some(SortColumn::Author) => {
-                let get_char = |idx: usize| -> char {
-                    let s = if self.sort_column == Some(SortColumn::Name) {
-                        &maps[idx].name
-                    } else {
-                        &maps[idx].author
-                    };
-                    s.chars().next().unwrap_or('?').to_ascii_uppercase()
+        if !self.grid_sections.is_empty() {
+            for &(pos, tier, _) in &self.grid_sections {
+                let label = Self::tier_marker(tier);
+                self.scroll_index_markers.push(ScrollIndexMarker { label, row_index: pos });
+            }
+            return;
+        }
+        if !self.list_groups.is_empty() {
+            // Group order comes first, so sort-based sections would repeat per group
+            for (group, row) in self.list_rows.iter().enumerate().filter_map(|(row, r)| match r {
+                ListRow::Header(group) => Some((*group, row)),
+                ListRow::Map(_) => None,
+            }) {
+                let g = &self.list_groups[group];
+                let label = match self.group_by {
+                    _ if self.grouping_by_relevance() => Self::tier_marker(g.order),
+                    GroupBy::Category => Self::category_marker(&g.key),
+                    GroupBy::Year => match g.key.parse::<i32>() {
+                        Ok(year) => format!("'{:02}", year.rem_euclid(100)),
+                        Err(_) => "N/A".to_string(),
+                    },
+                    GroupBy::Stars => match g.key.split(' ').next().and_then(|s| s.parse::<i32>().ok()) {
+                        Some(stars) => format!("{}★", stars),
+                        None => "0★".to_string(),
+                    },
+                    _ => g.key.chars().next().unwrap_or('?').to_uppercase().collect(),
                 };
-
-                let mut current_char = '\0';
-                for (row_idx, &map_idx) in indices.iter().enumerate() {
-                    let c = get_char(map_idx);
-                    if c != current_char {
-                        current_char = c;
-                        self.scroll_index_markers.push(ScrollIndexMarker {
-                            label: c.to_string(),
-                            row_index: row_idx,
-                        });
-                    }
-                }
+                // Collapsed groups have no rows; jump to the next visible map instead
+                self.scroll_index_markers.push(ScrollIndexMarker {
+                    label,
+                    row_index: self.list_pos_of(row),
+                });
             }
-            Some(SortColumn::Category) => {
-                let mut current_cat = "";
-                for (row_idx, &map_idx) in indices.iter().enumerate() {
-                    let cat = &maps[map_idx].category;
-                    if cat != current_cat {
-                        current_cat = cat;
-                        let label = match cat.as_str() {
-                            "Easy" => "EZ",
-                            "Main" => "MN",
-                            "Hard" => "HD",
-                            "Insane" => "IN",
-                            "Extreme" => "EX",
-                            "Solo" => "SO",
-                            "Mod" => "MD",
-                            "Extra" => "XT",
-                            _ => &cat[..2.min(cat.len())],
-                        };
-                        self.scroll_index_markers.push(ScrollIndexMarker {
-                            label: label.to_string(),
-                            row_index: row_idx,
-                        });
-                    }
+            // Adjacent groups can share an initial (authors); keep the first
+            self.scroll_index_markers.dedup_by(|b, a| a.label == b.label);
+            return;
+        }
+
+        // Appends a marker whenever the section label changes
+        let mut push_sections = |label_of: &dyn Fn(&Map) -> String| {
+            let mut current: Option<String> = None;
+            for (row_idx, &map_idx) in indices.iter().enumerate() {
+                let label = label_of(&maps[map_idx]);
+                if current.as_ref() != Some(&label) {
+                    current = Some(label.clone());
+                    self.scroll_index_markers.push(ScrollIndexMarker { label, row_index: row_idx });
                 }
             }
-            Some(SortColumn::Stars) => {
-                let mut current_stars = -1;
-                for (row_idx, &map_idx) in indices.iter().enumerate() {
-                    let stars = maps[map_idx].stars;
-                    if stars != current_stars {
-                        current_stars = stars;
-                        self.scroll_index_markers.push(ScrollIndexMarker {
-                            label: format!("{}★", stars),
-                            row_index: row_idx,
-                        });
-                    }
-                }
+        };
+        let initial = |s: &str| -> String {
+            match s.chars().next() {
+                Some(c) if c.is_alphabetic() => c.to_uppercase().collect(),
+                Some(_) => "#".to_string(),
+                None => "?".to_string(),
             }
-            Some(SortColumn::Points) => {
-                if indices.is_empty() {
-                    return;
-                }
+        };
 
+        match self.sort_column {
+            Some(SortColumn::Name) => push_sections(&|m| initial(&m.name)),
+            // Same primary author the sort uses
+            Some(SortColumn::Author) => push_sections(&|m| initial(m.authors.first().unwrap_or(&m.author))),
+            Some(SortColumn::Category) => push_sections(&|m| Self::category_marker(&m.category)),
+            Some(SortColumn::Stars) => push_sections(&|m| format!("{}★", m.stars)),
+            Some(SortColumn::ReleaseDate) => push_sections(&|m| match Self::map_year(m) {
+                Some(year) => format!("'{:02}", year.rem_euclid(100)),
+                None => "N/A".to_string(),
+            }),
+            Some(SortColumn::Points) => {
                 let mut points: Vec<i32> = indices.iter().map(|&i| maps[i].points).collect();
                 points.sort();
                 let min_pts = points[0];
                 let max_pts = points[points.len() - 1];
 
-                let breakpoints: Vec<i32> = if max_pts - min_pts < 20 {
-                    vec![min_pts, max_pts]
-                } else {
-                    let q1 = points[points.len() / 4];
-                    let q2 = points[points.len() / 2];
-                    let q3 = points[3 * points.len() / 4];
-                    let mut bp = vec![min_pts];
-                    if q1 > min_pts {
-                        bp.push(q1);
-                    }
-                    if q2 > q1 {
-                        bp.push(q2);
-                    }
-                    if q3 > q2 {
-                        bp.push(q3);
-                    }
-                    if max_pts > q3 {
-                        bp.push(max_pts);
-                    }
-                    bp
-                };
-
-                let mut bp_idx = 0;
-                for (row_idx, &map_idx) in indices.iter().enumerate() {
-                    let pts = maps[map_idx].points;
-                    while bp_idx < breakpoints.len() && pts >= breakpoints[bp_idx] {
-                        self.scroll_index_markers.push(ScrollIndexMarker {
-                            label: format!("{}", breakpoints[bp_idx]),
-                            row_index: row_idx,
-                        });
-                        bp_idx += 1;
-                    }
-                }
-            }
-            Some(SortColumn::ReleaseDate) => {
-                let mut current_year = "";
-                for (row_idx, &map_idx) in indices.iter().enumerate() {
-                    let date = &maps[map_idx].release_date;
-                    let year =
-                        if date.len() >= 4 && date.chars().take(4).all(|c| c.is_ascii_digit()) {
-                            &date[2..4]
-                        } else {
-                            "NA"
-                        };
-                    if year != current_year {
-                        current_year = year;
-                        self.scroll_index_markers.push(ScrollIndexMarker {
-                            label: if year == "NA" {
-                                "N/A".to_string()
-                            } else {
-                                format!("'{}", year)
-                            },
-                            row_index: row_idx,
-                        });
+                // Quartile lower bounds; each map is labeled with the bucket it falls in
+                let mut breakpoints = vec![min_pts];
+                if max_pts - min_pts >= 20 {
+                    for q in [points.len() / 4, points.len() / 2, 3 * points.len() / 4] {
+                        if points[q] > *breakpoints.last().unwrap() {
+                            breakpoints.push(points[q]);
+                        }
                     }
                 }
+                // Works for either direction since buckets are contiguous in sorted order
+                push_sections(&|m| {
+                    let bucket = breakpoints.partition_point(|&bp| bp <= m.points).max(1) - 1;
+                    breakpoints[bucket].to_string()
+                });
             }
+            // Search relevance: no meaningful sections
             None => {}
         }
     }
+
+    /// Jump marker for the match tier `order` (see `match_tier`)
+    fn tier_marker(order: i64) -> String {
+        let (_, marker) = MATCH_TIERS[(order.max(0) as usize).min(MATCH_TIERS.len() - 1)];
+        marker.to_string()
+    }
+
+    /// Two-letter category abbreviation for the jump markers
+    fn category_marker(category: &str) -> String {
+        match category {
+            "Easy" => "EZ".to_string(),
+            "Main" => "MN".to_string(),
+            "Hard" => "HD".to_string(),
+            "Insane" => "IN".to_string(),
+            "Extreme" => "EX".to_string(),
+            "Solo" => "SO".to_string(),
+            "Mod" => "MD".to_string(),
+            "Extra" => "XT".to_string(),
+            // Char-based so unknown multibyte categories can't panic
+            _ => category.chars().take(2).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(name: &str, stars: i32, category: &str) -> Map {
+        serde_json::from_value(serde_json::json!({
+            "id": 0, "name": name, "category": category, "stars": stars, "points": 10,
+            "author": "", "release_date": "2024-01-01", "size": 0, "downloaded": false, "local_path": null,
+        }))
+        .unwrap()
+    }
+
+    fn sorted(maps: &[Map], col: SortColumn, dir: SortDirection) -> Vec<&str> {
+        let mut order: Vec<&Map> = maps.iter().collect();
+        order.sort_by(|a, b| App::sort_order(a, b, col, dir));
+        order.iter().map(|m| m.name.as_str()).collect()
+    }
+
+    #[test]
+    fn equal_keys_fall_back_to_name_in_both_directions() {
+        let maps = [
+            map("delta", 3, "Hard"),
+            map("Bravo", 3, "Hard"),
+            map("alpha", 3, "Hard"),
+            map("charlie", 5, "Hard"),
+            map("echo", 5, "Hard"),
+        ];
+        assert_eq!(
+            sorted(&maps, SortColumn::Stars, SortDirection::Ascending),
+            ["alpha", "Bravo", "delta", "charlie", "echo"]
+        );
+        assert_eq!(
+            sorted(&maps, SortColumn::Stars, SortDirection::Descending),
+            ["charlie", "echo", "alpha", "Bravo", "delta"]
+        );
+        // Difficulty (category) ties too
+        assert_eq!(
+            sorted(&maps, SortColumn::Category, SortDirection::Descending),
+            ["alpha", "Bravo", "charlie", "delta", "echo"]
+        );
+    }
+
+    #[test]
+    fn tie_order_does_not_depend_on_input_order() {
+        let mut maps = vec![map("b", 4, "Main"), map("a", 4, "Main"), map("c", 4, "Main")];
+        let first = sorted(&maps, SortColumn::Stars, SortDirection::Ascending).join(",");
+        maps.reverse();
+        assert_eq!(sorted(&maps, SortColumn::Stars, SortDirection::Ascending).join(","), first);
+    }
+
+    #[test]
+    fn scan_finds_only_map_files_in_the_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["Kobra.map", "Sunny Side Up.map", "Olé.map", "notes.txt", "Kobra.map.part"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+        std::fs::create_dir(dir.path().join("Folder.map")).unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("sub").join("Nested.map"), b"").unwrap();
+
+        let names = scan_map_names(dir.path());
+
+        let expected: HashSet<String> = ["Kobra", "Sunny Side Up", "Olé"].map(String::from).into();
+        assert_eq!(names, expected);
+        assert!(scan_map_names(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn tier_markers_follow_the_tier_not_its_label() {
+        let marker = |priority| App::tier_marker(App::match_tier(priority).0);
+        assert_eq!([marker(0), marker(2)], ["NM", "NM"]);
+        assert_eq!([marker(1), marker(3)], ["AU", "AU"]);
+        assert_eq!(marker(4), "OT");
+    }
+
+    #[test]
+    fn author_query_round_trips_any_name() {
+        for name in ["Ravie", "Welf & Ravie", r#"Tom "T" Ato"#, r"back\slash", r#"ends with ""#, r"ends\"] {
+            let parsed = ParsedQuery::parse(&format!("{} kobra", author_query(name)));
+            assert_eq!(parsed.author.as_deref(), Some(name.to_lowercase().as_str()), "{}", name);
+            assert_eq!(parsed.text, "kobra", "{}", name);
+        }
+    }
 }
@@ -0,0 +1,68 @@
+//! Lazy .map file metadata for the preview details strip
+
+use super::App;
+use crate::db::Database;
+use crate::mapfile::{self, MapFileInfo};
+use eframe::egui;
+use tracing::{debug, warn};
+
+fn memory_id(name: &str) -> egui::Id {
+    egui::Id::new(("map_file_info", name))
+}
+
+impl App {
+    /// Metadata of a downloaded map. The first call starts a background parse
+    /// (or DB cache lookup) and returns None; later calls return the result.
+    pub fn map_file_info(&mut self, ctx: &egui::Context, name: &str) -> Option<&MapFileInfo> {
+        if self.map_file_info_pending.contains(name) {
+            let result = ctx.memory_mut(|mem| mem.data.remove_temp::<Option<MapFileInfo>>(memory_id(name)))?;
+            self.map_file_info_pending.remove(name);
+            self.map_file_info.insert(name.to_string(), result);
+        }
+        if !self.map_file_info.contains_key(name) {
+            if self.downloaded_names.contains(name) {
+                self.start_map_file_parse(ctx, name);
+            }
+            return None;
+        }
+        self.map_file_info.get(name)?.as_ref()
+    }
+
+    fn start_map_file_parse(&mut self, ctx: &egui::Context, name: &str) {
+        self.map_file_info_pending.insert(name.to_string());
+        let path = self.download_path.join(format!("{}.map", name));
+        let db_path = self.data_dir.join("maps.db");
+        let name = name.to_string();
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let modified = std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs() as i64);
+            let file_size = std::fs::metadata(&path).map_or(0, |m| m.len());
+            let db = Database::open(&db_path).ok();
+
+            let cached = db
+                .as_ref()
+                .and_then(|db| db.get_map_file_info(&name, file_size, modified).ok().flatten());
+            let info = cached.or_else(|| match mapfile::read_info(&path) {
+                Ok(info) => {
+                    debug!(map = %name, width = info.width, height = info.height, "Parsed map file");
+                    if let Some(db) = &db {
+                        db.set_map_file_info(&name, modified, &info).ok();
+                    }
+                    Some(info)
+                }
+                Err(e) => {
+                    warn!(map = %name, error = %e, "Failed to read map file");
+                    None
+                }
+            });
+
+            ctx.memory_mut(|mem| mem.data.insert_temp(memory_id(&name), info));
+            ctx.request_repaint();
+        });
+    }
+}
@@ -2,23 +2,38 @@
 
 mod context_menu;
 mod downloads;
+mod featured;
 mod filters;
+mod map_info;
 mod modals;
+mod onboarding;
+mod outdated;
+mod selection;
 mod thumbnails;
 mod updates;
 mod views;
 
+use filters::{FilterCounts, SidebarFilters};
+use modals::SettingsCategory;
+
 use crate::constants::*;
 use crate::db::{Database, Map};
-use crate::settings::Settings;
+use crate::local_server::{LocalServer, SharedMap};
+use crate::mapfile::MapFileInfo;
+use crate::settings::{Settings, SettingsSource};
+use crate::sound::{self, SoundSource};
+use crate::taskbar::TaskbarProgress;
 use crate::theme;
+use crate::tray::Tray;
 use crate::types::*;
-use crate::utils::{get_cache_dir, process_cache_refresh};
+use crate::utils::{get_cache_dir, os_prefers_reduced_motion, process_cache_refresh};
 use eframe::egui;
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, AtomicUsize};
+use std::sync::{mpsc, Arc, Mutex};
 use tokio_util::sync::CancellationToken;
+use tracing::warn;
 
 // ============================================================================
 // APP STATE
@@ -30,8 +45,12 @@ pub struct App {
     pub(crate) filtered_indices: Vec<usize>,
     pub(crate) search_query: String,
     pub(crate) focus_search: bool,
-    pub(crate) logo_texture: Option<egui::TextureHandle>,
+    pub(crate) focus_search_select_all: bool, // "/" and Ctrl+F select the query instead of appending
+    pub(crate) logo_texture: Option<(u32, egui::TextureHandle)>, // keyed by its width in physical pixels
     pub(crate) selected_indices: HashSet<usize>,
+    pub(crate) selection_history: Vec<Vec<String>>, // sorted map names, oldest first
+    pub(crate) selection_toggling: bool,              // last change was a single-item toggle
+    pub(crate) paste_list: Option<PasteList>,
     pub(crate) last_selected: Option<usize>,
     pub(crate) last_clicked_item: Option<usize>,
     pub(crate) map_list_focused: bool,
@@ -41,10 +60,34 @@ pub struct App {
     pub(crate) show_points: bool,
     pub(crate) show_author: bool,
     pub(crate) show_release_date: bool,
+    pub(crate) show_star_label: bool,
+    pub(crate) grid_info: GridInfo,
     pub(crate) show_settings: bool,
+    pub(crate) settings_category: SettingsCategory,
+    pub(crate) settings_search: String,
+    pub(crate) settings_reset_confirm: bool,
     // View mode
     pub(crate) compact_view: bool,
     pub(crate) large_thumbnails: bool,
+    pub(crate) grid_card_scale: f32,
+    pub(crate) date_format: DateFormat,
+    pub(crate) row_density: RowDensity,
+    pub(crate) list_thumbnails: bool,
+    pub(crate) auto_scroll_selection: bool,
+    pub(crate) type_to_search: bool,
+    pub(crate) map_of_the_day: bool,
+    pub(crate) map_of_the_day_shown: Option<String>,
+    pub(crate) featured_map: Option<String>, // map of the day banner, until dismissed
+    pub(crate) search_case_sensitive: bool,
+    pub(crate) search_whole_word: bool,
+    pub(crate) scroll_into_view_pos: Option<usize>, // position in filtered_indices to reveal
+    pub(crate) accent_color: egui::Color32,
+    pub(crate) accent_save_pending: bool,
+    // Animations (defaults follow the OS reduce-motion preference)
+    pub(crate) reduce_motion_default: bool,
+    pub(crate) smooth_scroll: bool,
+    pub(crate) button_press_animation: bool,
+    pub(crate) toast_fade: bool,
     // Column widths (resizable)
     pub(crate) col_widths: [f32; 6],
     // Column order (indices into col_widths)
@@ -59,26 +102,69 @@ pub struct App {
     pub(crate) filter_stars: [bool; 5],
     pub(crate) stars_mode_range: bool,
     pub(crate) stars_range: (u8, u8),
+    pub(crate) per_category_stars: bool,
+    pub(crate) category_stars: [(u8, u8); 8],
     pub(crate) filter_downloaded: u8,
     pub(crate) year_mode_range: bool,
     pub(crate) year_range: Option<(i32, i32)>,
+    pub(crate) filter_counts: FilterCounts, // rebuilt by apply_filters
+    pub(crate) match_count: Option<usize>,  // maps passing the filters, before groups collapse
+    pub(crate) match_count_change: Option<(i64, std::time::Instant)>, // delta flashed in the header
+    pub(crate) search_elsewhere: usize, // matches with the sidebar filters cleared, when none pass them
+    pub(crate) relaxed_filters: Option<SidebarFilters>, // stashed by "Search everywhere" until the search clears
+    pub(crate) search_generation: Arc<AtomicU64>, // bumped per query; stale background passes stop
+    pub(crate) search_pending: bool, // a background search pass is in flight
+    pub(crate) search_corpus: Option<Arc<Vec<Map>>>, // snapshot of `maps` for background passes
     pub(crate) filter_years: HashSet<i32>,
     pub(crate) available_years: Vec<i32>,
+    pub(crate) year_by_month: bool,
+    pub(crate) month_range: Option<(i32, i32)>,
+    pub(crate) available_months: Vec<i32>, // YYYYMM present in the data
+    pub(crate) include_undated: bool,
     pub(crate) show_filters: bool,
     // Download state
     pub(crate) download_state: Arc<Mutex<DownloadState>>,
     pub(crate) download_path: PathBuf,
     pub(crate) download_path_str: String,
+    pub(crate) downloaded_names: HashSet<String>, // map files in download_path, see rescan_downloaded
+    pub(crate) downloaded_count: usize,
+    pub(crate) downloaded_scan_due: bool,     // see poll_downloaded_scan
+    pub(crate) downloaded_scan_running: bool,
+    pub(crate) download_path_ok: bool, // false while the folder's drive is unreachable
+    pub(crate) download_path_checked_at: Option<std::time::Instant>,
+    pub(crate) download_path_check_pending: bool,
     pub(crate) runtime: tokio::runtime::Runtime,
     // Thumbnail cache
     pub(crate) thumbnail_cache: HashMap<String, Option<egui::TextureHandle>>,
+    pub(crate) thumbnail_last_used: HashMap<String, u64>, // map name -> pass number
+    pub(crate) thumbnail_pending: HashMap<String, (ThumbnailLoad, u64)>, // with the pass it was last drawn in
+    pub(crate) thumbnail_generation: u64, // bumped when the cache is cleared; older decodes are dropped
+    pub(crate) thumbnail_decoded_tx: mpsc::Sender<(u64, String, Option<egui::ColorImage>)>,
+    pub(crate) thumbnail_decoded_rx: mpsc::Receiver<(u64, String, Option<egui::ColorImage>)>,
     pub(crate) prefetch_started: bool,
+    pub(crate) onboarding: Option<OnboardingStep>, // first-run setup page, while it's open
+    pub(crate) onboarding_scanned: Option<usize>,  // maps found by the setup's folder scan
+    pub(crate) onboarding_scan_pending: bool,      // waiting for a scan started after "Scan folder"
+    pub(crate) onboarding_categories: [bool; 8],
+    pub(crate) onboarding_hide_downloaded: bool,
+    pub(crate) prefetch_running: Arc<AtomicUsize>, // prefetches in flight (clearing the cache starts another)
+    pub(crate) prefetch_progress: Arc<Mutex<PrefetchProgress>>,
+    pub(crate) thumbnail_missing: HashSet<String>, // not on the server (marker file cached)
+    pub(crate) cache_suspects: HashSet<String>, // maps whose cached image failed to decode
+    pub(crate) cache_checked: HashSet<String>,  // already re-checked this session
+    pub(crate) cache_repair_running: bool,
+    pub(crate) cache_repair_manual: bool,
+    pub(crate) cache_repair_result: Option<(usize, usize)>, // (repaired, not fetched again) files of the last manual check
+    pub(crate) cache_sizes: Option<[u64; 3]>, // bytes per CacheKind, measured in the background
+    pub(crate) cache_sizes_pending: bool,
     pub(crate) cache_dir: PathBuf,
     // Preview viewer state (multi-tab)
     pub(crate) preview_maps: Vec<String>,
     pub(crate) preview_active_tab: usize,
     pub(crate) preview_textures: HashMap<String, Option<egui::TextureHandle>>,
     pub(crate) preview_loading: HashSet<String>,
+    pub(crate) preview_hires: HashMap<String, Option<egui::TextureHandle>>, // None = server has none
+    pub(crate) preview_hires_loading: HashSet<String>,
     pub(crate) preview_zoom: f32,
     pub(crate) preview_offset: egui::Vec2,
     pub(crate) preview_dragging: bool,
@@ -87,6 +173,18 @@ pub struct App {
     pub(crate) sort_column: Option<SortColumn>,
     pub(crate) sort_direction: SortDirection,
     pub(crate) saved_sort: Option<(Option<SortColumn>, SortDirection)>,
+    pub(crate) list_sort: (Option<SortColumn>, SortDirection),
+    pub(crate) grid_sort: (Option<SortColumn>, SortDirection),
+    pub(crate) link_view_sorts: bool,
+    // Grouped list view; rows interleave group headers with maps
+    pub(crate) group_by: GroupBy,
+    pub(crate) collapsed_groups: HashSet<String>,
+    pub(crate) list_groups: Vec<ListGroup>,
+    pub(crate) list_rows: Vec<ListRow>,
+    pub(crate) list_row_of_pos: Vec<usize>, // position in filtered_indices -> row
+    pub(crate) group_search_matches: bool,
+    pub(crate) match_tiers: HashMap<usize, u8>, // map index -> search priority, while searching
+    pub(crate) grid_sections: Vec<(usize, i64, String)>, // grid position -> match tier, section label
     // Indexed scrollbar
     pub(crate) scroll_index_markers: Vec<ScrollIndexMarker>,
     pub(crate) scroll_target_row: Option<usize>,
@@ -104,25 +202,84 @@ pub struct App {
     pub(crate) update_in_progress: bool,
     pub(crate) app_update_error: Option<String>,
     pub(crate) app_update_success: Option<String>,
+    pub(crate) app_update_progress: Arc<Mutex<AppUpdateProgress>>,
+    pub(crate) app_update_cancel: Option<CancellationToken>,
     // Toast notification
-    pub(crate) toast_message: Option<String>,
-    pub(crate) toast_start: Option<std::time::Instant>,
+    pub(crate) toasts: std::collections::VecDeque<Toast>,
+    pub(crate) next_toast_id: u64,
     // Download modal state
     pub(crate) show_download_modal: bool,
     pub(crate) show_download_log: bool,
+    pub(crate) download_log_expanded: bool, // log grows to fill the window height
+    pub(crate) show_download_queue: bool,
+    pub(crate) taskbar: TaskbarProgress,
     pub(crate) download_log_filter: Option<&'static str>,
+    pub(crate) expanded_failures: HashSet<usize>,
+    pub(crate) download_log_search: String,
+    pub(crate) download_log_sort: Option<&'static str>,
+    pub(crate) download_log_sort_desc: bool,
     pub(crate) cancel_token: Option<CancellationToken>,
     // Settings
     pub(crate) play_sound_on_complete: bool,
+    pub(crate) sound_on_failure: bool,
+    pub(crate) sound_on_db_update: bool,
+    pub(crate) notification_sound: NotificationSound,
+    pub(crate) custom_sound_path: Option<PathBuf>,
+    pub(crate) sound_volume: f32,
+    pub(crate) seen_failed_count: usize, // failures already announced this batch
+    pub(crate) failure_sound_at: Option<std::time::Instant>,
+    pub(crate) max_retries: u32,
+    pub(crate) precheck_urls: bool,
+    pub(crate) minimize_to_tray: bool,
+    pub(crate) open_folder_after_download: bool,
+    pub(crate) post_download_command: Option<String>,
+    pub(crate) post_download_command_confirm: bool, // the "this runs a program" prompt is showing
+    pub(crate) collision_strategy: CollisionStrategy,
+    pub(crate) confirm_downloads: bool,
+    pub(crate) download_companion_files: bool,
+    pub(crate) purge_removed_months: u32,
+    pub(crate) local_server_enabled: bool,
+    pub(crate) local_server_port: u16,
+    pub(crate) local_server: Option<LocalServer>,
+    pub(crate) local_server_map: SharedMap,
+    pub(crate) local_server_current: (Option<String>, bool), // map last published to the endpoint, and whether it was downloaded
+    pub(crate) download_confirm: Option<Vec<usize>>, // batch awaiting the confirmation dialog
+    pub(crate) download_confirm_overwrite: bool,
+    pub(crate) batch_collision: Option<CollisionStrategy>, // one-off override for the next batch
+    pub(crate) precheck_order: Option<Vec<usize>>, // batch waiting on the URL precheck
+    pub(crate) precheck_cancel: Option<CancellationToken>,
+    pub(crate) next_batch: Option<Vec<DownloadJob>>, // batch waiting for the previous one's workers to stop
+    pub(crate) precheck_dead: Vec<(usize, String)>, // (map_idx, reason) awaiting confirmation
     pub(crate) window_pos: Option<egui::Pos2>,
-    pub(crate) window_size: Option<egui::Vec2>,
+    pub(crate) window_size: Option<egui::Vec2>, // floating geometry, kept while maximized
+    pub(crate) window_maximized: bool,
+    pub(crate) window_monitor: Option<egui::Vec2>,
+    pub(crate) window_restore_pending: bool, // first frame re-applies maximized / monitor
     pub(crate) was_downloading: bool,
+    // Exit while downloading
+    pub(crate) show_exit_confirm: bool,
+    pub(crate) show_shortcuts: bool, // "?" / F1 cheat sheet
+    pub(crate) exit_when_idle: bool, // draining: close once in-flight files finish
+    pub(crate) exit_confirmed: bool,
+    // Tray icon (window hidden while downloads continue)
+    pub(crate) tray: Tray,
+    pub(crate) hidden_to_tray: bool,
     pub(crate) needs_center: bool,
     pub(crate) data_dir: PathBuf,
     pub(crate) view_switch_count: u32,
     pub(crate) list_row_height: f32,
-    pub(crate) grid_scroll_target: Option<f32>,
+    /// Eased scroll destination shared by list and grid (index jumps, PageUp/Down)
+    pub(crate) scroll_target_offset: Option<f32>,
     pub(crate) grid_scroll_to_row: Option<usize>,
+    pub(crate) url_star_dirs: HashMap<String, String>,
+    pub(crate) author_aliases: HashMap<String, String>,
+    pub(crate) author_popup: Option<String>, // canonical author shown in the author modal
+    pub(crate) map_file_info: HashMap<String, Option<MapFileInfo>>, // None if the file couldn't be parsed
+    pub(crate) map_file_info_pending: HashSet<String>,
+    pub(crate) outdated_names: HashSet<String>, // downloaded maps whose file differs from the manifest
+    pub(crate) outdated_check_due: bool,
+    pub(crate) outdated_check_running: bool,
+    pub(crate) log_level: String,
 }
 
 // ============================================================================
@@ -130,7 +287,14 @@ pub struct App {
 // ============================================================================
 
 impl App {
-    pub fn new(cc: &eframe::CreationContext<'_>, db: Database, settings: Settings, data_dir: PathBuf) -> Self {
+    pub fn new(
+        cc: &eframe::CreationContext<'_>,
+        db: Database,
+        settings: Settings,
+        settings_source: SettingsSource,
+        data_dir: PathBuf,
+        startup_filter: Option<String>,
+    ) -> Self {
         // Force dark theme
         cc.egui_ctx.set_theme(egui::Theme::Dark);
 
@@ -156,11 +320,19 @@ impl App {
 
         cc.egui_ctx.set_fonts(fonts);
 
-        // Apply theme from theme.rs
-        theme::apply_visuals(&cc.egui_ctx);
+        // Apply theme from theme.rs (set_accent also applies visuals)
+        let [r, g, b] = settings.accent_color;
+        let accent_color = egui::Color32::from_rgb(r, g, b);
+        theme::set_accent(&cc.egui_ctx, accent_color);
+
+        let reduce_motion_default = os_prefers_reduced_motion();
+        let animate_default = !reduce_motion_default;
+        let button_press_animation = settings.button_press_animation.unwrap_or(animate_default);
+        theme::set_press_animation(button_press_animation);
 
         let maps = db.get_all_maps().unwrap_or_default();
         let filtered_indices: Vec<usize> = (0..maps.len()).collect();
+        let (thumbnail_decoded_tx, thumbnail_decoded_rx) = mpsc::channel();
 
         let download_path = settings.download_path_or_default();
 
@@ -176,8 +348,12 @@ impl App {
             filtered_indices,
             search_query: String::new(),
             focus_search: false,
+            focus_search_select_all: false,
             logo_texture: None,
             selected_indices: HashSet::new(),
+            selection_history: Vec::new(),
+            selection_toggling: false,
+            paste_list: None,
             last_selected: None,
             last_clicked_item: None,
             map_list_focused: true,
@@ -186,9 +362,41 @@ impl App {
             show_points: settings.col_points,
             show_author: settings.col_author,
             show_release_date: settings.col_release_date,
+            show_star_label: settings.col_star_label,
+            grid_info: settings.grid_info.unwrap_or(GridInfo {
+                category: settings.col_category,
+                stars: settings.col_stars,
+                star_label: settings.col_star_label,
+                author: settings.col_author,
+                points: settings.col_points,
+                release_date: settings.col_release_date,
+            }),
             show_settings: false,
+            settings_category: SettingsCategory::default(),
+            settings_search: String::new(),
+            settings_reset_confirm: false,
             compact_view: settings.compact_view,
             large_thumbnails: settings.large_thumbnails,
+            grid_card_scale: settings
+                .grid_card_scale
+                .clamp(theme::CARD_SCALE_MIN, theme::CARD_SCALE_MAX),
+            date_format: settings.date_format,
+            row_density: settings.row_density,
+            list_thumbnails: settings.list_thumbnails,
+            auto_scroll_selection: settings.auto_scroll_selection,
+            type_to_search: settings.type_to_search,
+            map_of_the_day: settings.map_of_the_day,
+            map_of_the_day_shown: settings.map_of_the_day_shown.clone(),
+            featured_map: None,
+            search_case_sensitive: settings.search_case_sensitive,
+            search_whole_word: settings.search_whole_word,
+            scroll_into_view_pos: None,
+            accent_color,
+            accent_save_pending: false,
+            reduce_motion_default,
+            smooth_scroll: settings.smooth_scroll.unwrap_or(animate_default),
+            button_press_animation,
+            toast_fade: settings.toast_fade.unwrap_or(animate_default),
             col_widths: [
                 settings.col_w_name,
                 settings.col_w_category,
@@ -200,31 +408,84 @@ impl App {
             col_order: settings.col_order,
             dragging_col: None,
             resizing_col: None,
-            filter_categories: [true; 8],
-            category_mode_range: true,
-            category_range: (0, 4),
-            filter_stars: [true; 5],
-            stars_mode_range: true,
-            stars_range: (1, 5),
-            show_filters: true,
+            filter_categories: settings.filter_categories,
+            category_mode_range: settings.category_mode_range,
+            // Hand-edited settings could index past the slider labels
+            category_range: clamp_range(settings.category_range, 0, 4),
+            filter_stars: settings.filter_stars,
+            stars_mode_range: settings.stars_mode_range,
+            stars_range: clamp_range(settings.stars_range, 1, 5),
+            per_category_stars: settings.per_category_stars,
+            category_stars: std::array::from_fn(|i| {
+                clamp_range(settings.category_stars[i], 1, DEFAULT_CATEGORY_STARS[i].1)
+            }),
+            show_filters: settings.show_filters,
             download_state: Arc::new(Mutex::new(DownloadState::default())),
             download_path: download_path.clone(),
             download_path_str: download_path.to_string_lossy().to_string(),
+            downloaded_names: HashSet::new(),
+            downloaded_count: 0,
+            downloaded_scan_due: true,
+            downloaded_scan_running: false,
+            download_path_ok: true,
+            download_path_checked_at: None,
+            download_path_check_pending: false,
             runtime: tokio::runtime::Runtime::new().unwrap(),
             thumbnail_cache: HashMap::new(),
+            thumbnail_last_used: HashMap::new(),
+            thumbnail_pending: HashMap::new(),
+            thumbnail_generation: 0,
+            thumbnail_decoded_tx,
+            thumbnail_decoded_rx,
             prefetch_started: false,
+            onboarding: None,
+            onboarding_scanned: None,
+            onboarding_scan_pending: false,
+            onboarding_categories: [true; 8],
+            onboarding_hide_downloaded: false,
+            prefetch_running: Arc::new(AtomicUsize::new(0)),
+            prefetch_progress: Arc::new(Mutex::new(PrefetchProgress::default())),
+            thumbnail_missing: HashSet::new(),
+            cache_suspects: HashSet::new(),
+            cache_checked: HashSet::new(),
+            cache_repair_running: false,
+            cache_repair_manual: false,
+            cache_repair_result: None,
+            cache_sizes: None,
+            cache_sizes_pending: false,
             cache_dir,
             preview_maps: Vec::new(),
             preview_active_tab: 0,
             preview_textures: HashMap::new(),
             preview_loading: HashSet::new(),
+            preview_hires: HashMap::new(),
+            preview_hires_loading: HashSet::new(),
             preview_zoom: 1.0,
             preview_offset: egui::Vec2::ZERO,
             preview_dragging: false,
             preview_needs_fit: false,
-            sort_column: Some(SortColumn::Name),
-            sort_direction: SortDirection::Ascending,
+            sort_column: if settings.compact_view || settings.link_view_sorts {
+                settings.list_sort.0
+            } else {
+                settings.grid_sort.0
+            },
+            sort_direction: if settings.compact_view || settings.link_view_sorts {
+                settings.list_sort.1
+            } else {
+                settings.grid_sort.1
+            },
             saved_sort: None,
+            list_sort: settings.list_sort,
+            grid_sort: settings.grid_sort,
+            link_view_sorts: settings.link_view_sorts,
+            group_by: settings.group_by,
+            collapsed_groups: HashSet::new(),
+            list_groups: Vec::new(),
+            list_rows: Vec::new(),
+            list_row_of_pos: Vec::new(),
+            group_search_matches: settings.group_search_matches,
+            match_tiers: HashMap::new(),
+            grid_sections: Vec::new(),
             scroll_index_markers: Vec::new(),
             scroll_target_row: None,
             main_scroll_offset: 0.0,
@@ -239,27 +500,95 @@ impl App {
             update_in_progress: false,
             app_update_error: None,
             app_update_success: None,
-            toast_message: None,
-            toast_start: None,
+            app_update_progress: Arc::new(Mutex::new(AppUpdateProgress::default())),
+            app_update_cancel: None,
+            toasts: std::collections::VecDeque::new(),
+            next_toast_id: 0,
             show_download_modal: false,
             show_download_log: false,
+            download_log_expanded: false,
+            show_download_queue: false,
+            taskbar: TaskbarProgress::new(cc),
             download_log_filter: None,
+            expanded_failures: HashSet::new(),
+            download_log_search: String::new(),
+            download_log_sort: None,
+            download_log_sort_desc: false,
             cancel_token: None,
             play_sound_on_complete: settings.play_sound,
-            window_pos: None,
-            window_size: None,
+            sound_on_failure: settings.sound_on_failure,
+            sound_on_db_update: settings.sound_on_db_update,
+            notification_sound: settings.notification_sound,
+            custom_sound_path: settings.custom_sound_path.as_ref().map(PathBuf::from),
+            sound_volume: settings.sound_volume.clamp(0.0, 1.0),
+            seen_failed_count: 0,
+            failure_sound_at: None,
+            max_retries: settings.max_retries.max(1),
+            precheck_urls: settings.precheck_urls,
+            minimize_to_tray: settings.minimize_to_tray,
+            open_folder_after_download: settings.open_folder_after_download,
+            post_download_command: settings.post_download_command.clone(),
+            post_download_command_confirm: false,
+            collision_strategy: settings.collision_strategy,
+            confirm_downloads: settings.confirm_downloads,
+            download_companion_files: settings.download_companion_files,
+            purge_removed_months: settings.purge_removed_months,
+            local_server_enabled: settings.local_server_enabled,
+            local_server_port: settings.local_server_port,
+            local_server: None,
+            local_server_map: Arc::new(Mutex::new("null".to_string())),
+            local_server_current: (None, false),
+            download_confirm: None,
+            download_confirm_overwrite: false,
+            batch_collision: None,
+            precheck_order: None,
+            precheck_cancel: None,
+            next_batch: None,
+            precheck_dead: Vec::new(),
+            window_pos: settings.window_x.zip(settings.window_y).map(|(x, y)| egui::pos2(x, y)),
+            window_size: settings.window_w.zip(settings.window_h).map(|(w, h)| egui::vec2(w, h)),
+            window_maximized: settings.window_maximized,
+            window_monitor: settings.window_monitor_w.zip(settings.window_monitor_h).map(|(w, h)| egui::vec2(w, h)),
+            window_restore_pending: true,
             filter_downloaded: 0,
-            year_mode_range: true,
-            year_range: None,
+            year_mode_range: settings.year_mode_range,
+            year_range: settings.year_range,
+            filter_counts: FilterCounts::default(),
+            match_count: None,
+            search_elsewhere: 0,
+            relaxed_filters: None,
+            match_count_change: None,
+            search_generation: Arc::new(AtomicU64::new(0)),
+            search_pending: false,
+            search_corpus: None,
             filter_years: HashSet::new(),
             available_years: Vec::new(),
+            year_by_month: settings.year_by_month,
+            month_range: settings.month_range,
+            available_months: Vec::new(),
+            include_undated: settings.include_undated,
             was_downloading: false,
+            show_exit_confirm: false,
+            show_shortcuts: false,
+            exit_when_idle: false,
+            exit_confirmed: false,
+            tray: Tray::new(cc),
+            hidden_to_tray: false,
             needs_center: false,
             data_dir,
             view_switch_count: 0,
-            list_row_height: 29.0,
-            grid_scroll_target: None,
+            list_row_height: settings.row_density.list_row_height(settings.list_thumbnails),
+            scroll_target_offset: None,
             grid_scroll_to_row: None,
+            url_star_dirs: settings.url_star_dirs.clone(),
+            author_aliases: settings.author_aliases.clone(),
+            author_popup: None,
+            map_file_info: HashMap::new(),
+            map_file_info_pending: HashSet::new(),
+            outdated_names: HashSet::new(),
+            outdated_check_due: true,
+            outdated_check_running: false,
+            log_level: settings.log_level.clone(),
         };
 
         // Compute available years from maps
@@ -278,24 +607,70 @@ impl App {
         years.sort();
         years.dedup();
         app.available_years = years.clone();
-        app.filter_years = years.into_iter().collect();
+        let mut months: Vec<i32> = app.maps.iter().filter_map(Self::map_month).collect();
+        months.sort();
+        months.dedup();
+        app.available_months = months;
+        app.filter_years = match &settings.filter_years {
+            Some(saved) => saved.iter().copied().filter(|y| years.contains(y)).collect(),
+            None => years.into_iter().collect(),
+        };
+
+        // Apply restored filters (also builds the initial scroll index)
+        app.normalize_authors();
+        app.purge_removed_maps();
+        app.apply_filters();
+        app.restart_local_server();
+        app.pick_map_of_the_day();
 
-        // Build initial scroll index
-        app.build_scroll_index();
+        // An unparsable file is kept as settings.json.invalid when it could be copied
+        // (see Settings::load); one that couldn't be read at all has no copy
+        match settings_source {
+            SettingsSource::Backup(None) => app.push_toast(
+                "Settings file was missing, restored the previous copy",
+                ToastSeverity::Warning,
+            ),
+            SettingsSource::Backup(Some(error)) => app.push_toast(
+                format!("Settings file {}, restored the previous copy.{}", error.reason(), error.kept_note()),
+                ToastSeverity::Warning,
+            ),
+            SettingsSource::DefaultsAfterError(error) => app.push_toast(
+                format!("Settings were reset because the file {}.{}", error.reason(), error.kept_note()),
+                ToastSeverity::Error,
+            ),
+            // No settings anywhere: a fresh install
+            SettingsSource::Defaults => app.start_onboarding(),
+            SettingsSource::File => {}
+        }
+
+        // --filter from the command line, applied over the restored filters
+        if let Some(spec) = startup_filter {
+            if let Err(e) = app.apply_filter_spec(&spec) {
+                warn!(filter = %spec, error = %e, "Startup filter ignored");
+                app.push_toast(format!("Startup filter ignored: {}", e), ToastSeverity::Warning);
+            }
+        }
         app
     }
 
     pub fn save_settings(&self) {
+        // The active sort belongs to the current view (or both when linked)
+        let current_sort = self.saved_sort.unwrap_or((self.sort_column, self.sort_direction));
         let settings = Settings {
             window_x: self.window_pos.map(|p| p.x),
             window_y: self.window_pos.map(|p| p.y),
             window_w: self.window_size.map(|s| s.x),
             window_h: self.window_size.map(|s| s.y),
+            window_maximized: self.window_maximized,
+            window_monitor_w: self.window_monitor.map(|m| m.x),
+            window_monitor_h: self.window_monitor.map(|m| m.y),
             col_category: self.show_category,
             col_stars: self.show_stars,
             col_points: self.show_points,
             col_author: self.show_author,
             col_release_date: self.show_release_date,
+            col_star_label: self.show_star_label,
+            grid_info: Some(self.grid_info),
             col_w_name: self.col_widths[0],
             col_w_category: self.col_widths[1],
             col_w_stars: self.col_widths[2],
@@ -305,17 +680,179 @@ impl App {
             col_order: self.col_order.clone(),
             compact_view: self.compact_view,
             large_thumbnails: self.large_thumbnails,
+            grid_card_scale: self.grid_card_scale,
+            date_format: self.date_format,
+            row_density: self.row_density,
+            list_thumbnails: self.list_thumbnails,
+            auto_scroll_selection: self.auto_scroll_selection,
+            type_to_search: self.type_to_search,
+            map_of_the_day: self.map_of_the_day,
+            map_of_the_day_shown: self.map_of_the_day_shown.clone(),
+            search_case_sensitive: self.search_case_sensitive,
+            search_whole_word: self.search_whole_word,
+            accent_color: [self.accent_color.r(), self.accent_color.g(), self.accent_color.b()],
+            // Only pin a value when it differs from the OS preference
+            smooth_scroll: self.animation_override(self.smooth_scroll),
+            button_press_animation: self.animation_override(self.button_press_animation),
+            toast_fade: self.animation_override(self.toast_fade),
             download_path: Some(self.download_path_str.clone()),
+            list_sort: if self.compact_view || self.link_view_sorts {
+                current_sort
+            } else {
+                self.list_sort
+            },
+            grid_sort: if !self.compact_view || self.link_view_sorts {
+                current_sort
+            } else {
+                self.grid_sort
+            },
+            link_view_sorts: self.link_view_sorts,
+            group_by: self.group_by,
+            group_search_matches: self.group_search_matches,
+            show_filters: self.show_filters,
+            category_mode_range: self.category_mode_range,
+            category_range: self.category_range,
+            filter_categories: self.filter_categories,
+            stars_mode_range: self.stars_mode_range,
+            stars_range: self.stars_range,
+            filter_stars: self.filter_stars,
+            per_category_stars: self.per_category_stars,
+            category_stars: self.category_stars,
+            year_mode_range: self.year_mode_range,
+            year_range: self.year_range,
+            year_by_month: self.year_by_month,
+            month_range: self.month_range,
+            include_undated: self.include_undated,
+            // Store None when every year is selected so newly added years show up
+            filter_years: if self.filter_years.len() == self.available_years.len() {
+                None
+            } else {
+                let mut years: Vec<i32> = self.filter_years.iter().copied().collect();
+                years.sort();
+                Some(years)
+            },
             play_sound: self.play_sound_on_complete,
+            sound_on_failure: self.sound_on_failure,
+            sound_on_db_update: self.sound_on_db_update,
+            notification_sound: self.notification_sound,
+            custom_sound_path: self.custom_sound_path.as_ref().map(|p| p.to_string_lossy().to_string()),
+            sound_volume: self.sound_volume,
+            max_retries: self.max_retries,
+            precheck_urls: self.precheck_urls,
+            minimize_to_tray: self.minimize_to_tray,
+            open_folder_after_download: self.open_folder_after_download,
+            post_download_command: self.post_download_command.clone(),
+            collision_strategy: self.collision_strategy,
+            confirm_downloads: self.confirm_downloads,
+            download_companion_files: self.download_companion_files,
+            purge_removed_months: self.purge_removed_months,
+            local_server_enabled: self.local_server_enabled,
+            local_server_port: self.local_server_port,
+            url_star_dirs: self.url_star_dirs.clone(),
+            author_aliases: self.author_aliases.clone(),
+            log_level: self.log_level.clone(),
         };
         settings.save(&self.data_dir);
     }
 
+    fn animation_override(&self, enabled: bool) -> Option<bool> {
+        (enabled == self.reduce_motion_default).then_some(enabled)
+    }
+
     /// Backwards-compatible alias
     pub fn save_column_settings(&self) {
         self.save_settings();
     }
 
+    /// Play the sound picked in Settings (or the failure sound) at the configured volume
+    pub fn play_notification(&self, failure: bool) {
+        let source = if failure {
+            SoundSource::failure()
+        } else {
+            SoundSource::for_choice(self.notification_sound, self.custom_sound_path.as_deref())
+        };
+        sound::play(source, self.sound_volume);
+    }
+
+    pub fn set_row_density(&mut self, density: RowDensity) {
+        self.set_list_rows(density, self.list_thumbnails);
+    }
+
+    pub fn set_list_thumbnails(&mut self, thumbnails: bool) {
+        self.set_list_rows(self.row_density, thumbnails);
+    }
+
+    /// Change the list row height, keeping the top visible map in place
+    fn set_list_rows(&mut self, density: RowDensity, thumbnails: bool) {
+        if density == self.row_density && thumbnails == self.list_thumbnails {
+            return;
+        }
+        let top_row = (self.main_scroll_offset / self.list_row_height).round() as usize;
+        self.row_density = density;
+        self.list_thumbnails = thumbnails;
+        self.list_row_height = density.list_row_height(thumbnails);
+        if self.compact_view {
+            self.scroll_sync_item = Some(self.list_pos_of(top_row));
+        }
+    }
+
+    /// Start, stop or rebind the local JSON endpoint to match the settings
+    pub fn restart_local_server(&mut self) {
+        self.local_server = None; // unbinds the old port first
+        if !self.local_server_enabled {
+            return;
+        }
+        match LocalServer::start(self.local_server_port, self.local_server_map.clone()) {
+            Ok(server) => self.local_server = Some(server),
+            Err(e) => {
+                warn!(port = self.local_server_port, error = %e, "Failed to start local map endpoint");
+                self.push_toast(
+                    format!("Couldn't start the local endpoint on port {}: {}", self.local_server_port, e),
+                    ToastSeverity::Error,
+                );
+            }
+        }
+    }
+
+    /// Publish the previewed map (or else the last selected one) to the local endpoint
+    pub fn update_local_server_map(&mut self) {
+        if self.local_server.is_none() {
+            return;
+        }
+        let previewed = self.preview_maps.get(self.preview_active_tab).cloned();
+        let current = previewed
+            .clone()
+            .or_else(|| self.last_selected.and_then(|idx| self.maps.get(idx)).map(|m| m.name.clone()));
+        // Re-publish when the map's downloaded state changes, not only the map itself
+        let downloaded = current.as_ref().is_some_and(|name| self.downloaded_names.contains(name));
+        let key = (current.clone(), downloaded);
+        if key == self.local_server_current {
+            return;
+        }
+        let json = current
+            .as_ref()
+            .and_then(|name| self.maps.iter().find(|m| &m.name == name))
+            .map(|map| {
+                serde_json::json!({
+                    "name": map.name,
+                    "category": map.category,
+                    "stars": map.stars,
+                    "points": map.points,
+                    "author": map.author,
+                    "authors": map.authors,
+                    "release_date": map.release_date,
+                    "size": map.size,
+                    "downloaded": downloaded,
+                    "url": self.get_map_url(map),
+                    "source": if previewed.is_some() { "preview" } else { "selection" },
+                })
+                .to_string()
+            })
+            .unwrap_or_else(|| "null".to_string());
+        *self.local_server_map.lock().unwrap() = json;
+        self.local_server_current = key;
+    }
+
     pub fn is_col_visible(&self, col_idx: usize) -> bool {
         match col_idx {
             0 => true,
@@ -358,10 +895,105 @@ impl App {
         "Easy", "Main", "Hard", "Insane", "Extreme", "Solo", "Mod", "Extra",
     ];
 
-    pub fn get_map_url(map: &Map) -> String {
-        format!(
-            "{}/{}/{}star/{}.map",
-            MAPS_BASE_URL, map.category, map.stars, map.name
-        )
+    /// Queue a toast notification (up to three are shown at once)
+    pub fn push_toast(&mut self, message: impl Into<String>, severity: ToastSeverity) {
+        self.push_toast_with_action(message, severity, None, None);
+    }
+
+    /// Queue a toast with an inline button, e.g. "Undo"
+    pub fn push_toast_with_button(&mut self, message: impl Into<String>, severity: ToastSeverity, button: ToastAction) {
+        self.push_toast_with_action(message, severity, None, Some(button));
+    }
+
+    pub fn push_toast_with_action(
+        &mut self,
+        message: impl Into<String>,
+        severity: ToastSeverity,
+        action: Option<ToastAction>,
+        button: Option<ToastAction>,
+    ) {
+        self.toasts.push_back(Toast {
+            id: self.next_toast_id,
+            message: message.into(),
+            severity,
+            action,
+            button,
+            shown_at: None,
+        });
+        self.next_toast_id += 1;
+    }
+
+    pub fn get_map_url(&self, map: &Map) -> String {
+        build_map_url(&self.url_star_dirs, &map.category, map.stars, &map.name)
+    }
+}
+
+/// Clamp a `(lo, hi)` filter range to `min..=max`, keeping `lo <= hi`
+fn clamp_range((lo, hi): (u8, u8), min: u8, max: u8) -> (u8, u8) {
+    let hi = hi.clamp(min, max);
+    (lo.clamp(min, hi), hi)
+}
+
+/// Build a map download URL as `{category}/{star dir}/{name}.map`.
+/// The star directory comes from `star_dirs` (falling back to `{stars}star`)
+/// and is left out entirely when it resolves to an empty string.
+/// Each part is percent-encoded, so names with spaces, `#`, `?` or `/` stay one path segment.
+pub fn build_map_url(
+    star_dirs: &HashMap<String, String>,
+    category: &str,
+    stars: i32,
+    name: &str,
+) -> String {
+    let star_dir = star_dirs
+        .get(category)
+        .map(String::as_str)
+        .unwrap_or(DEFAULT_STAR_DIR)
+        .replace("{stars}", &stars.to_string());
+    let mut url = reqwest::Url::parse(MAPS_BASE_URL).expect("MAPS_BASE_URL is a valid URL");
+    {
+        let mut segments = url.path_segments_mut().expect("MAPS_BASE_URL has a path");
+        segments.push(category);
+        if !star_dir.is_empty() {
+            segments.push(&star_dir);
+        }
+        segments.push(&format!("{}.map", name));
+    }
+    url.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(category: &str, stars: i32, name: &str) -> String {
+        build_map_url(&HashMap::new(), category, stars, name)
+    }
+
+    #[test]
+    fn default_layout_puts_maps_under_their_star_directory() {
+        assert_eq!(url("Easy", 1, "Kobra"), format!("{}/Easy/1star/Kobra.map", MAPS_BASE_URL));
+        assert_eq!(url("Solo", 0, "Alone"), format!("{}/Solo/0star/Alone.map", MAPS_BASE_URL));
+    }
+
+    #[test]
+    fn configured_star_directories_are_used_or_left_out() {
+        let dirs = HashMap::from([
+            ("Mod".to_string(), String::new()),
+            ("Extra".to_string(), "stars-{stars}".to_string()),
+        ]);
+        assert_eq!(build_map_url(&dirs, "Mod", 3, "Mods"), format!("{}/Mod/Mods.map", MAPS_BASE_URL));
+        assert_eq!(build_map_url(&dirs, "Extra", 2, "Ex"), format!("{}/Extra/stars-2/Ex.map", MAPS_BASE_URL));
+    }
+
+    #[test]
+    fn names_with_spaces_and_special_characters_are_encoded() {
+        let base = format!("{}/Main/3star/", MAPS_BASE_URL);
+        assert_eq!(url("Main", 3, "Sunny Side Up"), format!("{}Sunny%20Side%20Up.map", base));
+        assert_eq!(url("Main", 3, "Run #2?"), format!("{}Run%20%232%3F.map", base));
+        assert_eq!(url("Main", 3, "100%"), format!("{}100%25.map", base));
+        assert_eq!(url("Main", 3, "A/B"), format!("{}A%2FB.map", base));
+        assert_eq!(url("Main", 3, "Olé"), format!("{}Ol%C3%A9.map", base));
+        // Characters allowed in a path stay readable
+        assert_eq!(url("Main", 3, "Tom&Jerry's(1)"), format!("{}Tom&Jerry's(1).map", base));
     }
 }
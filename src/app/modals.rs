@@ -1,10 +1,1344 @@
-//! Modal dialogs (download modal, update dialogs)
+//! Modal dialogs
 //!
-//! This module will contain modal rendering methods.
-//! Currently these methods remain in main.rs as they are tightly
-//! coupled with the eframe::App update loop.
+//! The settings modal lives here, one function per category, along with the
+//! author popup and the paste-a-list dialog. The download, update and confirmation modals are still in main.rs.
 
 use super::App;
+use crate::constants::SHORTCUTS;
+use crate::settings::{self, Settings};
+use crate::theme;
+use crate::tray::Tray;
+use crate::ui::components::{render_stars, truncate_chars};
+use crate::utils::format_bytes;
+use crate::types::*;
+use eframe::egui;
+use std::path::PathBuf;
+use tracing::info;
 
-// Modal rendering methods are currently in main.rs
-// They will be migrated here in a future refactoring phase
+const NAV_WIDTH: f32 = 140.0;
+const CONTENT_WIDTH: f32 = 360.0;
+const CONTENT_HEIGHT: f32 = 420.0;
+const AUTHOR_MODAL_WIDTH: f32 = 460.0;
+const AUTHOR_TABLE_HEIGHT: f32 = 320.0;
+
+/// Left-hand categories of the settings modal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SettingsCategory {
+    #[default]
+    View,
+    Downloads,
+    Network,
+    Notifications,
+    Database,
+    Advanced,
+}
+
+impl SettingsCategory {
+    pub const ALL: [SettingsCategory; 6] = [
+        Self::View,
+        Self::Downloads,
+        Self::Network,
+        Self::Notifications,
+        Self::Database,
+        Self::Advanced,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::View => "View",
+            Self::Downloads => "Downloads",
+            Self::Network => "Network",
+            Self::Notifications => "Notifications",
+            Self::Database => "Database",
+            Self::Advanced => "Advanced",
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            Self::View => egui_phosphor::regular::EYE,
+            Self::Downloads => egui_phosphor::regular::DOWNLOAD_SIMPLE,
+            Self::Network => egui_phosphor::regular::GLOBE,
+            Self::Notifications => egui_phosphor::regular::BELL,
+            Self::Database => egui_phosphor::regular::DATABASE,
+            Self::Advanced => egui_phosphor::regular::WRENCH,
+        }
+    }
+}
+
+/// Lays out settings rows, skipping those that don't match the search.
+/// A group heading is only drawn once one of its rows is shown, so empty
+/// groups disappear while searching.
+struct SettingsPane {
+    query: String, // lowercase; empty shows everything
+    heading: Option<&'static str>,
+    heading_matches: bool,
+    shown: usize,
+}
+
+impl SettingsPane {
+    fn new(query: &str) -> Self {
+        Self {
+            query: query.trim().to_lowercase(),
+            heading: None,
+            heading_matches: false,
+            shown: 0,
+        }
+    }
+
+    /// Start a group; a search matching its title shows all of its rows
+    fn group(&mut self, title: &'static str) {
+        self.heading_matches = !self.query.is_empty() && title.to_lowercase().contains(&self.query);
+        self.heading = Some(title);
+    }
+
+    /// Whether the row labelled `label` should be drawn (draws a pending heading first)
+    fn row(&mut self, ui: &mut egui::Ui, label: &str) -> bool {
+        let visible = self.query.is_empty()
+            || self.heading_matches
+            || label.to_lowercase().contains(&self.query);
+        if !visible {
+            return false;
+        }
+        if let Some(title) = self.heading.take() {
+            if self.shown > 0 {
+                ui.add_space(theme::SPACING_MD);
+            }
+            ui.add(egui::Label::new(
+                egui::RichText::new(title).size(13.0).color(theme::accent()),
+            ).selectable(false));
+            ui.add_space(2.0);
+        }
+        self.shown += 1;
+        true
+    }
+}
+
+/// Button painted like the other settings actions (fixed size, press animation)
+fn settings_button(ui: &mut egui::Ui, width: f32, base: egui::Color32, text: String) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(width, 26.0), egui::Sense::click());
+    if response.hovered() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    }
+    let (fill, draw_rect) = theme::button_visual(&response, base, rect);
+    ui.painter().rect_filled(draw_rect, 4.0, fill);
+    ui.painter().text(
+        draw_rect.center(), egui::Align2::CENTER_CENTER,
+        &text, egui::FontId::proportional(12.0),
+        egui::Color32::WHITE,
+    );
+    theme::focus_ring(ui, &response, rect, 4.0);
+    response
+}
+
+/// X button for modal title bars (matches preview window style)
+fn modal_close_button(ui: &mut egui::Ui) -> bool {
+    let close_size = 24.0;
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(close_size, close_size),
+        egui::Sense::click(),
+    );
+    let close_color = if response.hovered() {
+        ui.painter().rect_filled(rect, 4.0, theme::BG_SURFACE);
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        theme::STATUS_ERROR
+    } else {
+        theme::TEXT_DIM
+    };
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        egui_phosphor::regular::X,
+        egui::FontId::proportional(16.0),
+        close_color,
+    );
+    response.clicked()
+}
+
+/// Dimmed label in front of a combo box or slider
+fn row_label(ui: &mut egui::Ui, text: &str) {
+    ui.add(egui::Label::new(
+        egui::RichText::new(text).size(13.0).color(theme::TEXT_SECONDARY),
+    ).selectable(false));
+}
+
+impl App {
+    pub fn render_settings_modal(&mut self, ctx: &egui::Context) {
+        if !self.show_settings {
+            return;
+        }
+        let modal_response = egui::Modal::new(egui::Id::new("settings_modal"))
+            .backdrop_color(egui::Color32::from_black_alpha(120))
+            .frame(egui::Frame::new()
+                .fill(egui::Color32::from_rgb(0x1a, 0x1a, 0x1e))
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(0x2a, 0x2a, 0x2e)))
+                .corner_radius(8.0)
+                .inner_margin(egui::Margin::same(20)))
+            .show(ctx, |ui| {
+                ui.set_width(NAV_WIDTH + CONTENT_WIDTH + 17.0);
+
+                // Title bar with close button (matches preview window style)
+                ui.horizontal(|ui| {
+                    ui.add(egui::Label::new(
+                        egui::RichText::new("Settings").size(16.0).strong(),
+                    ).selectable(false));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if modal_close_button(ui) {
+                            self.show_settings = false;
+                        }
+                    });
+                });
+                ui.add_space(4.0);
+
+                // Search box (filters rows across all categories)
+                egui::Frame::new()
+                    .fill(theme::BG_INPUT)
+                    .stroke(egui::Stroke::new(1.0, theme::BORDER_SUBTLE))
+                    .corner_radius(4.0)
+                    .inner_margin(egui::Margin::symmetric(6, 4))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(theme::TEXT_DIM, egui_phosphor::regular::MAGNIFYING_GLASS);
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.settings_search)
+                                    .hint_text("Search settings")
+                                    .frame(false)
+                                    .desired_width(f32::INFINITY)
+                                    .font(egui::FontId::proportional(13.0)),
+                            );
+                        });
+                    });
+                ui.add_space(theme::SPACING_SM);
+                ui.separator();
+                ui.add_space(theme::SPACING_SM);
+
+                let searching = !self.settings_search.trim().is_empty();
+                ui.horizontal_top(|ui| {
+                    // Category list
+                    ui.allocate_ui_with_layout(
+                        egui::vec2(NAV_WIDTH, CONTENT_HEIGHT),
+                        egui::Layout::top_down(egui::Align::Min),
+                        |ui| {
+                            ui.spacing_mut().item_spacing.y = 2.0;
+                            for category in SettingsCategory::ALL {
+                                self.settings_nav_item(ui, category, searching);
+                            }
+                        },
+                    );
+                    ui.separator();
+
+                    // Content pane (fixed size so switching categories doesn't resize the modal)
+                    ui.allocate_ui_with_layout(
+                        egui::vec2(CONTENT_WIDTH, CONTENT_HEIGHT),
+                        egui::Layout::top_down(egui::Align::Min),
+                        |ui| {
+                            egui::ScrollArea::vertical()
+                                .id_salt("settings_content")
+                                .max_height(CONTENT_HEIGHT)
+                                .auto_shrink([false, false])
+                                .show(ui, |ui| {
+                                    let mut pane = SettingsPane::new(&self.settings_search);
+                                    let categories: Vec<SettingsCategory> = if searching {
+                                        SettingsCategory::ALL.to_vec()
+                                    } else {
+                                        vec![self.settings_category]
+                                    };
+                                    for category in categories {
+                                        self.settings_section(ui, ctx, &mut pane, category);
+                                    }
+                                    if pane.shown == 0 {
+                                        ui.add(egui::Label::new(
+                                            egui::RichText::new("No matching settings").color(theme::TEXT_DIM),
+                                        ).selectable(false));
+                                    }
+                                });
+                        },
+                    );
+                });
+
+                ui.add_space(theme::SPACING_SM);
+                ui.separator();
+                ui.add_space(theme::SPACING_SM);
+                self.settings_footer(ui, ctx);
+            });
+
+        if modal_response.should_close() {
+            self.show_settings = false;
+        }
+        if !self.show_settings {
+            self.settings_reset_confirm = false;
+        }
+    }
+
+    fn settings_nav_item(&mut self, ui: &mut egui::Ui, category: SettingsCategory, searching: bool) {
+        let selected = !searching && self.settings_category == category;
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(NAV_WIDTH, 28.0), egui::Sense::click());
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        }
+        if selected {
+            ui.painter().rect_filled(rect, 4.0, theme::with_alpha(theme::accent(), 30));
+        } else if response.hovered() {
+            ui.painter().rect_filled(rect, 4.0, theme::BG_SURFACE);
+        }
+        let color = if selected { theme::accent() } else { theme::TEXT_SECONDARY };
+        ui.painter().text(
+            egui::pos2(rect.min.x + 8.0, rect.center().y),
+            egui::Align2::LEFT_CENTER,
+            format!("{}  {}", category.icon(), category.label()),
+            egui::FontId::proportional(13.0),
+            color,
+        );
+        theme::focus_ring(ui, &response, rect, 4.0);
+        theme::toggle_info(&response, category.label(), selected);
+        if response.clicked() {
+            self.settings_category = category;
+            self.settings_search.clear();
+        }
+    }
+
+    fn settings_section(
+        &mut self,
+        ui: &mut egui::Ui,
+        ctx: &egui::Context,
+        pane: &mut SettingsPane,
+        category: SettingsCategory,
+    ) {
+        match category {
+            SettingsCategory::View => self.settings_view(ui, ctx, pane),
+            SettingsCategory::Downloads => self.settings_downloads(ui, pane),
+            SettingsCategory::Network => self.settings_network(ui, pane),
+            SettingsCategory::Notifications => self.settings_notifications(ui, pane),
+            SettingsCategory::Database => self.settings_database(ui, pane),
+            SettingsCategory::Advanced => self.settings_advanced(ui, pane),
+        }
+    }
+
+    fn settings_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, pane: &mut SettingsPane) {
+        pane.group("Appearance");
+        if pane.row(ui, "Large Thumbnails")
+            && theme::settings_checkbox(ui, self.large_thumbnails, "Large Thumbnails", true)
+        {
+            self.large_thumbnails = !self.large_thumbnails;
+        }
+        if pane.row(ui, "Same sort in list and grid")
+            && theme::settings_checkbox(ui, self.link_view_sorts, "Same sort in list and grid", true)
+        {
+            self.link_view_sorts = !self.link_view_sorts;
+            self.save_settings();
+        }
+        if pane.row(ui, "Map of the day on startup")
+            && theme::settings_checkbox(ui, self.map_of_the_day, "Map of the day on startup", true)
+        {
+            self.map_of_the_day = !self.map_of_the_day;
+            if !self.map_of_the_day {
+                self.featured_map = None;
+            }
+            self.save_settings();
+        }
+        if pane.row(ui, "Date format") {
+            ui.add_space(2.0);
+            ui.horizontal(|ui| {
+                row_label(ui, "Date format");
+                let before = self.date_format;
+                egui::ComboBox::from_id_salt("date_format")
+                    .selected_text(self.date_format.label())
+                    .width(110.0)
+                    .show_ui(ui, |ui| {
+                        for format in DateFormat::ALL {
+                            ui.selectable_value(&mut self.date_format, format, format.label());
+                        }
+                    });
+                if self.date_format != before {
+                    self.save_settings();
+                }
+            });
+        }
+        if pane.row(ui, "Row density") {
+            ui.add_space(2.0);
+            ui.horizontal(|ui| {
+                row_label(ui, "Row density");
+                let mut density = self.row_density;
+                egui::ComboBox::from_id_salt("row_density")
+                    .selected_text(density.label())
+                    .width(110.0)
+                    .show_ui(ui, |ui| {
+                        for option in RowDensity::ALL {
+                            ui.selectable_value(&mut density, option, option.label());
+                        }
+                    });
+                if density != self.row_density {
+                    self.set_row_density(density);
+                    self.save_settings();
+                }
+            });
+        }
+        if pane.row(ui, "Accent color") {
+            ui.add_space(2.0);
+            ui.horizontal(|ui| {
+                row_label(ui, "Accent color");
+                let picker = egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.accent_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+                if picker.changed() {
+                    theme::set_accent(ctx, self.accent_color);
+                    self.accent_save_pending = true;
+                }
+                if self.accent_color != theme::DEFAULT_ACCENT
+                    && ui
+                        .add(egui::Label::new(
+                            egui::RichText::new(egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE)
+                                .color(theme::TEXT_DIM),
+                        ).sense(egui::Sense::click()))
+                        .on_hover_text("Reset to default")
+                        .clicked()
+                {
+                    self.accent_color = theme::DEFAULT_ACCENT;
+                    theme::set_accent(ctx, self.accent_color);
+                    self.accent_save_pending = true;
+                }
+            });
+        }
+        // Save once the picker is released rather than on every drag frame
+        if self.accent_save_pending && !ctx.input(|i| i.pointer.any_down()) {
+            self.accent_save_pending = false;
+            self.save_settings();
+        }
+        if pane.row(ui, "Card Size") {
+            ui.add_space(2.0);
+            ui.horizontal(|ui| {
+                row_label(ui, "Card Size");
+                let slider = ui.add(
+                    egui::Slider::new(
+                        &mut self.grid_card_scale,
+                        theme::CARD_SCALE_MIN..=theme::CARD_SCALE_MAX,
+                    )
+                    .show_value(false),
+                );
+                if slider.double_clicked() {
+                    self.grid_card_scale = 1.0;
+                }
+                if slider.drag_stopped() || slider.double_clicked() {
+                    self.save_settings();
+                }
+            });
+        }
+
+        pane.group("Animations");
+        let mut anim_changed = false;
+        for (val, label) in [
+            (&mut self.smooth_scroll, "Smooth scrolling"),
+            (&mut self.button_press_animation, "Button press effect"),
+            (&mut self.toast_fade, "Fade out notifications"),
+        ] {
+            if pane.row(ui, label) && theme::settings_checkbox(ui, *val, label, true) {
+                *val = !*val;
+                anim_changed = true;
+            }
+        }
+        if anim_changed {
+            theme::set_press_animation(self.button_press_animation);
+            self.save_settings();
+        }
+
+        pane.group("Navigation");
+        if pane.row(ui, "Keep keyboard selection in view")
+            && theme::settings_checkbox(ui, self.auto_scroll_selection, "Keep keyboard selection in view", true)
+        {
+            self.auto_scroll_selection = !self.auto_scroll_selection;
+            self.save_settings();
+        }
+        if pane.row(ui, "Type anywhere to search")
+            && theme::settings_checkbox(ui, self.type_to_search, "Type anywhere to search", true)
+        {
+            self.type_to_search = !self.type_to_search;
+            self.save_settings();
+        }
+        if pane.row(ui, "Group search results by match")
+            && theme::settings_checkbox(ui, self.group_search_matches, "Group search results by match", true)
+        {
+            self.group_search_matches = !self.group_search_matches;
+            self.save_settings();
+            self.apply_filters();
+        }
+
+        pane.group("List Columns");
+        if pane.row(ui, "Name") {
+            theme::settings_checkbox(ui, true, "Name", false); // Always enabled, dimmed
+        }
+        if pane.row(ui, "Thumbnail")
+            && theme::settings_checkbox(ui, self.list_thumbnails, "Thumbnail before the name", true)
+        {
+            self.set_list_thumbnails(!self.list_thumbnails);
+            self.save_settings();
+        }
+        let mut changed = false;
+        for (val, label) in [
+            (&mut self.show_category, "Category"),
+            (&mut self.show_stars, "Stars"),
+            (&mut self.show_points, "Points"),
+            (&mut self.show_author, "Author"),
+            (&mut self.show_release_date, "Release Date"),
+        ] {
+            if pane.row(ui, label) && theme::settings_checkbox(ui, *val, label, true) {
+                *val = !*val;
+                changed = true;
+            }
+        }
+        if pane.row(ui, "Star count (3/5)")
+            && theme::settings_checkbox(ui, self.show_star_label, "Star count (3/5)", self.show_stars)
+        {
+            self.show_star_label = !self.show_star_label;
+            changed = true;
+        }
+
+        pane.group("Grid Cards");
+        let info = &mut self.grid_info;
+        for (val, label) in [
+            (&mut info.category, "Category"),
+            (&mut info.stars, "Stars"),
+            (&mut info.points, "Points"),
+            (&mut info.release_date, "Release Date"),
+        ] {
+            if pane.row(ui, label) && theme::settings_checkbox(ui, *val, label, true) {
+                *val = !*val;
+                changed = true;
+            }
+        }
+        if pane.row(ui, "Author (large thumbnails)")
+            && theme::settings_checkbox(ui, info.author, "Author (large thumbnails)", true)
+        {
+            info.author = !info.author;
+            changed = true;
+        }
+        if pane.row(ui, "Star count (3/5)")
+            && theme::settings_checkbox(ui, info.star_label, "Star count (3/5)", info.stars)
+        {
+            info.star_label = !info.star_label;
+            changed = true;
+        }
+        if changed {
+            self.save_column_settings();
+        }
+    }
+
+    fn settings_downloads(&mut self, ui: &mut egui::Ui, pane: &mut SettingsPane) {
+        pane.group("Download Path");
+        if pane.row(ui, "Download folder") {
+            let path_changed = ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 4.0;
+                let browse_width = 28.0 + 4.0; // button + spacing
+                let frame_padding = 12.0 + 2.0; // inner_margin (6*2) + stroke (1*2)
+                let text_width = (ui.available_width() - browse_width - frame_padding).max(40.0);
+                // Text input styled like search box
+                let te = egui::Frame::new()
+                    .fill(theme::BG_INPUT)
+                    .stroke(egui::Stroke::new(1.0, theme::BORDER_SUBTLE))
+                    .corner_radius(4.0)
+                    .inner_margin(egui::Margin::symmetric(6, 4))
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.download_path_str)
+                                .frame(false)
+                                .desired_width(text_width)
+                                .font(egui::FontId::proportional(13.0)),
+                        )
+                    }).inner;
+                // Browse button (aligned to text input height)
+                let (rect, resp) = ui.allocate_exact_size(
+                    egui::vec2(28.0, 28.0), egui::Sense::click(),
+                );
+                if resp.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                    ui.painter().rect_filled(rect, 4.0, theme::BG_SURFACE);
+                }
+                ui.painter().text(
+                    rect.center(), egui::Align2::CENTER_CENTER,
+                    egui_phosphor::regular::FOLDER_OPEN,
+                    egui::FontId::proportional(16.0), theme::TEXT_SECONDARY,
+                );
+                let open_browser = resp.clicked() || te.double_clicked();
+                if open_browser {
+                    std::fs::create_dir_all(&self.download_path).ok();
+                    if let Some(path) = rfd::FileDialog::new()
+                        .set_directory(&self.download_path)
+                        .pick_folder()
+                    {
+                        self.download_path = path;
+                        self.download_path_str = self.download_path.to_string_lossy().to_string();
+                        self.check_download_path_now();
+                        self.save_settings();
+                    }
+                }
+                te.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))
+            }).inner;
+
+            if path_changed {
+                self.download_path = PathBuf::from(&self.download_path_str);
+                self.check_download_path_now();
+                self.save_settings();
+            }
+        }
+        if pane.row(ui, "Open Folder") {
+            ui.add_space(4.0);
+            let label = format!("{}  Open Folder", egui_phosphor::regular::FOLDER_OPEN);
+            if settings_button(ui, 120.0, theme::BTN_DEFAULT, label).clicked() {
+                std::fs::create_dir_all(&self.download_path).ok();
+                let _ = open::that(&self.download_path);
+            }
+        }
+        if pane.row(ui, "Run first-time setup again") {
+            ui.add_space(4.0);
+            let label = format!("{}  Run first-time setup again", egui_phosphor::regular::MAGIC_WAND);
+            if settings_button(ui, 200.0, theme::BTN_DEFAULT, label).clicked() {
+                self.start_onboarding();
+            }
+        }
+        if pane.row(ui, "If a map file already exists") {
+            ui.add_space(2.0);
+            ui.horizontal(|ui| {
+                row_label(ui, "If a map file already exists");
+                let before = self.collision_strategy;
+                egui::ComboBox::from_id_salt("collision_strategy")
+                    .selected_text(self.collision_strategy.label())
+                    .width(90.0)
+                    .show_ui(ui, |ui| {
+                        for strategy in CollisionStrategy::ALL {
+                            ui.selectable_value(&mut self.collision_strategy, strategy, strategy.label());
+                        }
+                    });
+                if self.collision_strategy != before {
+                    self.save_settings();
+                }
+            });
+        }
+        if pane.row(ui, "Confirm large downloads")
+            && theme::settings_checkbox(ui, self.confirm_downloads, "Confirm large downloads", true)
+        {
+            self.confirm_downloads = !self.confirm_downloads;
+            self.save_settings();
+        }
+        if pane.row(ui, "Download companion files")
+            && theme::settings_checkbox(ui, self.download_companion_files, "Download companion files", true)
+        {
+            self.download_companion_files = !self.download_companion_files;
+            self.save_settings();
+        }
+        if pane.row(ui, "Open folder when a batch finishes")
+            && theme::settings_checkbox(ui, self.open_folder_after_download, "Open folder when a batch finishes", true)
+        {
+            self.open_folder_after_download = !self.open_folder_after_download;
+            self.save_settings();
+        }
+        let command_on = self.post_download_command.is_some();
+        if pane.row(ui, "Run a command after each download")
+            && theme::settings_checkbox(
+                ui,
+                command_on || self.post_download_command_confirm,
+                "Run a command after each download",
+                true,
+            )
+        {
+            if command_on {
+                self.post_download_command = None;
+                self.save_settings();
+            } else {
+                self.post_download_command_confirm = !self.post_download_command_confirm;
+            }
+        }
+        if self.post_download_command_confirm && pane.row(ui, "Run a command after each download") {
+            // Enabling needs an explicit yes: the command runs with your permissions
+            ui.add(
+                egui::Label::new(
+                    egui::RichText::new(
+                        "The command runs a program on this computer after every downloaded map, \
+                         with your permissions. Only use commands you trust.",
+                    )
+                    .size(12.0)
+                    .color(theme::STATUS_WARNING),
+                )
+                .wrap(),
+            );
+            ui.horizontal(|ui| {
+                if ui.add(theme::button("I understand this runs a program")).clicked() {
+                    self.post_download_command = Some(String::new());
+                    self.post_download_command_confirm = false;
+                    self.save_settings();
+                }
+                if ui.add(theme::button("Cancel")).clicked() {
+                    self.post_download_command_confirm = false;
+                }
+            });
+        }
+        if self.post_download_command.is_some() && pane.row(ui, "Command") {
+            let command = self.post_download_command.get_or_insert_with(String::new);
+            let response = ui.add(
+                egui::TextEdit::singleline(command)
+                    .hint_text("\"C:\\Tools\\sync.exe\" \"{path}\"")
+                    .desired_width(ui.available_width())
+                    .font(egui::FontId::proportional(13.0)),
+            )
+            .on_hover_text("Runs without a shell. {path} is the saved file, {name} the map name.\nQuote arguments that contain spaces.");
+            if response.lost_focus() {
+                self.save_settings();
+            }
+        }
+
+        if Tray::supported() {
+            pane.group("Background");
+            if pane.row(ui, "Keep downloading in the tray when closed")
+                && theme::settings_checkbox(ui, self.minimize_to_tray, "Keep downloading in the tray when closed", true)
+            {
+                self.minimize_to_tray = !self.minimize_to_tray;
+                self.save_settings();
+            }
+        }
+    }
+
+    fn settings_network(&mut self, ui: &mut egui::Ui, pane: &mut SettingsPane) {
+        pane.group("Requests");
+        if pane.row(ui, "Retries per map") {
+            ui.horizontal(|ui| {
+                row_label(ui, "Retries per map");
+                let slider = ui.add(egui::Slider::new(&mut self.max_retries, 1..=10));
+                if slider.drag_stopped() || (slider.changed() && !slider.dragged()) {
+                    self.save_settings();
+                }
+            });
+        }
+        if pane.row(ui, "Check map links before downloading")
+            && theme::settings_checkbox(ui, self.precheck_urls, "Check map links before downloading", true)
+        {
+            self.precheck_urls = !self.precheck_urls;
+            self.save_settings();
+        }
+    }
+
+    fn settings_notifications(&mut self, ui: &mut egui::Ui, pane: &mut SettingsPane) {
+        pane.group("Sounds");
+        let mut changed = false;
+        for (val, label) in [
+            (&mut self.play_sound_on_complete, "Play sound on download complete"),
+            (&mut self.sound_on_failure, "Play sound when a map fails"),
+            (&mut self.sound_on_db_update, "Play sound after a database update"),
+        ] {
+            if pane.row(ui, label) && theme::settings_checkbox(ui, *val, label, true) {
+                *val = !*val;
+                changed = true;
+            }
+        }
+        if changed {
+            self.save_settings();
+        }
+        if pane.row(ui, "Sound") {
+            ui.add_space(2.0);
+            ui.horizontal(|ui| {
+                row_label(ui, "Sound");
+                let before = self.notification_sound;
+                egui::ComboBox::from_id_salt("notification_sound")
+                    .selected_text(self.notification_sound.label())
+                    .width(110.0)
+                    .show_ui(ui, |ui| {
+                        for sound in NotificationSound::ALL {
+                            ui.selectable_value(&mut self.notification_sound, sound, sound.label());
+                        }
+                    });
+                if self.notification_sound != before {
+                    self.save_settings();
+                }
+                if ui
+                    .add(egui::Button::new(egui_phosphor::regular::PLAY).frame(false))
+                    .on_hover_text("Preview")
+                    .clicked()
+                {
+                    self.play_notification(false);
+                }
+            });
+            if self.notification_sound == NotificationSound::Custom {
+                ui.horizontal(|ui| {
+                    let name = self
+                        .custom_sound_path
+                        .as_ref()
+                        .and_then(|p| p.file_name())
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "No file chosen (using Chime)".to_string());
+                    ui.add(egui::Label::new(
+                        egui::RichText::new(name).size(12.0).color(theme::TEXT_MUTED),
+                    ).truncate());
+                    if ui
+                        .add(egui::Button::new(egui_phosphor::regular::FOLDER_OPEN).frame(false))
+                        .on_hover_text("Choose a .wav or .ogg file")
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Audio", &["wav", "ogg"])
+                            .pick_file()
+                        {
+                            self.custom_sound_path = Some(path);
+                            self.save_settings();
+                        }
+                    }
+                });
+            }
+        }
+        if pane.row(ui, "Volume") {
+            ui.horizontal(|ui| {
+                row_label(ui, "Volume");
+                let slider = ui.add(
+                    egui::Slider::new(&mut self.sound_volume, 0.0..=1.0)
+                        .custom_formatter(|v, _| format!("{:.0}%", v * 100.0))
+                        .custom_parser(|s| s.trim_end_matches('%').trim().parse::<f64>().ok().map(|v| v / 100.0)),
+                );
+                if slider.drag_stopped() || (slider.changed() && !slider.dragged()) {
+                    self.save_settings();
+                }
+            });
+        }
+    }
+
+    fn settings_database(&mut self, ui: &mut egui::Ui, pane: &mut SettingsPane) {
+        pane.group("Map Database");
+        if pane.row(ui, "Sync database now") {
+            let version = self.db.get_db_version().ok().flatten().unwrap_or_else(|| "unknown".to_string());
+            ui.add(egui::Label::new(
+                egui::RichText::new(format!("{} maps · version {}", self.maps.len(), version))
+                    .size(12.0)
+                    .color(theme::TEXT_MUTED),
+            ).selectable(false));
+            ui.add_space(4.0);
+            let label = format!("{}  Sync database now", egui_phosphor::regular::ARROWS_CLOCKWISE);
+            let response = settings_button(ui, 160.0, theme::BTN_DEFAULT, label);
+            if response.clicked() && !self.update_in_progress {
+                self.perform_db_update(ui.ctx());
+            }
+        }
+        if pane.row(ui, "Import from file") {
+            ui.add(egui::Label::new(
+                egui::RichText::new("Replace the map list with a local manifest JSON")
+                    .size(12.0)
+                    .color(theme::TEXT_MUTED),
+            ).selectable(false));
+            ui.add_space(4.0);
+            let label = format!("{}  Import from file", egui_phosphor::regular::FILE_ARROW_UP);
+            let response = settings_button(ui, 160.0, theme::BTN_DEFAULT, label);
+            if response.clicked() && !self.update_in_progress {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Manifest", &["json"])
+                    .pick_file()
+                {
+                    self.import_manifest_file(ui.ctx(), path);
+                }
+            }
+        }
+        if pane.row(ui, "Forget removed maps") {
+            ui.horizontal(|ui| {
+                row_label(ui, "Forget removed maps");
+                let label = |months: u32| match months {
+                    0 => "Never".to_string(),
+                    m => format!("After {} months", m),
+                };
+                let before = self.purge_removed_months;
+                egui::ComboBox::from_id_salt("purge_removed_months")
+                    .selected_text(label(self.purge_removed_months))
+                    .width(130.0)
+                    .show_ui(ui, |ui| {
+                        for months in [0, 3, 6, 12] {
+                            ui.selectable_value(&mut self.purge_removed_months, months, label(months));
+                        }
+                    });
+                if self.purge_removed_months != before {
+                    self.save_settings();
+                    self.purge_removed_maps();
+                }
+            })
+            .response
+            .on_hover_text("Maps dropped from the manifest stay listed under the Removed filter until then");
+        }
+
+        pane.group("Cache");
+        self.poll_cache_sizes(ui.ctx());
+        let size_text = |size: Option<u64>| size.map_or_else(|| "…".to_string(), format_bytes);
+        for (i, kind) in CacheKind::ALL.into_iter().enumerate() {
+            if pane.row(ui, kind.label()) {
+                ui.horizontal(|ui| {
+                    row_label(ui, kind.label());
+                    ui.add(egui::Label::new(
+                        egui::RichText::new(size_text(self.cache_sizes.map(|s| s[i])))
+                            .size(12.0)
+                            .color(theme::TEXT_MUTED),
+                    ).selectable(false));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let label = format!("{}  Clear", egui_phosphor::regular::TRASH);
+                        if settings_button(ui, 80.0, theme::BTN_DANGER, label).clicked() {
+                            self.clear_cache_kind(ui.ctx(), kind);
+                        }
+                    });
+                });
+            }
+        }
+        if pane.row(ui, "Total") {
+            ui.horizontal(|ui| {
+                row_label(ui, "Total");
+                ui.add(egui::Label::new(
+                    egui::RichText::new(size_text(self.cache_sizes.map(|s| s.iter().sum())))
+                        .size(12.0)
+                        .color(theme::TEXT_MUTED),
+                ).selectable(false));
+            });
+        }
+        if pane.row(ui, "Check cache integrity") {
+            ui.horizontal(|ui| {
+                let label = format!("{}  Check cache integrity", egui_phosphor::regular::SHIELD_CHECK);
+                let response = settings_button(ui, 180.0, theme::BTN_DEFAULT, label);
+                if response.clicked() {
+                    self.check_cache_integrity(ui.ctx());
+                }
+                if self.cache_repair_manual {
+                    ui.spinner();
+                } else if let Some((repaired, failed)) = self.cache_repair_result {
+                    let files = |n: usize| if n == 1 { "1 file".to_string() } else { format!("{} files", n) };
+                    let text = match (repaired, failed) {
+                        (0, 0) => "No problems found".to_string(),
+                        (n, 0) => format!("Repaired {}", files(n)),
+                        (0, f) => format!("Removed {} that couldn't be fetched again", files(f)),
+                        (n, f) => format!("Repaired {}, {} couldn't be fetched again", files(n), f),
+                    };
+                    ui.add(egui::Label::new(
+                        egui::RichText::new(text).size(12.0).color(theme::TEXT_MUTED),
+                    ).selectable(false));
+                }
+            });
+        }
+    }
+
+    fn settings_advanced(&mut self, ui: &mut egui::Ui, pane: &mut SettingsPane) {
+        pane.group("Integrations");
+        if pane.row(ui, "Local JSON endpoint for overlays")
+            && theme::settings_checkbox(ui, self.local_server_enabled, "Local JSON endpoint for overlays", true)
+        {
+            self.local_server_enabled = !self.local_server_enabled;
+            self.save_settings();
+            self.restart_local_server();
+        }
+        if pane.row(ui, "Port") {
+            ui.horizontal(|ui| {
+                row_label(ui, "Port");
+                let response = ui.add(egui::DragValue::new(&mut self.local_server_port).range(1024..=65535));
+                if response.lost_focus() || response.drag_stopped() {
+                    self.save_settings();
+                    let bound = self.local_server.as_ref().map(|s| s.port);
+                    if self.local_server_enabled && bound != Some(self.local_server_port) {
+                        self.restart_local_server();
+                    }
+                }
+                if self.local_server.is_some() {
+                    let url = format!("http://127.0.0.1:{}/map", self.local_server_port);
+                    ui.hyperlink_to(egui::RichText::new(&url).size(12.0), &url);
+                }
+            });
+        }
+
+        pane.group("Diagnostics");
+        if pane.row(ui, "Log level") {
+            ui.horizontal(|ui| {
+                row_label(ui, "Log level");
+                let before = self.log_level.clone();
+                egui::ComboBox::from_id_salt("log_level")
+                    .selected_text(&self.log_level)
+                    .width(90.0)
+                    .show_ui(ui, |ui| {
+                        for level in settings::LOG_LEVELS {
+                            ui.selectable_value(&mut self.log_level, level.to_string(), level);
+                        }
+                    });
+                if self.log_level != before {
+                    self.save_settings();
+                }
+                ui.add(egui::Label::new(
+                    egui::RichText::new("(applies on restart)").size(11.0).color(theme::TEXT_DIM),
+                ).selectable(false));
+            });
+        }
+        if pane.row(ui, "Open Logs Folder") {
+            ui.add_space(4.0);
+            let label = format!("{}  Open Logs Folder", egui_phosphor::regular::FILE_TEXT);
+            if settings_button(ui, 140.0, theme::BTN_DEFAULT, label).clicked() {
+                let logs_dir = self.data_dir.join("logs");
+                std::fs::create_dir_all(&logs_dir).ok();
+                let _ = open::that(&logs_dir);
+            }
+        }
+    }
+
+    /// "Reset all to defaults", confirmed inline
+    fn settings_footer(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.horizontal(|ui| {
+            if self.settings_reset_confirm {
+                ui.add(egui::Label::new(
+                    egui::RichText::new("Reset every setting to its default?").color(theme::TEXT_SECONDARY),
+                ).selectable(false));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add(theme::button_danger("Reset")).clicked() {
+                        self.reset_settings_to_defaults(ctx);
+                        self.settings_reset_confirm = false;
+                    }
+                    if ui.add(theme::button("Cancel")).clicked() {
+                        self.settings_reset_confirm = false;
+                    }
+                });
+            } else if ui
+                .add(theme::button(format!(
+                    "{}  Reset all to defaults",
+                    egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE
+                )))
+                .clicked()
+            {
+                self.settings_reset_confirm = true;
+            }
+        });
+    }
+
+    /// Apply a default `Settings` to everything the settings modal controls and save.
+    /// Filters, sorting, column layout and window geometry are left alone.
+    pub fn reset_settings_to_defaults(&mut self, ctx: &egui::Context) {
+        let defaults = Settings::default();
+        info!("Resetting settings to defaults");
+
+        self.large_thumbnails = defaults.large_thumbnails;
+        self.link_view_sorts = defaults.link_view_sorts;
+        self.date_format = defaults.date_format;
+        self.set_row_density(defaults.row_density);
+        self.set_list_thumbnails(defaults.list_thumbnails);
+        self.grid_card_scale = defaults.grid_card_scale;
+        let [r, g, b] = defaults.accent_color;
+        self.accent_color = egui::Color32::from_rgb(r, g, b);
+        theme::set_accent(ctx, self.accent_color);
+        self.accent_save_pending = false;
+
+        self.auto_scroll_selection = defaults.auto_scroll_selection;
+        self.type_to_search = defaults.type_to_search;
+        self.map_of_the_day = defaults.map_of_the_day;
+        self.group_search_matches = defaults.group_search_matches;
+        self.search_case_sensitive = defaults.search_case_sensitive;
+        self.search_whole_word = defaults.search_whole_word;
+
+        // None follows the OS reduce-motion preference
+        let animate_default = !self.reduce_motion_default;
+        self.smooth_scroll = defaults.smooth_scroll.unwrap_or(animate_default);
+        self.button_press_animation = defaults.button_press_animation.unwrap_or(animate_default);
+        self.toast_fade = defaults.toast_fade.unwrap_or(animate_default);
+        theme::set_press_animation(self.button_press_animation);
+
+        self.show_category = defaults.col_category;
+        self.show_stars = defaults.col_stars;
+        self.show_points = defaults.col_points;
+        self.show_author = defaults.col_author;
+        self.show_release_date = defaults.col_release_date;
+        self.show_star_label = defaults.col_star_label;
+        self.grid_info = GridInfo::default();
+
+        self.download_path = defaults.download_path_or_default();
+        self.download_path_str = self.download_path.to_string_lossy().to_string();
+        self.minimize_to_tray = defaults.minimize_to_tray;
+        self.open_folder_after_download = defaults.open_folder_after_download;
+        self.post_download_command = defaults.post_download_command;
+        self.post_download_command_confirm = false;
+        self.confirm_downloads = defaults.confirm_downloads;
+        self.download_companion_files = defaults.download_companion_files;
+        self.local_server_enabled = defaults.local_server_enabled;
+        self.local_server_port = defaults.local_server_port;
+        self.restart_local_server();
+        self.collision_strategy = defaults.collision_strategy;
+        self.max_retries = defaults.max_retries;
+        self.precheck_urls = defaults.precheck_urls;
+        self.purge_removed_months = defaults.purge_removed_months;
+
+        self.play_sound_on_complete = defaults.play_sound;
+        self.sound_on_failure = defaults.sound_on_failure;
+        self.sound_on_db_update = defaults.sound_on_db_update;
+        self.notification_sound = defaults.notification_sound;
+        self.custom_sound_path = defaults.custom_sound_path.as_ref().map(PathBuf::from);
+        self.sound_volume = defaults.sound_volume;
+
+        self.log_level = defaults.log_level;
+
+        self.check_download_path_now();
+        self.save_settings();
+        self.push_toast("Settings reset to defaults".to_string(), ToastSeverity::Info);
+    }
+
+    /// Cheat sheet of every shortcut in `SHORTCUTS`; any key or click closes it
+    pub fn render_shortcut_sheet(&mut self, ctx: &egui::Context) {
+        if !self.show_shortcuts {
+            return;
+        }
+        let modal_response = egui::Modal::new(egui::Id::new("shortcut_sheet"))
+            .backdrop_color(egui::Color32::from_black_alpha(180))
+            .frame(theme::modal_frame())
+            .show(ctx, |ui| {
+                ui.set_width(420.0);
+                ui.add(egui::Label::new(
+                    egui::RichText::new(format!("{}  Keyboard shortcuts", egui_phosphor::regular::KEYBOARD))
+                        .size(16.0)
+                        .strong(),
+                ).selectable(false));
+                for (area, bindings) in SHORTCUTS {
+                    ui.add_space(theme::SPACING_MD);
+                    ui.add(egui::Label::new(
+                        egui::RichText::new(area.to_uppercase()).size(11.0).color(theme::TEXT_DIM),
+                    ).selectable(false));
+                    ui.add_space(theme::SPACING_SM);
+                    egui::Grid::new(("shortcut_grid", *area))
+                        .num_columns(2)
+                        .min_col_width(150.0)
+                        .spacing([theme::SPACING_MD, 4.0])
+                        .show(ui, |ui| {
+                            for (keys, action) in *bindings {
+                                ui.add(egui::Label::new(
+                                    egui::RichText::new(*keys).size(12.0).monospace().color(theme::accent()),
+                                ).selectable(false));
+                                ui.add(egui::Label::new(
+                                    egui::RichText::new(*action).size(12.0).color(theme::TEXT_SECONDARY),
+                                ).selectable(false));
+                                ui.end_row();
+                            }
+                        });
+                }
+                ui.add_space(theme::SPACING_MD);
+                ui.add(egui::Label::new(
+                    egui::RichText::new("Press any key to close").size(11.0).color(theme::TEXT_DIM),
+                ).selectable(false));
+            });
+        let clicked = ctx.input(|i| i.pointer.any_click());
+        if modal_response.should_close() || clicked {
+            self.show_shortcuts = false;
+        }
+    }
+
+    /// Paste-a-list modal: a box to paste map names into (one per line), then the
+    /// names that matched nothing. Ctrl+Shift+V skips straight to the result.
+    pub fn render_paste_list_modal(&mut self, ctx: &egui::Context) {
+        let Some(paste) = self.paste_list.as_mut() else {
+            return;
+        };
+        let mut close = false;
+        let mut submit: Option<String> = None;
+        let modal_response = egui::Modal::new(egui::Id::new("paste_list_modal"))
+            .backdrop_color(egui::Color32::from_black_alpha(180))
+            .frame(theme::modal_frame())
+            .show(ctx, |ui| {
+                ui.set_width(380.0);
+                match paste {
+                    PasteList::Input(text) => {
+                        ui.add(egui::Label::new(
+                            egui::RichText::new(format!("{}  Select from a list", egui_phosphor::regular::LIST_CHECKS))
+                                .size(16.0)
+                                .strong(),
+                        ).selectable(false));
+                        ui.add_space(theme::SPACING_SM);
+                        ui.add(egui::Label::new(
+                            egui::RichText::new("Paste map names, one per line. Case and surrounding spaces are ignored.")
+                                .color(theme::TEXT_SECONDARY),
+                        ).wrap());
+                        ui.add_space(theme::SPACING_SM);
+                        egui::ScrollArea::vertical().max_height(220.0).show(ui, |ui| {
+                            let response = ui.add(
+                                egui::TextEdit::multiline(text)
+                                    .desired_width(f32::INFINITY)
+                                    .desired_rows(8)
+                                    .hint_text("Map names…"),
+                            );
+                            if !response.has_focus() && text.is_empty() {
+                                response.request_focus();
+                            }
+                        });
+                        ui.add_space(theme::SPACING_MD);
+                        ui.horizontal(|ui| {
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.add_enabled(!text.trim().is_empty(), theme::button_accent("Select")).clicked() {
+                                    submit = Some(text.clone());
+                                }
+                                if ui.add(theme::button("Cancel")).clicked() {
+                                    close = true;
+                                }
+                            });
+                        });
+                    }
+                    PasteList::Unmatched(names) => {
+                        ui.add(egui::Label::new(
+                            egui::RichText::new(format!("{} names not found", names.len())).size(16.0).strong(),
+                        ).selectable(false));
+                        ui.add_space(theme::SPACING_SM);
+                        ui.add(egui::Label::new(
+                            egui::RichText::new("These lines match no map in the current list.")
+                                .color(theme::TEXT_SECONDARY),
+                        ));
+                        ui.add_space(theme::SPACING_SM);
+                        egui::ScrollArea::vertical()
+                            .max_height(180.0)
+                            .auto_shrink([false, true])
+                            .show(ui, |ui| {
+                                for name in names.iter() {
+                                    ui.horizontal(|ui| {
+                                        ui.colored_label(theme::STATUS_WARNING, egui_phosphor::regular::QUESTION);
+                                        ui.label(truncate_chars(name, 40));
+                                    });
+                                }
+                            });
+                        ui.add_space(theme::SPACING_MD);
+                        ui.horizontal(|ui| {
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.add(theme::button_accent("OK")).clicked() {
+                                    close = true;
+                                }
+                                if ui.add(theme::button("Copy names")).clicked() {
+                                    ui.ctx().copy_text(names.join("\n"));
+                                }
+                            });
+                        });
+                    }
+                }
+            });
+
+        if let Some(text) = submit {
+            self.select_names(&text);
+        } else if modal_response.should_close() || close {
+            self.paste_list = None;
+        }
+    }
+
+    /// Popup for `author_popup`: every map credited to that (canonical) author,
+    /// totals, and shortcuts to select or filter them. Clicking a row jumps to it.
+    pub fn render_author_modal(&mut self, ctx: &egui::Context) {
+        let Some(author) = self.author_popup.clone() else {
+            return;
+        };
+        let mut indices: Vec<usize> = (0..self.maps.len())
+            .filter(|&i| self.maps[i].authors.iter().any(|a| a.eq_ignore_ascii_case(&author)))
+            .collect();
+        indices.sort_by(|&a, &b| self.maps[b].release_date.cmp(&self.maps[a].release_date));
+        let total_points: i64 = indices.iter().map(|&i| self.maps[i].points as i64).sum();
+        let downloaded = indices
+            .iter()
+            .filter(|&&i| self.downloaded_names.contains(&self.maps[i].name))
+            .count();
+
+        let mut close = false;
+        let mut jump_to: Option<usize> = None;
+        let modal_response = egui::Modal::new(egui::Id::new("author_modal"))
+            .backdrop_color(egui::Color32::from_black_alpha(120))
+            .frame(egui::Frame::new()
+                .fill(egui::Color32::from_rgb(0x1a, 0x1a, 0x1e))
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(0x2a, 0x2a, 0x2e)))
+                .corner_radius(8.0)
+                .inner_margin(egui::Margin::same(20)))
+            .show(ctx, |ui| {
+                ui.set_width(AUTHOR_MODAL_WIDTH);
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::Label::new(
+                        egui::RichText::new(format!("{}  {}", egui_phosphor::regular::USER, author))
+                            .size(16.0)
+                            .strong(),
+                    ).selectable(false));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if modal_close_button(ui) {
+                            close = true;
+                        }
+                    });
+                });
+                ui.add(egui::Label::new(
+                    egui::RichText::new(format!(
+                        "{} map{} · {} points · {} downloaded",
+                        indices.len(),
+                        if indices.len() == 1 { "" } else { "s" },
+                        total_points,
+                        downloaded
+                    ))
+                    .size(12.0)
+                    .color(theme::TEXT_DIM),
+                ).selectable(false));
+                ui.add_space(theme::SPACING_SM);
+                ui.separator();
+
+                egui_extras::TableBuilder::new(ui)
+                    .striped(false)
+                    .resizable(false)
+                    .sense(egui::Sense::click())
+                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                    .max_scroll_height(AUTHOR_TABLE_HEIGHT)
+                    .min_scrolled_height(0.0)
+                    .column(egui_extras::Column::remainder().clip(true))
+                    .column(egui_extras::Column::exact(70.0))
+                    .column(egui_extras::Column::exact(70.0))
+                    .column(egui_extras::Column::exact(55.0))
+                    .column(egui_extras::Column::exact(20.0))
+                    .body(|body| {
+                        body.rows(24.0, indices.len(), |mut row| {
+                            let map = &self.maps[indices[row.index()]];
+                            let cell = |text: String, color: egui::Color32| {
+                                egui::Label::new(egui::RichText::new(text).size(12.0).color(color))
+                                    .truncate()
+                                    .selectable(false)
+                            };
+                            row.col(|ui| {
+                                ui.add(cell(map.name.clone(), theme::TEXT_PRIMARY));
+                            });
+                            row.col(|ui| {
+                                ui.add(cell(map.category.clone(), theme::TEXT_SECONDARY));
+                            });
+                            row.col(|ui| {
+                                ui.add(cell(render_stars(map.stars), theme::STAR_FILLED));
+                            });
+                            row.col(|ui| {
+                                ui.add(cell(map.points.to_string(), theme::TEXT_DIM));
+                            });
+                            row.col(|ui| {
+                                if self.downloaded_names.contains(&map.name) {
+                                    ui.add(cell(
+                                        egui_phosphor::regular::CHECK_CIRCLE.to_string(),
+                                        theme::STATUS_SUCCESS,
+                                    ))
+                                    .on_hover_text("Downloaded");
+                                }
+                            });
+                            let response = row.response();
+                            if response.hovered() {
+                                response.ctx.set_cursor_icon(egui::CursorIcon::PointingHand);
+                            }
+                            if response.clicked() {
+                                jump_to = Some(indices[row.index()]);
+                            }
+                        });
+                    });
+
+                ui.add_space(theme::SPACING_SM);
+                ui.separator();
+                ui.add_space(theme::SPACING_SM);
+                ui.horizontal(|ui| {
+                    let label = format!("{}  Select all", egui_phosphor::regular::CHECK_SQUARE);
+                    if settings_button(ui, 120.0, theme::BTN_DEFAULT, label).clicked() {
+                        self.remember_selection();
+                        self.selected_indices.extend(indices.iter().copied());
+                        close = true;
+                    }
+                    let label = format!("{}  Filter to author", egui_phosphor::regular::FUNNEL);
+                    if settings_button(ui, 140.0, theme::BTN_DEFAULT, label).clicked() {
+                        self.search_query = super::filters::author_query(&author);
+                        self.apply_filters();
+                        close = true;
+                    }
+                });
+            });
+
+        if let Some(map_idx) = jump_to {
+            self.jump_to_map(map_idx);
+            close = true;
+        }
+        if close || modal_response.should_close() {
+            self.author_popup = None;
+        }
+    }
+}
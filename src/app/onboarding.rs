@@ -0,0 +1,224 @@
+//! First-run setup: download folder, maps already present, default filters and
+//! thumbnails. Shown when no settings file exists; every page can be skipped and
+//! Settings > Downloads runs it again.
+
+use super::App;
+use crate::theme;
+use crate::types::OnboardingStep;
+use crate::utils;
+use eframe::egui;
+use std::path::PathBuf;
+
+impl App {
+    /// Open the setup on its first page
+    pub fn start_onboarding(&mut self) {
+        self.show_settings = false;
+        self.onboarding = Some(OnboardingStep::Folder);
+        self.onboarding_scanned = None;
+        self.onboarding_scan_pending = false;
+        self.onboarding_categories = if self.category_mode_range {
+            std::array::from_fn(|i| (self.category_range.0..=self.category_range.1).contains(&(i as u8)))
+        } else {
+            self.filter_categories
+        };
+        self.onboarding_hide_downloaded = self.filter_downloaded == 2;
+    }
+
+    fn next_onboarding_step(&mut self, step: OnboardingStep) {
+        self.onboarding = step.next();
+        if self.onboarding.is_none() {
+            self.save_settings();
+        }
+    }
+
+    fn onboarding_use_folder(&mut self, path: PathBuf) {
+        self.download_path_str = path.to_string_lossy().to_string();
+        self.download_path = path;
+        self.check_download_path_now();
+        self.save_settings();
+    }
+
+    pub fn render_onboarding(&mut self, ctx: &egui::Context) {
+        let Some(step) = self.onboarding else {
+            return;
+        };
+        let mut advance = false;
+        let mut close = false;
+
+        let modal_response = egui::Modal::new(egui::Id::new("onboarding_modal"))
+            .backdrop_color(egui::Color32::from_black_alpha(180))
+            .frame(theme::modal_frame())
+            .show(ctx, |ui| {
+                ui.set_width(440.0);
+                let pos = OnboardingStep::ALL.iter().position(|&s| s == step).unwrap_or(0);
+                ui.add(egui::Label::new(
+                    egui::RichText::new(format!("SETUP · {} OF {}", pos + 1, OnboardingStep::ALL.len()))
+                        .size(11.0)
+                        .color(theme::TEXT_DIM),
+                ).selectable(false));
+                ui.add(egui::Label::new(egui::RichText::new(step.title()).size(16.0).strong()).selectable(false));
+                ui.add_space(theme::SPACING_SM);
+
+                match step {
+                    OnboardingStep::Folder => advance = self.onboarding_folder_page(ui),
+                    OnboardingStep::Scan => advance = self.onboarding_scan_page(ui),
+                    OnboardingStep::Filters => advance = self.onboarding_filters_page(ui),
+                    OnboardingStep::Thumbnails => advance = self.onboarding_thumbnails_page(ui),
+                }
+
+                ui.add_space(theme::SPACING_MD);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add(egui::Button::new(egui::RichText::new("Skip setup").color(theme::TEXT_DIM)).frame(false))
+                        .clicked()
+                    {
+                        close = true;
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let label = if step == OnboardingStep::Thumbnails { "Not now" } else { "Skip" };
+                        if ui.add(theme::button(label)).clicked() {
+                            advance = true;
+                        }
+                    });
+                });
+            });
+
+        if modal_response.should_close() || close {
+            self.onboarding = None;
+            self.save_settings();
+        } else if advance {
+            self.next_onboarding_step(step);
+        }
+    }
+
+    /// Detected DDNet folders, a browse button, or keep the current path
+    fn onboarding_folder_page(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut chosen = None;
+        ui.add(egui::Label::new(
+            egui::RichText::new(
+                "Maps are saved straight into a folder. Using your DDNet maps folder makes \
+                 them available in the game right away.",
+            )
+            .color(theme::TEXT_SECONDARY),
+        ).wrap());
+        ui.add_space(theme::SPACING_SM);
+        ui.add(egui::Label::new(
+            egui::RichText::new(format!("Currently: {}", self.download_path.display())).size(12.0).color(theme::TEXT_MUTED),
+        ).wrap());
+        ui.add_space(theme::SPACING_SM);
+
+        let detected = utils::ddnet_maps_dirs();
+        if detected.is_empty() {
+            ui.add(egui::Label::new(
+                egui::RichText::new("No DDNet maps folder was found on this computer.").size(12.0).color(theme::TEXT_DIM),
+            ));
+        }
+        for dir in detected {
+            let label = format!("{}  Use {}", egui_phosphor::regular::FOLDER, dir.display());
+            if ui.add(theme::button_accent(label)).clicked() {
+                chosen = Some(dir);
+            }
+        }
+        let browse = format!("{}  Choose another folder…", egui_phosphor::regular::FOLDER_OPEN);
+        if ui.add(theme::button(browse)).clicked() {
+            chosen = rfd::FileDialog::new().set_directory(&self.download_path).pick_folder();
+        }
+
+        match chosen {
+            Some(path) => {
+                self.onboarding_use_folder(path);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rescan the folder so maps already in it count as downloaded
+    fn onboarding_scan_page(&mut self, ui: &mut egui::Ui) -> bool {
+        ui.add(egui::Label::new(
+            egui::RichText::new(format!(
+                "Check {} for maps you already have. They're marked as downloaded and can be hidden from the list.",
+                self.download_path.display()
+            ))
+            .color(theme::TEXT_SECONDARY),
+        ).wrap());
+        ui.add_space(theme::SPACING_SM);
+        match self.onboarding_scanned {
+            // `poll_downloaded_scan` fills in the count once the folder is listed
+            None if self.onboarding_scan_pending => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(egui::RichText::new("Scanning…").color(theme::TEXT_SECONDARY));
+                });
+                false
+            }
+            None => {
+                let label = format!("{}  Scan folder", egui_phosphor::regular::MAGNIFYING_GLASS);
+                if ui.add(theme::button_accent(label)).clicked() {
+                    self.check_download_path_now();
+                    self.onboarding_scan_pending = true;
+                }
+                false
+            }
+            Some(found) => {
+                ui.add(egui::Label::new(
+                    egui::RichText::new(format!("Found {} of {} maps.", found, self.maps.len()))
+                        .color(theme::STATUS_SUCCESS),
+                ));
+                ui.add_space(theme::SPACING_SM);
+                ui.add(theme::button_accent("Next")).clicked()
+            }
+        }
+    }
+
+    /// Categories to show and whether to hide maps already downloaded
+    fn onboarding_filters_page(&mut self, ui: &mut egui::Ui) -> bool {
+        ui.add(egui::Label::new(
+            egui::RichText::new("Pick the categories to list. Filters can be changed any time in the sidebar.")
+                .color(theme::TEXT_SECONDARY),
+        ).wrap());
+        ui.add_space(theme::SPACING_SM);
+        ui.columns(4, |columns| {
+            for (i, name) in Self::CATEGORY_NAMES.iter().enumerate() {
+                if theme::settings_checkbox(&mut columns[i % 4], self.onboarding_categories[i], name, true) {
+                    self.onboarding_categories[i] = !self.onboarding_categories[i];
+                }
+            }
+        });
+        ui.add_space(theme::SPACING_SM);
+        if theme::settings_checkbox(ui, self.onboarding_hide_downloaded, "Hide maps I already have", true) {
+            self.onboarding_hide_downloaded = !self.onboarding_hide_downloaded;
+        }
+        ui.add_space(theme::SPACING_SM);
+        let any = self.onboarding_categories.contains(&true);
+        if ui.add_enabled(any, theme::button_accent("Apply filters")).clicked() {
+            self.category_mode_range = false;
+            self.filter_categories = self.onboarding_categories;
+            self.filter_downloaded = if self.onboarding_hide_downloaded { 2 } else { 0 };
+            self.apply_filters();
+            return true;
+        }
+        false
+    }
+
+    /// Explain the prefetch before it starts; it's held back while setup runs
+    fn onboarding_thumbnails_page(&mut self, ui: &mut egui::Ui) -> bool {
+        ui.add(egui::Label::new(
+            egui::RichText::new(format!(
+                "The grid view shows a preview image for every map. Fetching them downloads {} small \
+                 images in the background, once; they're cached for later starts. Without them, \
+                 cards show a placeholder until the next start.",
+                self.maps.len()
+            ))
+            .color(theme::TEXT_SECONDARY),
+        ).wrap());
+        ui.add_space(theme::SPACING_SM);
+        let label = format!("{}  Download thumbnails", egui_phosphor::regular::DOWNLOAD_SIMPLE);
+        if ui.add(theme::button_accent(label)).clicked() {
+            let ctx = ui.ctx().clone();
+            self.start_thumbnail_prefetch(&ctx);
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,126 @@
+//! Detection of downloaded maps that differ from the manifest (re-released fixes)
+
+use super::App;
+use crate::db::Database;
+use crate::types::{CollisionStrategy, ToastSeverity};
+use eframe::egui;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use tracing::{debug, info, warn};
+
+/// Memory key for the names found outdated by the last check
+const OUTDATED_KEY: &str = "outdated_maps";
+
+/// Hex SHA-256 of a file
+fn file_sha256(path: &Path) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+impl App {
+    /// Pick up a finished outdated check and start another if the download folder or
+    /// the database changed since. Hashes are cached per file size and mtime, so only
+    /// new or rewritten files are read.
+    pub fn poll_outdated_check(&mut self, ctx: &egui::Context) {
+        if let Some(outdated) = ctx.memory(|mem| mem.data.get_temp::<Vec<String>>(OUTDATED_KEY.into())) {
+            ctx.memory_mut(|mem| mem.data.remove::<Vec<String>>(OUTDATED_KEY.into()));
+            self.outdated_check_running = false;
+            if !outdated.is_empty() {
+                info!(count = outdated.len(), "Downloaded maps differ from the manifest");
+            }
+            self.outdated_names = outdated.into_iter().collect();
+            if self.filter_downloaded == 3 {
+                self.apply_filters();
+            }
+        }
+        if !self.outdated_check_due || self.outdated_check_running {
+            return;
+        }
+        self.outdated_check_due = false;
+
+        // Only maps the manifest still lists a hash for can be checked
+        let expected: Vec<(String, String, i64)> = self
+            .maps
+            .iter()
+            .filter(|m| m.removed_at.is_none() && self.downloaded_names.contains(&m.name))
+            .filter_map(|m| Some((m.name.clone(), m.sha256.clone()?, m.size)))
+            .collect();
+        if expected.is_empty() {
+            self.outdated_names.clear();
+            return;
+        }
+
+        self.outdated_check_running = true;
+        let download_path = self.download_path.clone();
+        let db_path = self.data_dir.join("maps.db");
+        let ctx = ctx.clone();
+        debug!(count = expected.len(), "Checking downloaded maps against the manifest");
+
+        std::thread::spawn(move || {
+            let db = Database::open(&db_path).ok();
+            let outdated: Vec<String> = expected
+                .into_iter()
+                .filter(|(name, sha256, size)| {
+                    let path = download_path.join(format!("{}.map", name));
+                    let Ok(meta) = std::fs::metadata(&path) else { return false };
+                    // A different size settles it without reading the file
+                    if *size > 0 && meta.len() != *size as u64 {
+                        return true;
+                    }
+                    let modified = meta
+                        .modified()
+                        .ok()
+                        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                        .map_or(0, |d| d.as_secs() as i64);
+                    let cached = db
+                        .as_ref()
+                        .and_then(|db| db.get_file_hash(name, meta.len(), modified).ok().flatten());
+                    let actual = match cached {
+                        Some(hash) => hash,
+                        None => match file_sha256(&path) {
+                            Ok(hash) => {
+                                if let Some(db) = &db {
+                                    db.set_file_hash(name, meta.len(), modified, &hash).ok();
+                                }
+                                hash
+                            }
+                            Err(e) => {
+                                warn!(map = %name, error = %e, "Failed to hash map file");
+                                return false;
+                            }
+                        },
+                    };
+                    actual != *sha256
+                })
+                .map(|(name, ..)| name)
+                .collect();
+
+            ctx.memory_mut(|mem| mem.data.insert_temp(OUTDATED_KEY.into(), outdated));
+            ctx.request_repaint();
+        });
+    }
+
+    /// Download the outdated maps again, replacing the local copies
+    pub fn update_outdated_maps(&mut self, ctx: &egui::Context) {
+        if self.precheck_order.is_some() || self.download_confirm.is_some() {
+            return;
+        }
+        let outdated: Vec<usize> = self
+            .maps
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| self.outdated_names.contains(&m.name))
+            .map(|(idx, _)| idx)
+            .collect();
+        if outdated.is_empty() {
+            return;
+        }
+        if !self.download_path_ok {
+            self.push_toast("Download folder is unreachable".to_string(), ToastSeverity::Error);
+            return;
+        }
+        self.batch_collision = Some(CollisionStrategy::Overwrite);
+        self.begin_download(ctx, outdated);
+    }
+}
@@ -0,0 +1,123 @@
+//! Selection undo history (Ctrl+Z). Session-only, stored as map names so entries
+//! survive a database reload that reorders `maps`.
+
+use super::App;
+use crate::constants::SELECTION_UNDO_DEPTH;
+use crate::types::{PasteList, SelectScope, ToastAction, ToastSeverity};
+use std::collections::{HashMap, HashSet};
+
+impl App {
+    /// Add the filtered maps in `scope` to the selection
+    pub fn select_filtered(&mut self, scope: SelectScope) {
+        self.remember_selection();
+        for &idx in &self.filtered_indices {
+            let downloaded = self.downloaded_names.contains(&self.maps[idx].name);
+            let wanted = match scope {
+                SelectScope::All => true,
+                SelectScope::NotDownloaded => !downloaded,
+                SelectScope::Downloaded => downloaded,
+            };
+            if wanted {
+                self.selected_indices.insert(idx);
+            }
+        }
+    }
+
+    /// Select every map named on a line of `text` (trimmed, case-insensitive), e.g. a
+    /// list pasted from chat. Names that match nothing are kept for the paste modal.
+    pub fn select_names(&mut self, text: &str) {
+        let by_name: HashMap<String, usize> = self
+            .maps
+            .iter()
+            .enumerate()
+            .map(|(idx, m)| (m.name.to_lowercase(), idx))
+            .collect();
+        let mut matched = HashSet::new();
+        let mut unmatched: Vec<String> = Vec::new();
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            match by_name.get(&line.to_lowercase()) {
+                Some(&idx) => {
+                    matched.insert(idx);
+                }
+                None if !unmatched.iter().any(|u| u.eq_ignore_ascii_case(line)) => {
+                    unmatched.push(line.to_string());
+                }
+                None => {}
+            }
+        }
+        if matched.is_empty() && unmatched.is_empty() {
+            self.push_toast("No map names to select", ToastSeverity::Info);
+            return;
+        }
+
+        self.remember_selection();
+        self.selected_indices.extend(&matched);
+        let noun = if matched.len() == 1 { "map" } else { "maps" };
+        if unmatched.is_empty() {
+            self.push_toast_with_button(
+                format!("Selected {} {}", matched.len(), noun),
+                ToastSeverity::Success,
+                ToastAction::UndoSelection,
+            );
+            self.paste_list = None;
+        } else {
+            self.push_toast_with_button(
+                format!("Selected {} {} · {} not found", matched.len(), noun, unmatched.len()),
+                ToastSeverity::Warning,
+                ToastAction::UndoSelection,
+            );
+            self.paste_list = Some(PasteList::Unmatched(unmatched));
+        }
+    }
+
+    /// Save the current selection before a bulk change (clear, select all, ...)
+    pub fn remember_selection(&mut self) {
+        self.selection_toggling = false;
+        self.push_selection_history();
+    }
+
+    /// Save the current selection before a single-item toggle. A run of toggles
+    /// shares one entry, so clicking through maps doesn't flood the history.
+    pub fn remember_selection_toggle(&mut self) {
+        if !self.selection_toggling {
+            self.selection_toggling = true;
+            self.push_selection_history();
+        }
+    }
+
+    fn push_selection_history(&mut self) {
+        let mut names: Vec<String> = self
+            .selected_indices
+            .iter()
+            .filter_map(|&idx| self.maps.get(idx).map(|m| m.name.clone()))
+            .collect();
+        names.sort_unstable();
+        if self.selection_history.last() == Some(&names) {
+            return;
+        }
+        self.selection_history.push(names);
+        if self.selection_history.len() > SELECTION_UNDO_DEPTH {
+            self.selection_history.remove(0);
+        }
+    }
+
+    /// Restore the selection from before the last change
+    pub fn undo_selection(&mut self) {
+        self.selection_toggling = false;
+        let Some(names) = self.selection_history.pop() else {
+            self.push_toast("Nothing to undo", ToastSeverity::Info);
+            return;
+        };
+        self.selected_indices = self
+            .maps
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| names.binary_search(&m.name).is_ok())
+            .map(|(idx, _)| idx)
+            .collect();
+        self.last_selected = None;
+        let count = self.selected_indices.len();
+        let noun = if count == 1 { "map" } else { "maps" };
+        self.push_toast(format!("Selection restored ({} {})", count, noun), ToastSeverity::Info);
+    }
+}
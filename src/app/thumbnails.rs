@@ -2,9 +2,94 @@
 
 use super::App;
 use crate::constants::*;
+use crate::types::{CacheKind, PrefetchProgress, ThumbnailLoad, ToastSeverity};
+use crate::utils;
 use eframe::egui;
 use futures::StreamExt;
-use tracing::{debug, warn};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use tracing::{debug, info, warn};
+
+/// Memory key for a finished cache integrity check: the map names whose files were
+/// corrupt, and how many files were fetched again and how many couldn't be
+const CACHE_REPAIR_KEY: &str = "cache_repaired";
+
+/// Memory key for the measured cache sizes, in `CacheKind::ALL` order
+const CACHE_SIZES_KEY: &str = "cache_sizes";
+
+/// Memory key for the number of thumbnails a finished prefetch couldn't fetch
+const PREFETCH_FAILED_KEY: &str = "prefetch_failed";
+
+/// How often paused prefetch workers check whether they may continue
+const PREFETCH_PAUSE_POLL: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// How long a cached 404 holds before the image is asked for again, in case it was
+/// uploaded since
+const MISSING_MARKER_TTL: std::time::Duration = std::time::Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Empty file cached next to where an image would be when the server returned 404,
+/// so it isn't requested again. Clearing the cache removes it with the images.
+fn missing_marker(image_path: &Path) -> PathBuf {
+    image_path.with_extension("missing")
+}
+
+/// Whether a 404 for `image_path` was cached within `MISSING_MARKER_TTL`, going by the
+/// marker's modification time. An expired marker is removed.
+fn cached_miss(image_path: &Path) -> bool {
+    let marker = missing_marker(image_path);
+    let Ok(modified) = std::fs::metadata(&marker).and_then(|m| m.modified()) else {
+        return false;
+    };
+    if modified.elapsed().is_ok_and(|age| age >= MISSING_MARKER_TTL) {
+        std::fs::remove_file(&marker).ok();
+        return false;
+    }
+    true
+}
+
+/// Write fetched image bytes next to `base` with the extension of their actual format,
+/// told by the magic bytes, and drop a copy cached in another format. Anything but
+/// PNG, WebP or JPEG (an error page served as 200) isn't saved.
+fn save_image(base: &Path, bytes: &[u8]) -> bool {
+    let ext = match image::guess_format(bytes) {
+        Ok(image::ImageFormat::Png) => "png",
+        Ok(image::ImageFormat::WebP) => "webp",
+        Ok(image::ImageFormat::Jpeg) => "jpg",
+        format => {
+            warn!(path = %base.display(), ?format, "Fetched image isn't PNG, WebP or JPEG");
+            return false;
+        }
+    };
+    if utils::write_atomic(&base.with_extension(ext), bytes).is_err() {
+        return false;
+    }
+    for other in IMAGE_EXTENSIONS.iter().filter(|&&e| e != ext) {
+        std::fs::remove_file(base.with_extension(other)).ok();
+    }
+    true
+}
+
+/// Save a fetched image, or remember that the server doesn't have it.
+/// Returns false when it's worth asking again (server error, cut-off body).
+async fn store_fetched_image(response: reqwest::Response, path: &Path) -> bool {
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        debug!(path = %path.display(), "Image not on server, caching the miss");
+        std::fs::write(missing_marker(path), []).ok();
+        true
+    } else if response.status().is_success() {
+        match response.bytes().await {
+            Ok(bytes) => save_image(path, &bytes),
+            Err(_) => false,
+        }
+    } else {
+        false
+    }
+}
+
+/// GET an image, preferring the smaller formats
+async fn fetch_image(client: &reqwest::Client, url: &str) -> reqwest::Result<reqwest::Response> {
+    client.get(url).header(reqwest::header::ACCEPT, IMAGE_ACCEPT).send().await
+}
 
 impl App {
     pub fn start_thumbnail_prefetch(&mut self, ctx: &egui::Context) {
@@ -13,6 +98,10 @@ impl App {
         let map_names: Vec<String> = self.maps.iter().map(|m| m.name.clone()).collect();
 
         debug!(count = map_names.len(), "Starting thumbnail prefetch");
+        let running = self.prefetch_running.clone();
+        running.fetch_add(1, Ordering::Relaxed);
+        let progress = self.prefetch_progress.clone();
+        *progress.lock().unwrap() = PrefetchProgress::default();
 
         self.runtime.spawn(async move {
             let client = reqwest::Client::new();
@@ -25,25 +114,43 @@ impl App {
 
             for name in map_names {
                 let thumb_path = thumb_dir.join(format!("{}.png", name));
-                if thumb_path.exists() {
+                if utils::cached_image(&thumb_path).is_some() || cached_miss(&thumb_path) {
                     continue;
                 }
 
                 let sem = semaphore.clone();
                 let client = client.clone();
                 let ctx = ctx_clone.clone();
+                let progress = progress.clone();
                 let url = format!("{}/thumbnails/{}.png", PREVIEWS_BASE_URL, name);
+                progress.lock().unwrap().queued += 1;
 
                 let handle = tokio::spawn(async move {
                     let _permit = sem.acquire().await.ok();
-                    if let Ok(response) = client.get(&url).send().await {
-                        if response.status().is_success() {
-                            if let Ok(bytes) = response.bytes().await {
-                                std::fs::write(&thumb_path, &bytes).ok();
-                                ctx.request_repaint();
+                    let mut stored = false;
+                    for attempt in 0..=PREFETCH_RETRIES {
+                        while progress.lock().unwrap().paused {
+                            tokio::time::sleep(PREFETCH_PAUSE_POLL).await;
+                        }
+                        if attempt > 0 {
+                            tokio::time::sleep(std::time::Duration::from_secs(attempt as u64)).await;
+                        }
+                        if let Ok(response) = fetch_image(&client, &url).await {
+                            if store_fetched_image(response, &thumb_path).await {
+                                stored = true;
+                                break;
                             }
                         }
                     }
+                    {
+                        let mut p = progress.lock().unwrap();
+                        if stored {
+                            p.fetched += 1;
+                        } else {
+                            p.failed += 1;
+                        }
+                    }
+                    ctx.request_repaint();
                 });
                 handles.push(handle);
             }
@@ -51,6 +158,13 @@ impl App {
             for handle in handles {
                 handle.await.ok();
             }
+            running.fetch_sub(1, Ordering::Relaxed);
+            let failed = progress.lock().unwrap().failed;
+            if failed > 0 {
+                warn!(failed, "Thumbnail prefetch finished with failures");
+                ctx_clone.memory_mut(|mem| mem.data.insert_temp(PREFETCH_FAILED_KEY.into(), failed));
+            }
+            ctx_clone.request_repaint();
         });
     }
 
@@ -59,43 +173,269 @@ impl App {
         ctx: &egui::Context,
         map_name: &str,
     ) -> Option<egui::TextureHandle> {
+        let pass = ctx.cumulative_pass_nr();
+        if self.thumbnail_missing.contains(map_name) {
+            return None;
+        }
         if let Some(cached) = self.thumbnail_cache.get(map_name) {
+            self.thumbnail_last_used.insert(map_name.to_string(), pass);
             return cached.clone();
         }
+        if let Some((_, wanted)) = self.thumbnail_pending.get_mut(map_name) {
+            *wanted = pass;
+            return None;
+        }
 
         let thumb_path = self
             .cache_dir
             .join("thumbnails")
             .join(format!("{}.png", map_name));
 
-        if thumb_path.exists() {
-            let texture = image::open(&thumb_path).ok().map(|img| {
+        if let Some(cached) = utils::cached_image(&thumb_path) {
+            // Decoded off the UI thread; upload_thumbnails creates the texture
+            let decoding = self.thumbnail_pending.values().filter(|(l, _)| matches!(l, ThumbnailLoad::Decoding)).count();
+            if decoding < THUMBNAIL_DECODES_IN_FLIGHT {
+                self.thumbnail_pending.insert(map_name.to_string(), (ThumbnailLoad::Decoding, pass));
+                let tx = self.thumbnail_decoded_tx.clone();
+                let generation = self.thumbnail_generation;
+                let name = map_name.to_string();
+                let ctx = ctx.clone();
+                self.runtime.spawn_blocking(move || {
+                    let image = image::open(&cached).ok().map(|img| {
+                        let rgba = img.to_rgba8();
+                        let size = [rgba.width() as usize, rgba.height() as usize];
+                        egui::ColorImage::from_rgba_unmultiplied(size, &rgba.into_raw())
+                    });
+                    tx.send((generation, name, image)).ok();
+                    ctx.request_repaint();
+                });
+            }
+            return None;
+        }
+        if cached_miss(&thumb_path) {
+            self.thumbnail_missing.insert(map_name.to_string());
+        }
+
+        None
+    }
+
+    /// Whether a thumbnail is being decoded or waits for upload; the card shows as loading
+    pub fn thumbnail_pending(&self, map_name: &str) -> bool {
+        self.thumbnail_pending.contains_key(map_name)
+    }
+
+    /// Turn decoded thumbnails into textures, at most `THUMBNAIL_UPLOADS_PER_FRAME` a
+    /// frame. Thumbnails drawn most recently (on screen) go first; ones not drawn for
+    /// `THUMBNAIL_STALE_PASSES` scrolled away and are dropped, to be decoded again if
+    /// they come back.
+    pub fn upload_thumbnails(&mut self, ctx: &egui::Context) {
+        while let Ok((generation, name, image)) = self.thumbnail_decoded_rx.try_recv() {
+            if generation != self.thumbnail_generation {
+                continue;
+            }
+            let Some((load, _)) = self.thumbnail_pending.get_mut(&name) else { continue };
+            *load = match image {
+                Some(image) => ThumbnailLoad::Decoded(image),
+                None => ThumbnailLoad::Failed,
+            };
+        }
+
+        let pass = ctx.cumulative_pass_nr();
+        self.thumbnail_pending.retain(|_, (load, wanted)| {
+            matches!(load, ThumbnailLoad::Decoding) || *wanted + THUMBNAIL_STALE_PASSES >= pass
+        });
+        let mut ready: Vec<(u64, String)> = self
+            .thumbnail_pending
+            .iter()
+            .filter(|(_, (load, _))| !matches!(load, ThumbnailLoad::Decoding))
+            .map(|(name, (_, wanted))| (*wanted, name.clone()))
+            .collect();
+        if ready.is_empty() {
+            return;
+        }
+        ready.sort_unstable_by_key(|&(wanted, _)| std::cmp::Reverse(wanted));
+
+        for (_, name) in ready.into_iter().take(THUMBNAIL_UPLOADS_PER_FRAME) {
+            let Some((load, _)) = self.thumbnail_pending.remove(&name) else { continue };
+            let texture = match load {
+                ThumbnailLoad::Decoded(image) => Some(ctx.load_texture(&name, image, egui::TextureOptions::LINEAR)),
+                _ => {
+                    self.cache_suspects.insert(name.clone());
+                    None
+                }
+            };
+            self.thumbnail_cache.insert(name.clone(), texture);
+            self.thumbnail_last_used.insert(name, pass);
+        }
+        if self.thumbnail_cache.len() > THUMBNAIL_TEXTURE_CAP {
+            self.evict_thumbnails(pass);
+        }
+        // More may be waiting for next frame's budget
+        ctx.request_repaint();
+    }
+
+    /// Where the thumbnail of `map_name` is cached, like `full_preview_path`
+    fn thumbnail_path(&self, map_name: &str) -> PathBuf {
+        let base = self.cache_dir.join("thumbnails").join(format!("{}.png", map_name));
+        utils::cached_image(&base).unwrap_or(base)
+    }
+
+    /// While true, cards without a thumbnail may still get one and show as loading
+    pub fn thumbnail_prefetch_running(&self) -> bool {
+        self.prefetch_running.load(Ordering::Relaxed) > 0
+    }
+
+    /// Stop or resume fetching thumbnails (requests in flight finish)
+    pub fn toggle_prefetch_paused(&mut self) {
+        let mut progress = self.prefetch_progress.lock().unwrap();
+        progress.paused = !progress.paused;
+        info!(paused = progress.paused, "Toggled thumbnail prefetch pause");
+    }
+
+    /// One summarized toast for the thumbnails a finished prefetch gave up on
+    pub fn poll_thumbnail_prefetch(&mut self, ctx: &egui::Context) {
+        let Some(failed) = ctx.memory(|mem| mem.data.get_temp::<usize>(PREFETCH_FAILED_KEY.into())) else {
+            return;
+        };
+        ctx.memory_mut(|mem| mem.data.remove::<usize>(PREFETCH_FAILED_KEY.into()));
+        let noun = if failed == 1 { "thumbnail" } else { "thumbnails" };
+        self.push_toast(
+            format!("Couldn't fetch {} {}; they're retried next start", failed, noun),
+            ToastSeverity::Warning,
+        );
+    }
+
+    /// Whether the server had no full preview for `map_name` last time it was asked
+    pub fn full_preview_missing(&self, map_name: &str) -> bool {
+        cached_miss(&self.full_preview_path(map_name))
+    }
+
+    /// Drop least recently used thumbnail textures (they reload from disk on demand).
+    /// Evicts down to 90% of the cap so this doesn't run every frame while scrolling.
+    fn evict_thumbnails(&mut self, current_pass: u64) {
+        let target = THUMBNAIL_TEXTURE_CAP * 9 / 10;
+        let mut by_age: Vec<(u64, String)> = self
+            .thumbnail_cache
+            .keys()
+            .map(|name| (self.thumbnail_last_used.get(name).copied().unwrap_or(0), name.clone()))
+            .filter(|(used, _)| *used < current_pass) // never evict what's on screen
+            .collect();
+        by_age.sort_unstable();
+
+        let excess = self.thumbnail_cache.len().saturating_sub(target);
+        for (_, name) in by_age.into_iter().take(excess) {
+            self.thumbnail_cache.remove(&name);
+            self.thumbnail_last_used.remove(&name);
+        }
+        debug!(
+            resident = self.thumbnail_cache.len(),
+            "Evicted least recently used thumbnails"
+        );
+    }
+
+    /// Where the full-size preview of `map_name` is cached: the file in whatever
+    /// format it was saved as, or the `.png` path when it isn't cached yet
+    pub fn full_preview_path(&self, map_name: &str) -> PathBuf {
+        let base = self.cache_dir.join("full").join(format!("{}.png", map_name));
+        utils::cached_image(&base).unwrap_or(base)
+    }
+
+    /// Where the higher-resolution preview of `map_name` is cached, next to the full one
+    fn hires_preview_path(&self, map_name: &str) -> PathBuf {
+        let base = self.cache_dir.join("full").join(format!("{}{}.png", map_name, PREVIEW_HIRES_SUFFIX));
+        utils::cached_image(&base).unwrap_or(base)
+    }
+
+    /// Higher-resolution texture for a preview zoomed past its native size. The first
+    /// call fetches it in the background; until it arrives, or when the server has
+    /// none, this returns None and the full preview stays on screen.
+    pub fn hires_preview(&mut self, ctx: &egui::Context, map_name: &str) -> Option<egui::TextureHandle> {
+        if let Some(tex) = self.preview_hires.get(map_name) {
+            return tex.clone();
+        }
+        let path = self.hires_preview_path(map_name);
+        if path.exists() {
+            let tex = image::open(&path).ok().map(|img| {
                 let rgba = img.to_rgba8();
                 let size = [rgba.width() as usize, rgba.height() as usize];
                 let pixels = rgba.into_raw();
                 ctx.load_texture(
-                    map_name,
+                    format!("{}_hires", map_name),
                     egui::ColorImage::from_rgba_unmultiplied(size, &pixels),
                     egui::TextureOptions::LINEAR,
                 )
             });
-            self.thumbnail_cache
-                .insert(map_name.to_string(), texture.clone());
-            return texture;
+            if tex.is_none() {
+                // Checked like any full preview; the file stem keeps the suffix
+                self.cache_suspects.insert(format!("{}{}", map_name, PREVIEW_HIRES_SUFFIX));
+            }
+            self.preview_hires_loading.remove(map_name);
+            self.preview_hires.insert(map_name.to_string(), tex.clone());
+            return tex;
+        }
+        if cached_miss(&path) {
+            self.preview_hires_loading.remove(map_name);
+            self.preview_hires.insert(map_name.to_string(), None);
+            return None;
+        }
+        if self.preview_hires_loading.insert(map_name.to_string()) {
+            let url = format!("{}/full/{}{}.png", PREVIEWS_BASE_URL, map_name, PREVIEW_HIRES_SUFFIX);
+            let ctx = ctx.clone();
+            self.runtime.spawn(async move {
+                if let Ok(response) = fetch_image(&reqwest::Client::new(), &url).await {
+                    std::fs::create_dir_all(path.parent().unwrap()).ok();
+                    store_fetched_image(response, &path).await;
+                }
+                ctx.request_repaint();
+            });
         }
-
         None
     }
 
+    /// Zoom that shows the sharpest loaded render of `map_name` at 1:1. Zoom is
+    /// relative to the full preview, so a loaded @2x render is native at 2.0.
+    pub fn native_preview_zoom(&self, map_name: &str) -> f32 {
+        let full = self.preview_textures.get(map_name).and_then(|t| t.as_ref());
+        let hires = self.preview_hires.get(map_name).and_then(|t| t.as_ref());
+        match (full, hires) {
+            (Some(full), Some(hires)) if full.size()[0] > 0 => hires.size()[0] as f32 / full.size()[0] as f32,
+            _ => 1.0,
+        }
+    }
+
+    /// Drop a full preview's texture and fetch it again
+    pub fn reload_full_preview(&mut self, ctx: &egui::Context, map_name: &str) {
+        std::fs::remove_file(missing_marker(&self.full_preview_path(map_name))).ok();
+        std::fs::remove_file(missing_marker(&self.hires_preview_path(map_name))).ok();
+        self.preview_textures.remove(map_name);
+        self.preview_loading.remove(map_name);
+        self.preview_hires.remove(map_name);
+        self.preview_hires_loading.remove(map_name);
+        self.load_full_preview(ctx, map_name);
+    }
+
+    /// Open the cached full preview in the default image viewer, or select it in the
+    /// file manager. If the file is gone (cache cleared mid-session) it's fetched again.
+    pub fn open_full_preview_file(&mut self, ctx: &egui::Context, map_name: &str, reveal: bool) {
+        let path = self.full_preview_path(map_name);
+        if !path.exists() {
+            self.push_toast("Preview image is no longer cached, downloading it again", ToastSeverity::Warning);
+            self.reload_full_preview(ctx, map_name);
+            return;
+        }
+        let result = if reveal { utils::show_in_folder(&path) } else { open::that(&path) };
+        if let Err(e) = result {
+            warn!(path = %path.display(), error = %e, "Failed to open preview image");
+            self.push_toast(format!("Couldn't open preview image: {}", e), ToastSeverity::Error);
+        }
+    }
+
     pub fn load_full_preview(&mut self, ctx: &egui::Context, map_name: &str) {
         if self.preview_textures.contains_key(map_name) || self.preview_loading.contains(map_name) {
             return;
         }
 
-        let full_path = self
-            .cache_dir
-            .join("full")
-            .join(format!("{}.png", map_name));
+        let full_path = self.full_preview_path(map_name);
 
         if full_path.exists() {
             let tex = image::open(&full_path).ok().map(|img| {
@@ -108,9 +448,16 @@ impl App {
                     egui::TextureOptions::LINEAR,
                 )
             });
+            if tex.is_none() {
+                self.cache_suspects.insert(map_name.to_string());
+            }
             self.preview_textures.insert(map_name.to_string(), tex);
             return;
         }
+        if self.full_preview_missing(map_name) {
+            self.preview_textures.insert(map_name.to_string(), None);
+            return;
+        }
 
         self.preview_loading.insert(map_name.to_string());
         let url = format!("{}/full/{}.png", PREVIEWS_BASE_URL, map_name);
@@ -118,13 +465,9 @@ impl App {
         let ctx_clone = ctx.clone();
 
         self.runtime.spawn(async move {
-            if let Ok(response) = reqwest::get(&url).await {
-                if response.status().is_success() {
-                    if let Ok(bytes) = response.bytes().await {
-                        std::fs::create_dir_all(cache_path.parent().unwrap()).ok();
-                        std::fs::write(&cache_path, &bytes).ok();
-                    }
-                }
+            if let Ok(response) = fetch_image(&reqwest::Client::new(), &url).await {
+                std::fs::create_dir_all(cache_path.parent().unwrap()).ok();
+                store_fetched_image(response, &cache_path).await;
             }
             ctx_clone.request_repaint();
         });
@@ -140,4 +483,295 @@ impl App {
             self.load_full_preview(ctx, name);
         }
     }
+
+    /// Close the preview window and drop all its textures
+    pub fn close_preview(&mut self) {
+        self.preview_maps.clear();
+        self.preview_textures.clear();
+        self.preview_loading.clear();
+        self.preview_hires.clear();
+        self.preview_hires_loading.clear();
+        self.preview_active_tab = 0;
+    }
+
+    /// Close preview tabs by index, dropping their textures. The active tab stays
+    /// active if it survives, otherwise the tab that followed it takes over.
+    pub fn close_preview_tabs(&mut self, tabs: &[usize]) {
+        let active = self.preview_active_tab;
+        let removed_before = tabs.iter().filter(|&&t| t < active).count();
+        let mut idx = 0;
+        self.preview_maps.retain(|name| {
+            let keep = !tabs.contains(&idx);
+            idx += 1;
+            if !keep {
+                self.preview_textures.remove(name);
+                self.preview_loading.remove(name);
+                self.preview_hires.remove(name);
+                self.preview_hires_loading.remove(name);
+            }
+            keep
+        });
+        self.preview_active_tab = (active - removed_before).min(self.preview_maps.len().saturating_sub(1));
+        if tabs.contains(&active) {
+            self.preview_zoom = 1.0;
+            self.preview_offset = egui::Vec2::ZERO;
+            self.preview_needs_fit = true;
+        }
+    }
+
+    /// Pick up a finished integrity check and start one for images that failed to
+    /// decode since. Each map is checked once per session, so an image that is
+    /// broken on the server doesn't loop.
+    pub fn poll_cache_repair(&mut self, ctx: &egui::Context) {
+        if let Some((corrupt, repaired, failed)) =
+            ctx.memory(|mem| mem.data.get_temp::<(Vec<String>, usize, usize)>(CACHE_REPAIR_KEY.into()))
+        {
+            ctx.memory_mut(|mem| mem.data.remove::<(Vec<String>, usize, usize)>(CACHE_REPAIR_KEY.into()));
+            self.cache_repair_running = false;
+            for name in &corrupt {
+                self.thumbnail_cache.remove(name);
+                self.thumbnail_pending.remove(name);
+                self.preview_textures.remove(name);
+                self.preview_loading.remove(name);
+                if let Some(base) = name.strip_suffix(PREVIEW_HIRES_SUFFIX) {
+                    self.preview_hires.remove(base);
+                }
+            }
+            if !corrupt.is_empty() {
+                info!(repaired, failed, "Replaced corrupt cache files");
+            }
+            if self.cache_repair_manual {
+                self.cache_repair_manual = false;
+                self.cache_repair_result = Some((repaired, failed));
+            }
+        }
+
+        if self.cache_repair_running || self.cache_suspects.is_empty() {
+            return;
+        }
+        let names: Vec<String> = std::mem::take(&mut self.cache_suspects)
+            .into_iter()
+            .filter(|name| self.cache_checked.insert(name.clone()))
+            .collect();
+        if names.is_empty() {
+            return;
+        }
+        let files = names
+            .iter()
+            .flat_map(|name| {
+                [
+                    (self.thumbnail_path(name), "thumbnails"),
+                    (self.full_preview_path(name), "full"),
+                ]
+                .map(|(path, kind)| (name.clone(), path, kind))
+            })
+            .collect();
+        self.spawn_cache_repair(ctx, files);
+    }
+
+    /// Check every cached image (Settings > Cache). The count shows in Settings when done.
+    pub fn check_cache_integrity(&mut self, ctx: &egui::Context) {
+        if self.cache_repair_running {
+            // An automatic repair of images that failed to decode
+            if !self.cache_repair_manual {
+                self.push_toast("Already repairing some cached images, try again in a moment", ToastSeverity::Info);
+            }
+            return;
+        }
+        let mut files = Vec::new();
+        for kind in ["thumbnails", "full"] {
+            let Ok(entries) = std::fs::read_dir(self.cache_dir.join(kind)) else { continue };
+            for path in entries.flatten().map(|e| e.path()) {
+                if path.extension().is_some_and(|e| IMAGE_EXTENSIONS.iter().any(|ext| e == *ext)) {
+                    let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                    files.push((name, path, kind));
+                }
+            }
+        }
+        debug!(count = files.len(), "Checking cache integrity");
+        self.cache_repair_manual = true;
+        self.cache_repair_result = None;
+        self.spawn_cache_repair(ctx, files);
+    }
+
+    /// Decode each (map name, path, cache kind) off the UI thread; corrupt files are
+    /// deleted and fetched again. Posts the outcome to `CACHE_REPAIR_KEY`.
+    fn spawn_cache_repair(&mut self, ctx: &egui::Context, files: Vec<(String, PathBuf, &'static str)>) {
+        self.cache_repair_running = true;
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let corrupt = tokio::task::spawn_blocking(move || {
+                files
+                    .into_iter()
+                    .filter(|(_, path, _)| path.exists() && image::open(path).is_err())
+                    .collect::<Vec<_>>()
+            })
+            .await
+            .unwrap_or_default();
+
+            let client = reqwest::Client::new();
+            let mut names = Vec::new();
+            let (mut repaired, mut failed) = (0, 0);
+            for (name, path, kind) in corrupt {
+                warn!(path = %path.display(), "Corrupt cache file, fetching it again");
+                std::fs::remove_file(&path).ok();
+                let url = format!("{}/{}/{}.png", PREVIEWS_BASE_URL, kind, name);
+                let saved = match fetch_image(&client, &url).await {
+                    Ok(response) if response.status().is_success() => match response.bytes().await {
+                        Ok(bytes) => save_image(&path, &bytes),
+                        Err(_) => false,
+                    },
+                    _ => false,
+                };
+                if saved {
+                    repaired += 1;
+                } else {
+                    warn!(url = %url, "Couldn't fetch the cache file again");
+                    failed += 1;
+                }
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            ctx.memory_mut(|mem| mem.data.insert_temp(CACHE_REPAIR_KEY.into(), (names, repaired, failed)));
+            ctx.request_repaint();
+        });
+    }
+
+    fn cache_kind_dir(&self, kind: CacheKind) -> PathBuf {
+        match kind {
+            CacheKind::Thumbnails => self.cache_dir.join("thumbnails"),
+            CacheKind::FullPreviews => self.cache_dir.join("full"),
+            CacheKind::Logs => self.data_dir.join("logs"),
+        }
+    }
+
+    /// Pick up sizes measured by `refresh_cache_sizes`, starting a measurement if none is cached
+    pub fn poll_cache_sizes(&mut self, ctx: &egui::Context) {
+        if let Some(sizes) = ctx.memory(|mem| mem.data.get_temp::<[u64; 3]>(CACHE_SIZES_KEY.into())) {
+            ctx.memory_mut(|mem| mem.data.remove::<[u64; 3]>(CACHE_SIZES_KEY.into()));
+            self.cache_sizes = Some(sizes);
+            self.cache_sizes_pending = false;
+        }
+        if self.cache_sizes.is_none() && !self.cache_sizes_pending {
+            self.refresh_cache_sizes(ctx);
+        }
+    }
+
+    /// Measure each cache folder on a background thread (thousands of files)
+    pub fn refresh_cache_sizes(&mut self, ctx: &egui::Context) {
+        self.cache_sizes_pending = true;
+        let dirs = CacheKind::ALL.map(|kind| self.cache_kind_dir(kind));
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let sizes = dirs.map(|dir| {
+                std::fs::read_dir(dir)
+                    .map(|entries| {
+                        entries
+                            .flatten()
+                            .filter_map(|e| e.metadata().ok())
+                            .filter(|m| m.is_file())
+                            .map(|m| m.len())
+                            .sum()
+                    })
+                    .unwrap_or(0)
+            });
+            ctx.memory_mut(|mem| mem.data.insert_temp(CACHE_SIZES_KEY.into(), sizes));
+            ctx.request_repaint();
+        });
+    }
+
+    /// Delete one kind of cached data and drop only the in-memory textures built from it
+    pub fn clear_cache_kind(&mut self, ctx: &egui::Context, kind: CacheKind) {
+        let dir = self.cache_kind_dir(kind);
+        match kind {
+            CacheKind::Thumbnails => {
+                let _ = std::fs::remove_dir_all(&dir);
+                self.thumbnail_cache.clear();
+                self.thumbnail_last_used.clear();
+                self.thumbnail_pending.clear();
+                self.thumbnail_generation += 1;
+                self.thumbnail_missing.clear();
+                self.start_thumbnail_prefetch(ctx);
+            }
+            CacheKind::FullPreviews => {
+                let _ = std::fs::remove_dir_all(&dir);
+                self.preview_textures.clear();
+                self.preview_loading.clear();
+                self.preview_hires.clear();
+                self.preview_hires_loading.clear();
+                // Open preview tabs fetch their image again
+                for name in self.preview_maps.clone() {
+                    self.load_full_preview(ctx, &name);
+                }
+            }
+            CacheKind::Logs => {
+                // The daily appender keeps today's file open; it's skipped (and locked on Windows)
+                let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+                if let Ok(entries) = std::fs::read_dir(&dir) {
+                    for path in entries.flatten().map(|e| e.path()) {
+                        if !path.to_string_lossy().ends_with(&today) {
+                            let _ = std::fs::remove_file(&path);
+                        }
+                    }
+                }
+            }
+        }
+        info!(kind = kind.label(), "Cleared cache");
+        self.refresh_cache_sizes(ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn encode(format: image::ImageFormat) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image::DynamicImage::new_rgb8(4, 3).write_to(&mut Cursor::new(&mut bytes), format).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn saves_each_format_under_its_extension_and_decodes_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("Map");
+        for (format, ext) in [
+            (image::ImageFormat::Png, "png"),
+            (image::ImageFormat::WebP, "webp"),
+            (image::ImageFormat::Jpeg, "jpg"),
+        ] {
+            assert!(save_image(&base, &encode(format)), "{} not saved", ext);
+            let path = utils::cached_image(&base).unwrap();
+            assert_eq!(path, base.with_extension(ext));
+            let decoded = image::open(&path).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (4, 3));
+            // The copy in the previous format is gone
+            let cached = IMAGE_EXTENSIONS.iter().filter(|e| base.with_extension(e).exists()).count();
+            assert_eq!(cached, 1);
+        }
+    }
+
+    #[test]
+    fn corrupt_bytes_are_not_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("Map");
+        assert!(!save_image(&base, b"<html>502 Bad Gateway</html>"));
+        assert!(!save_image(&base, &[]));
+        assert_eq!(utils::cached_image(&base), None);
+    }
+
+    /// The magic bytes can't tell a cut-off download; decoding (as the integrity
+    /// check does) can
+    #[test]
+    fn corrupt_cached_file_fails_to_decode() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("Map");
+        let mut truncated = encode(image::ImageFormat::Png);
+        truncated.truncate(truncated.len() / 2);
+        assert!(save_image(&base, &truncated));
+        let path = utils::cached_image(&base).unwrap();
+        assert!(image::open(path).is_err());
+    }
 }
@@ -2,10 +2,13 @@
 
 use super::App;
 use crate::constants::*;
-use crate::db::Database;
+use crate::db::{Database, ManifestValidators};
 use crate::types::*;
 use eframe::egui;
+use std::io::{Read, Write};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 impl App {
@@ -17,7 +20,7 @@ impl App {
 
         let ctx = ctx.clone();
         let current_db_version = self.db.get_db_version().ok().flatten().unwrap_or_default();
-        let current_map_count = self.maps.len();
+        let current_map_count = self.maps.iter().filter(|m| m.removed_at.is_none()).count();
         let current_map_names: std::collections::HashSet<String> = 
             self.maps.iter().map(|m| m.name.clone()).collect();
         let db_path = dirs::data_local_dir()
@@ -103,65 +106,74 @@ impl App {
                 ctx.request_repaint();
             } else if !mock_app {
             
-            debug!(url = MANIFEST_URL, "Fetching manifest");
-            match reqwest::blocking::get(MANIFEST_URL) {
-                Ok(response) => {
-                    debug!(status = %response.status(), "Manifest response received");
-                    match response.json::<Manifest>() {
-                        Ok(manifest) => {
-                            debug!(
-                                manifest_version = %manifest.version,
-                                manifest_count = manifest.map_count,
-                                "Manifest parsed"
-                            );
-                            
-                            if manifest.version != current_db_version
-                                || manifest.map_count != current_map_count
-                            {
-                                info!("Database update available, auto-updating");
-                                
-                                    let new_maps: Vec<String> = manifest.maps.iter()
-                                        .filter(|m| !current_map_names.contains(&m.name))
-                                        .map(|m| m.name.clone())
-                                        .collect();
-                                    
-                                    let result: Result<usize, String> = (|| {
-                                        let db = Database::open(&db_path).map_err(|e| e.to_string())?;
-                                        db.clear_maps().map_err(|e| e.to_string())?;
-                                        let count = db.import_maps(&manifest.maps).map_err(|e| e.to_string())?;
-                                        db.set_db_version(&manifest.version).map_err(|e| e.to_string())?;
-                                        Ok(count)
-                                    })();
-                                    
-                                    match result {
-                                        Ok(count) => {
-                                            info!(
-                                                total = count,
-                                                new = new_maps.len(),
-                                                names = ?new_maps,
-                                                "Database auto-updated"
-                                            );
-                                            ctx.memory_mut(|mem| {
-                                                mem.data.insert_temp(
-                                                    "db_auto_updated".into(),
-                                                    new_maps.join(","),
-                                                );
-                                            });
-                                        }
-                                        Err(e) => {
-                                            error!(error = %e, "Database auto-update failed");
-                                        }
-                                    }
-                                    ctx.request_repaint();
-                            } else {
-                                debug!("Database is up to date");
+            let fetched = Database::open(&db_path)
+                .map_err(|e| ManifestError::Fetch(e.to_string()))
+                .and_then(|db| fetch_manifest(&db));
+            match fetched {
+                Ok(None) => {
+                    debug!("Manifest not modified, database is up to date");
+                }
+                Ok(Some((manifest, validators))) => {
+                    debug!(
+                        manifest_version = %manifest.version,
+                        manifest_count = manifest.map_count,
+                        "Manifest parsed"
+                    );
+
+                    if let Some(message) = stale_manifest_message(&manifest.version, &current_db_version) {
+                        warn!(manifest_version = %manifest.version, db_version = %current_db_version, "Manifest older than local database, skipped");
+                        ctx.memory_mut(|mem| mem.data.insert_temp("db_manifest_older".into(), message));
+                        ctx.request_repaint();
+                    } else if manifest.version != current_db_version
+                        || manifest.map_count != current_map_count
+                    {
+                        info!("Database update available, auto-updating");
+
+                            let new_maps: Vec<String> = manifest.maps.iter()
+                                .filter(|m| !current_map_names.contains(&m.name))
+                                .map(|m| m.name.clone())
+                                .collect();
+
+                            let result: Result<usize, String> = (|| {
+                                let db = Database::open(&db_path).map_err(|e| e.to_string())?;
+                                import_manifest(&db, &manifest, &validators)
+                            })();
+
+                            match result {
+                                Ok(count) => {
+                                    info!(
+                                        total = count,
+                                        new = new_maps.len(),
+                                        names = ?new_maps,
+                                        "Database auto-updated"
+                                    );
+                                    ctx.memory_mut(|mem| {
+                                        mem.data.insert_temp(
+                                            "db_auto_updated".into(),
+                                            new_maps.join(","),
+                                        );
+                                    });
+                                }
+                                Err(e) => {
+                                    error!(error = %e, "Database auto-update failed");
+                                }
                             }
-                        }
-                        Err(e) => {
-                            error!(error = %e, "Failed to parse manifest JSON");
+                            ctx.request_repaint();
+                    } else {
+                        debug!("Database is up to date");
+                        // Same content under new validators; keep them so the next check is a 304
+                        if let Ok(db) = Database::open(&db_path) {
+                            db.set_manifest_validators(MANIFEST_URL, &validators).ok();
                         }
                     }
                 }
+                Err(e @ ManifestError::Unsupported(schema)) => {
+                    error!(schema, supported = MANIFEST_SCHEMA, "Manifest schema not supported");
+                    ctx.memory_mut(|mem| {
+                        mem.data.insert_temp("db_schema_unsupported".into(), e.to_string());
+                    });
+                    ctx.request_repaint();
+                }
                 Err(e) => {
                     error!(error = %e, "Failed to fetch manifest");
                 }
@@ -191,44 +203,59 @@ impl App {
         self.update_in_progress = true;
         let ctx = ctx.clone();
         let is_mock_retry = std::env::var("MOCK_APP_UPDATE").is_ok() && self.app_update_error.is_some();
+        // A fresh one per attempt, so a cancelled download still winding down can't
+        // report into the next attempt's progress
+        let progress = Arc::new(Mutex::new(AppUpdateProgress::default()));
+        self.app_update_progress = progress.clone();
+        let cancel = CancellationToken::new();
+        self.app_update_cancel = Some(cancel.clone());
 
         info!("Starting app update download");
         std::thread::spawn(move || {
-            if is_mock_retry {
-                // Mock: simulate success on retry
-                std::thread::sleep(std::time::Duration::from_millis(500));
-                ctx.memory_mut(|mem| {
-                    mem.data.insert_temp("app_update_done".into(), "1.0.0".to_string());
-                });
-                ctx.request_repaint();
+            let result = if is_mock_retry {
+                // Mock: simulate a download that succeeds on retry
+                mock_app_update(&progress, &cancel)
+            } else {
+                install_app_update(&progress, &cancel)
+            };
+            if cancel.is_cancelled() {
+                info!("App update cancelled");
                 return;
             }
 
-            let result = self_update::backends::github::Update::configure()
-                .repo_owner(REPO_OWNER)
-                .repo_name(REPO_NAME)
-                .bin_name("gores-map-downloader")
-                .bin_path_in_archive("Gores Map Downloader.exe")
-                .current_version(APP_VERSION)
-                .build()
-                .and_then(|u| u.update());
-
             ctx.memory_mut(|mem| match result {
-                Ok(status) => {
-                    info!(version = %status.version(), "App update downloaded");
-                    mem.data
-                        .insert_temp("app_update_done".into(), status.version().to_string());
+                Ok(version) => {
+                    info!(version = %version, "App update downloaded");
+                    mem.data.insert_temp("app_update_done".into(), version);
                 }
                 Err(e) => {
                     error!(error = %e, "App update failed");
-                    mem.data
-                        .insert_temp("app_update_error".into(), e.to_string());
+                    mem.data.insert_temp("app_update_error".into(), e);
                 }
             });
             ctx.request_repaint();
         });
     }
 
+    /// Stop the app update download; the partial file goes with its temp folder.
+    /// Once installing has started it runs to the end and reports its own outcome.
+    pub fn cancel_app_update(&mut self) {
+        {
+            // Same lock the download takes to switch to installing, so they can't cross
+            let progress = self.app_update_progress.lock().unwrap();
+            if progress.installing {
+                drop(progress);
+                self.push_toast("The update is already being installed", ToastSeverity::Info);
+                return;
+            }
+            if let Some(cancel) = self.app_update_cancel.take() {
+                cancel.cancel();
+            }
+        }
+        self.update_in_progress = false;
+        self.push_toast("Update cancelled", ToastSeverity::Info);
+    }
+
     pub fn perform_db_update(&mut self, ctx: &egui::Context) {
         self.update_in_progress = true;
         let ctx = ctx.clone();
@@ -239,23 +266,36 @@ impl App {
 
         info!("Starting manual database update");
         std::thread::spawn(move || {
-            let result: Result<(String, usize), String> = (|| {
-                let response = reqwest::blocking::get(MANIFEST_URL).map_err(|e| e.to_string())?;
-                let manifest: Manifest = response.json().map_err(|e| e.to_string())?;
+            // None when the server reports the manifest unchanged (304)
+            let mut stale: Option<String> = None;
+            let result: Result<Option<(String, usize)>, String> = (|| {
                 let db = Database::open(&db_path).map_err(|e| e.to_string())?;
-                db.clear_maps().map_err(|e| e.to_string())?;
-                let count = db.import_maps(&manifest.maps).map_err(|e| e.to_string())?;
-                db.set_db_version(&manifest.version)
-                    .map_err(|e| e.to_string())?;
-                Ok((manifest.version, count))
+                let Some((manifest, validators)) = fetch_manifest(&db).map_err(|e| e.to_string())? else {
+                    return Ok(None);
+                };
+                let local = db.get_db_version().map_err(|e| e.to_string())?.unwrap_or_default();
+                if let Some(message) = stale_manifest_message(&manifest.version, &local) {
+                    warn!(manifest_version = %manifest.version, db_version = %local, "Manifest older than local database, skipped");
+                    stale = Some(message);
+                    return Ok(None);
+                }
+                let count = import_manifest(&db, &manifest, &validators)?;
+                Ok(Some((manifest.version, count)))
             })();
 
             ctx.memory_mut(|mem| match result {
-                Ok((version, count)) => {
+                Ok(None) if stale.is_some() => {
+                    mem.data.insert_temp("db_manifest_older".into(), stale.unwrap_or_default());
+                }
+                Ok(Some((version, count))) => {
                     info!(version = %version, count = count, "Manual database update complete");
                     mem.data
                         .insert_temp("db_update_done".into(), format!("{}:{}", version, count));
                 }
+                Ok(None) => {
+                    info!("Manifest not modified, database already up to date");
+                    mem.data.insert_temp("db_update_unchanged".into(), true);
+                }
                 Err(e) => {
                     error!(error = %e, "Manual database update failed");
                     mem.data.insert_temp("db_update_error".into(), e);
@@ -264,4 +304,264 @@ impl App {
             ctx.request_repaint();
         });
     }
+
+    /// Import a manifest read from disk (offline setups, testing); maps it doesn't
+    /// list are flagged as removed. Reads and imports on a background thread, like
+    /// `perform_db_update`; `poll_manifest_import` reports the result.
+    /// Its validators are cleared, so the next online sync fetches the full manifest.
+    pub fn import_manifest_file(&mut self, ctx: &egui::Context, path: PathBuf) {
+        self.update_in_progress = true;
+        let db_path = self.data_dir.join("maps.db");
+        let ctx = ctx.clone();
+        info!(file = %path.display(), "Importing manifest from file");
+        std::thread::spawn(move || {
+            let result = std::fs::read(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|bytes| Manifest::parse(&bytes).map_err(|e| e.to_string()))
+                .and_then(|manifest| {
+                    if manifest.maps.is_empty() {
+                        return Err("it contains no maps".to_string());
+                    }
+                    if let Some(pos) = manifest.maps.iter().position(|m| m.name.trim().is_empty()) {
+                        return Err(format!("map #{} has no name", pos + 1));
+                    }
+                    let db = Database::open(&db_path).map_err(|e| e.to_string())?;
+                    let count = import_manifest(&db, &manifest, &ManifestValidators::default())?;
+                    Ok((manifest.version, count))
+                });
+            ctx.memory_mut(|mem| mem.data.insert_temp("manifest_import_done".into(), (path, result)));
+            ctx.request_repaint();
+        });
+    }
+
+    /// Report a finished `import_manifest_file`
+    pub fn poll_manifest_import(&mut self, ctx: &egui::Context) {
+        type ImportResult = (PathBuf, Result<(String, usize), String>);
+        let Some((path, result)) = ctx.memory(|mem| mem.data.get_temp::<ImportResult>("manifest_import_done".into()))
+        else {
+            return;
+        };
+        ctx.memory_mut(|mem| mem.data.remove::<ImportResult>("manifest_import_done".into()));
+        self.update_in_progress = false;
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        match result {
+            Ok((version, count)) => {
+                info!(file = %path.display(), version = %version, count, "Imported manifest from file");
+                self.reload_maps();
+                self.push_toast(
+                    format!("Imported {} maps from {} (version {})", count, file_name, version),
+                    ToastSeverity::Success,
+                );
+            }
+            Err(e) => {
+                error!(file = %path.display(), error = %e, "Manifest import failed");
+                self.push_toast(format!("Couldn't import {}: {}", file_name, e), ToastSeverity::Error);
+            }
+        }
+    }
+}
+
+/// Download the latest release in chunks, reporting to `progress`, then replace the
+/// running binary with the one in it. Returns the version now installed. Stops
+/// between chunks once `cancel` fires.
+fn install_app_update(progress: &Mutex<AppUpdateProgress>, cancel: &CancellationToken) -> Result<String, String> {
+    let updater = self_update::backends::github::Update::configure()
+        .repo_owner(REPO_OWNER)
+        .repo_name(REPO_NAME)
+        .bin_name("gores-map-downloader")
+        .bin_path_in_archive(UPDATE_BIN_IN_ARCHIVE)
+        .current_version(APP_VERSION)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let release = updater.get_latest_release().map_err(|e| e.to_string())?;
+    if !self_update::version::bump_is_greater(APP_VERSION, &release.version).map_err(|e| e.to_string())? {
+        return Ok(APP_VERSION.to_string());
+    }
+    let asset = release
+        .asset_for(&updater.target(), updater.identifier().as_deref())
+        .ok_or_else(|| format!("v{} has no download for {}", release.version, updater.target()))?;
+
+    // Removed with everything in it when this returns, finished or not
+    let dir = self_update::TempDir::new().map_err(|e| e.to_string())?;
+    let archive_path = dir.path().join(&asset.name);
+    let mut response = reqwest::blocking::Client::builder()
+        .connect_timeout(UPDATE_CONNECT_TIMEOUT)
+        .timeout(UPDATE_READ_TIMEOUT)
+        .build()
+        .and_then(|client| {
+            client
+                .get(&asset.download_url)
+                .header(reqwest::header::ACCEPT, "application/octet-stream")
+                .header(reqwest::header::USER_AGENT, format!("gores-map-downloader/{}", APP_VERSION))
+                .send()
+        })
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?;
+    progress.lock().unwrap().total = response.content_length();
+    debug!(asset = %asset.name, size = ?response.content_length(), "Downloading app update");
+
+    let mut file = std::fs::File::create(&archive_path).map_err(|e| e.to_string())?;
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        if cancel.is_cancelled() {
+            return Err("cancelled".to_string());
+        }
+        let read = response.read(&mut buf).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buf[..read]).map_err(|e| e.to_string())?;
+        progress.lock().unwrap().downloaded += read as u64;
+    }
+    drop(file);
+
+    start_installing(progress, cancel)?;
+    self_update::Extract::from_source(&archive_path)
+        .extract_file(dir.path(), UPDATE_BIN_IN_ARCHIVE)
+        .map_err(|e| e.to_string())?;
+    self_update::self_replace::self_replace(dir.path().join(UPDATE_BIN_IN_ARCHIVE)).map_err(|e| e.to_string())?;
+    Ok(release.version)
+}
+
+/// MOCK_APP_UPDATE: a fake download so the progress and cancel can be tried
+fn mock_app_update(progress: &Mutex<AppUpdateProgress>, cancel: &CancellationToken) -> Result<String, String> {
+    const CHUNK: u64 = 256 * 1024;
+    progress.lock().unwrap().total = Some(32 * CHUNK);
+    for _ in 0..32 {
+        if cancel.is_cancelled() {
+            return Err("cancelled".to_string());
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        progress.lock().unwrap().downloaded += CHUNK;
+    }
+    start_installing(progress, cancel)?;
+    Ok("1.0.0".to_string())
+}
+
+/// Switch to installing unless the download was cancelled. `cancel_app_update` checks
+/// the flag under the same lock, so a cancel either lands first or is refused.
+fn start_installing(progress: &Mutex<AppUpdateProgress>, cancel: &CancellationToken) -> Result<(), String> {
+    let mut progress = progress.lock().unwrap();
+    if cancel.is_cancelled() {
+        return Err("cancelled".to_string());
+    }
+    progress.installing = true;
+    Ok(())
+}
+
+/// Fetch the manifest, sending the stored validators as conditional headers.
+/// Returns `None` on 304 Not Modified, so the caller can skip the import entirely.
+fn fetch_manifest(db: &Database) -> Result<Option<(Manifest, ManifestValidators)>, ManifestError> {
+    let fetch_err = |e: &dyn std::fmt::Display| ManifestError::Fetch(e.to_string());
+    let cached = db.get_manifest_validators(MANIFEST_URL).map_err(|e| fetch_err(&e))?;
+    let mut request = reqwest::blocking::Client::new().get(MANIFEST_URL);
+    if let Some(etag) = &cached.etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &cached.last_modified {
+        request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+    }
+
+    debug!(url = MANIFEST_URL, conditional = cached.etag.is_some() || cached.last_modified.is_some(), "Fetching manifest");
+    let response = request.send().map_err(|e| fetch_err(&e))?;
+    debug!(status = %response.status(), "Manifest response received");
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    let response = response.error_for_status().map_err(|e| fetch_err(&e))?;
+
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v: &reqwest::header::HeaderValue| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let validators = ManifestValidators {
+        etag: header(reqwest::header::ETAG),
+        last_modified: header(reqwest::header::LAST_MODIFIED),
+    };
+    let bytes = response.bytes().map_err(|e| fetch_err(&e))?;
+    let manifest = Manifest::parse(&bytes)?;
+    debug!(schema = manifest.schema, "Manifest parsed");
+    Ok(Some((manifest, validators)))
+}
+
+/// Toast text when the fetched manifest is older than the local database, so a
+/// stale mirror can't roll it back. Versions that can't be ordered never block.
+fn stale_manifest_message(remote: &str, local: &str) -> Option<String> {
+    (compare_manifest_versions(remote, local)? == std::cmp::Ordering::Less).then(|| {
+        format!(
+            "Remote manifest (v{}) is older than local (v{}); skipped.",
+            remote.trim_start_matches('v'),
+            local.trim_start_matches('v')
+        )
+    })
+}
+
+/// Order two manifest versions made of numeric parts ("1.4.2", "v2", "2024-05-01").
+/// Missing parts count as 0; anything else (empty, "beta", "1.x") is None.
+fn compare_manifest_versions(a: &str, b: &str) -> Option<std::cmp::Ordering> {
+    let parse = |s: &str| -> Option<Vec<u64>> {
+        let s = s.trim().trim_start_matches('v');
+        if s.is_empty() {
+            return None;
+        }
+        s.split(['.', '-']).map(|p| p.parse().ok()).collect()
+    };
+    let (mut a, mut b) = (parse(a)?, parse(b)?);
+    let len = a.len().max(b.len());
+    a.resize(len, 0);
+    b.resize(len, 0);
+    Some(a.cmp(&b))
+}
+
+/// Import `manifest`, flag maps it no longer lists as removed and remember its validators
+fn import_manifest(db: &Database, manifest: &Manifest, validators: &ManifestValidators) -> Result<usize, String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    // All or nothing, so a failed import can't leave maps without their files or hashes
+    let (count, removed) = db
+        .in_transaction(|db| {
+            let count = db.import_maps(&manifest.maps)?;
+            let removed = db.mark_removed(&manifest.maps, now)?;
+            db.set_db_version(&manifest.version)?;
+            db.set_manifest_validators(MANIFEST_URL, validators)?;
+            Ok((count, removed))
+        })
+        .map_err(|e| e.to_string())?;
+    if removed > 0 {
+        info!(removed, "Maps no longer in the manifest");
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn orders_numeric_versions() {
+        assert_eq!(compare_manifest_versions("1.4.2", "1.10.0"), Some(Ordering::Less));
+        assert_eq!(compare_manifest_versions("2024-05-01", "2024-04-30"), Some(Ordering::Greater));
+        assert_eq!(compare_manifest_versions("v2", "1.9"), Some(Ordering::Greater));
+    }
+
+    #[test]
+    fn missing_parts_count_as_zero() {
+        assert_eq!(compare_manifest_versions("1.4", "1.4.0"), Some(Ordering::Equal));
+        assert_eq!(compare_manifest_versions("v3", "3"), Some(Ordering::Equal));
+        assert_eq!(compare_manifest_versions("1.4", "1.4.1"), Some(Ordering::Less));
+    }
+
+    #[test]
+    fn malformed_or_missing_versions_are_unordered() {
+        assert_eq!(compare_manifest_versions("beta", "1.0"), None);
+        assert_eq!(compare_manifest_versions("1.0", "1.x"), None);
+        assert_eq!(compare_manifest_versions("", "1.0"), None);
+        assert_eq!(compare_manifest_versions("1.0", "  "), None);
+        assert_eq!(compare_manifest_versions("1..0", "1.0"), None);
+    }
 }
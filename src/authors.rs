@@ -0,0 +1,48 @@
+//! Author normalization: collaborations are split into individual mappers and
+//! spelling variants are mapped to one canonical name. The manifest's original
+//! string is still what gets displayed; search and sorting use the canonical names.
+
+use crate::constants::DEFAULT_AUTHOR_ALIASES;
+use std::collections::HashMap;
+
+/// Variant -> canonical author, matched case-insensitively
+pub struct AuthorAliases {
+    map: HashMap<String, String>,
+}
+
+impl AuthorAliases {
+    /// Shipped defaults, overridden by the user's entries from settings.json
+    pub fn new(user: &HashMap<String, String>) -> Self {
+        let map = DEFAULT_AUTHOR_ALIASES
+            .iter()
+            .copied()
+            .chain(user.iter().map(|(variant, canonical)| (variant.as_str(), canonical.as_str())))
+            .map(|(variant, canonical)| (variant.trim().to_lowercase(), canonical.trim().to_string()))
+            .collect();
+        Self { map }
+    }
+
+    pub fn canonical(&self, name: &str) -> String {
+        self.map
+            .get(&name.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    /// Canonical names of everyone credited in `raw`, in credit order without duplicates
+    pub fn normalize(&self, raw: &str) -> Vec<String> {
+        let mut authors: Vec<String> = Vec::new();
+        for name in split_authors(raw) {
+            let canonical = self.canonical(name);
+            if !authors.iter().any(|a| a.eq_ignore_ascii_case(&canonical)) {
+                authors.push(canonical);
+            }
+        }
+        authors
+    }
+}
+
+/// Individual names in a credit like "Fau & Soapy Sandwich" or "A, B"
+pub fn split_authors(raw: &str) -> impl Iterator<Item = &str> {
+    raw.split(['&', ',']).map(str::trim).filter(|name| !name.is_empty())
+}
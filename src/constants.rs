@@ -6,8 +6,123 @@ pub const PREVIEWS_BASE_URL: &str =
 pub const MANIFEST_URL: &str =
     "https://raw.githubusercontent.com/wtfseanscool/kog-maps/main/manifest.json";
 pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Newest manifest schema this build understands; manifests without a schema field are 1
+pub const MANIFEST_SCHEMA: u32 = 1;
 pub const REPO_OWNER: &str = "wtfseanscool";
 pub const REPO_NAME: &str = "Gores-Map-Downloader";
+/// Executable inside the release archive that replaces the running one
+pub const UPDATE_BIN_IN_ARCHIVE: &str = "Gores Map Downloader.exe";
+/// Connect timeout for the app update download
+pub const UPDATE_CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+/// Longest the app update download may go without receiving data. It applies per read,
+/// so slow connections still finish, and a stalled one can't outlive Cancel for long.
+pub const UPDATE_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+
+/// How long a post-download command may run before it's killed
+pub const POST_COMMAND_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// New-issue page for "Report issue"; title and body are appended as query parameters
+pub const ISSUE_URL: &str = "https://github.com/wtfseanscool/Gores-Map-Downloader/issues/new";
+
+/// File name suffix of the double-resolution preview render, on the server and in
+/// the full preview cache (`full/<name>@2x.png`)
+pub const PREVIEW_HIRES_SUFFIX: &str = "@2x";
+
+/// Max thumbnail textures kept on the GPU; least recently used are evicted
+pub const THUMBNAIL_TEXTURE_CAP: usize = 512;
+
+/// Thumbnail textures created per frame; the rest wait, so a fast scroll through
+/// the grid doesn't upload dozens at once
+pub const THUMBNAIL_UPLOADS_PER_FRAME: usize = 6;
+
+/// Thumbnails decoded off the UI thread at once
+pub const THUMBNAIL_DECODES_IN_FLIGHT: usize = 16;
+
+/// Frames a decoded thumbnail waits without being drawn before it's dropped
+/// (its card scrolled far out of view)
+pub const THUMBNAIL_STALE_PASSES: u64 = 30;
+
+/// Formats a cached thumbnail or preview may be stored in, by file extension.
+/// URLs and cache paths are written with `.png`; the file takes the extension of
+/// what the server actually sent.
+pub const IMAGE_EXTENSIONS: [&str; 3] = ["png", "webp", "jpg"];
+
+/// Accept header for image requests; WebP is far smaller where the server has it
+pub const IMAGE_ACCEPT: &str = "image/webp,image/jpeg;q=0.9,image/png;q=0.8";
+
+/// Extra attempts for a prefetched thumbnail after a network or server error
+pub const PREFETCH_RETRIES: u32 = 2;
+
+/// Selection states kept for Ctrl+Z
+pub const SELECTION_UNDO_DEPTH: usize = 10;
+
+/// Select All menu entry that opens the paste-a-list modal
+pub const PASTE_LIST_LABEL: &str = "Paste list to select…";
+
+/// Batches up to this size start without the download confirmation
+pub const CONFIRM_DOWNLOAD_THRESHOLD: usize = 20;
+
+/// Libraries with more maps than this score searches on a background thread
+pub const ASYNC_SEARCH_THRESHOLD: usize = 5_000;
+
+/// Pause after a keystroke before a background search starts, so typing doesn't queue passes
+pub const SEARCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(120);
+
+/// How long the header shows the result count change after filtering
+pub const COUNT_FLASH_DURATION: std::time::Duration = std::time::Duration::from_millis(2000);
+
+/// Star range per category (Easy..Extreme, Solo, Mod, Extra) for the per-category
+/// star filter; only Solo, Mod and Extra go above 3 stars
+pub const DEFAULT_CATEGORY_STARS: [(u8, u8); 8] =
+    [(1, 3), (1, 3), (1, 3), (1, 3), (1, 3), (1, 5), (1, 5), (1, 5)];
+
+/// Default star directory in map URLs (`{stars}` is replaced with the star count)
+pub const DEFAULT_STAR_DIR: &str = "{stars}star";
+
+/// Known spellings of the same mapper (variant, canonical); users can add more
+/// under `author_aliases` in settings.json
+pub const DEFAULT_AUTHOR_ALIASES: &[(&str, &str)] = &[("FauCry", "Fau")];
 
 /// Cache refresh - maps to clear when upgrading to/past each version
 pub const CACHE_REFRESH: &[(&str, &[&str])] = &[];
+
+/// Keyboard and mouse bindings by area, listed in the "?" / F1 cheat sheet.
+/// Keep in sync with the handlers when adding or changing a shortcut.
+pub const SHORTCUTS: &[(&str, &[(&str, &str)])] = &[
+    ("Navigation", &[
+        ("Up / Down", "Move the selection"),
+        ("Page Up / Page Down", "Scroll one page"),
+        ("Type anywhere", "Search"),
+        ("/  or  Ctrl+F", "Focus the search box"),
+        ("Ctrl+,", "Open or close Settings"),
+        ("?  or  F1", "Show this cheat sheet"),
+    ]),
+    ("Selection", &[
+        ("Click", "Select or deselect a map"),
+        ("Shift+Click", "Select a range"),
+        ("Shift+Up / Down", "Extend the selection"),
+        ("Ctrl+A", "Select all shown maps"),
+        ("Ctrl+Shift+A", "Select shown maps not downloaded"),
+        ("Ctrl+Alt+A", "Select shown maps already downloaded"),
+        ("Ctrl+Shift+V", "Select maps named in the clipboard"),
+        ("Ctrl+Z", "Undo a selection change"),
+        ("Escape", "Clear the selection"),
+    ]),
+    ("Downloads", &[
+        ("Ctrl+D", "Download the selection"),
+    ]),
+    ("Preview", &[
+        ("Enter", "Preview the selection"),
+        ("Double-click", "Preview a map"),
+        ("Escape", "Close the preview or topmost dialog"),
+    ]),
+];
+
+/// Search box tooltip describing the numeric filter syntax
+pub const SEARCH_SYNTAX_HELP: &str = "Every word must match the name, author or category\n\
+    (kobra extreme); \"quote a phrase\" to match it whole.\n\n\
+    Filter by numbers alongside text:\n\
+    points>=5000, points<=200, >=5000 (points)\n\
+    stars:4, stars>=3\n\
+    pts:100-500, stars:2-4 (inclusive ranges)\n\n\
+    author:\"Full Name\" shows only that author's maps";
@@ -1,9 +1,11 @@
 //! Database module for Gores Map Downloader
 //! Handles SQLite storage for map metadata and user settings
 
-use crate::types::ManifestMap;
+use crate::mapfile::MapFileInfo;
+use crate::types::{CompanionFile, ManifestMap};
 use rusqlite::{params, Connection, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use tracing::{debug, error};
 
@@ -20,6 +22,25 @@ pub struct Map {
     pub size: i64,
     pub downloaded: bool,
     pub local_path: Option<String>,
+    /// Canonical names of everyone credited in `author`, filled by `App::normalize_authors`
+    #[serde(default)]
+    pub authors: Vec<String>,
+    #[serde(default)]
+    pub companions: Vec<CompanionFile>,
+    /// Expected SHA-256 of the .map file, when the manifest lists one
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// Unix time the manifest stopped listing this map; its download URL is dead
+    #[serde(default)]
+    pub removed_at: Option<i64>,
+}
+
+/// HTTP validators from the last imported manifest response, sent back as
+/// If-None-Match / If-Modified-Since so an unchanged manifest costs a 304
+#[derive(Debug, Clone, Default)]
+pub struct ManifestValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
 }
 
 pub struct Database {
@@ -65,23 +86,104 @@ impl Database {
             CREATE TABLE IF NOT EXISTS metadata (
                 key TEXT PRIMARY KEY,
                 value TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS map_companions (
+                map TEXT NOT NULL,
+                file TEXT NOT NULL,
+                size INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (map, file)
+            );
+
+            CREATE TABLE IF NOT EXISTS map_hashes (
+                map TEXT PRIMARY KEY,
+                sha256 TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS map_file_hash (
+                name TEXT PRIMARY KEY,
+                file_size INTEGER NOT NULL,
+                modified INTEGER NOT NULL,
+                sha256 TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS map_file_info (
+                name TEXT PRIMARY KEY,
+                file_size INTEGER NOT NULL,
+                modified INTEGER NOT NULL,
+                info TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS map_removed (
+                map TEXT PRIMARY KEY,
+                removed_at INTEGER NOT NULL
             );",
         )?;
         Ok(())
     }
 
-    /// Clear all maps from database
-    pub fn clear_maps(&self) -> Result<()> {
-        self.conn.execute("DELETE FROM maps", [])?;
-        Ok(())
+    /// Flag maps missing from `maps` (a complete manifest) as removed at `now` and
+    /// unflag any listed again. Rows are kept, not deleted. Returns how many were
+    /// newly flagged.
+    pub fn mark_removed(&self, maps: &[ManifestMap], now: i64) -> Result<usize> {
+        let listed: HashSet<&str> = maps.iter().map(|m| m.name.as_str()).collect();
+        let known: Vec<String> = self
+            .conn
+            .prepare("SELECT name FROM maps")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_>>()?;
+        let mut flagged = 0;
+        for name in known {
+            if listed.contains(name.as_str()) {
+                self.conn.execute("DELETE FROM map_removed WHERE map = ?1", params![name])?;
+            } else {
+                flagged += self.conn.execute(
+                    "INSERT OR IGNORE INTO map_removed (map, removed_at) VALUES (?1, ?2)",
+                    params![name, now],
+                )?;
+            }
+        }
+        debug!(flagged, "Maps missing from the manifest flagged as removed");
+        Ok(flagged)
     }
 
-    /// Import maps from JSON data, preserving download status
-    pub fn import_maps(&self, maps: &[ManifestMap]) -> Result<usize> {
-        let mut imported = 0;
+    /// Delete maps flagged as removed before `before` (Unix time), with everything
+    /// stored about them, in one transaction
+    pub fn purge_removed(&self, before: i64) -> Result<usize> {
+        self.in_transaction(|db| {
+            let stale = "SELECT map FROM map_removed WHERE removed_at < ?1";
+            let purged = db
+                .conn
+                .execute(&format!("DELETE FROM maps WHERE name IN ({})", stale), params![before])?;
+            for (table, column) in [
+                ("map_companions", "map"),
+                ("map_hashes", "map"),
+                ("map_file_hash", "name"),
+                ("map_file_info", "name"),
+            ] {
+                db.conn.execute(
+                    &format!("DELETE FROM {} WHERE {} IN ({})", table, column, stale),
+                    params![before],
+                )?;
+            }
+            db.conn.execute("DELETE FROM map_removed WHERE removed_at < ?1", params![before])?;
+            Ok(purged)
+        })
+    }
+
+    /// Run `f` in one transaction: committed if it returns Ok, rolled back otherwise
+    pub fn in_transaction<T>(&self, f: impl FnOnce(&Self) -> Result<T>) -> Result<T> {
+        let tx = self.conn.unchecked_transaction()?;
+        let value = f(self)?;
+        tx.commit()?;
+        Ok(value)
+    }
 
+    /// Import maps from JSON data, preserving download status.
+    /// Stops at the first failure; run it in `in_transaction` so nothing is kept then.
+    pub fn import_maps(&self, maps: &[ManifestMap]) -> Result<usize> {
         for map in maps {
-            let result = self.conn.execute(
+            self.conn.execute(
                 "INSERT INTO maps (name, category, stars, points, author, release_date, size)
                  VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
                  ON CONFLICT(name) DO UPDATE SET
@@ -100,16 +202,16 @@ impl Database {
                     map.release_date,
                     map.size
                 ],
-            );
-
-            match result {
-                Ok(_) => imported += 1,
-                Err(e) => error!(map = %map.name, error = %e, "Failed to import map"),
-            }
+            )
+            .inspect_err(|e| error!(map = %map.name, error = %e, "Failed to import map"))?;
+            self.set_companions(&map.name, &map.files)
+                .inspect_err(|e| error!(map = %map.name, error = %e, "Failed to import companion files"))?;
+            self.set_hash(&map.name, map.sha256.as_deref())
+                .inspect_err(|e| error!(map = %map.name, error = %e, "Failed to import map hash"))?;
         }
 
-        debug!(imported = imported, total = maps.len(), "Maps imported");
-        Ok(imported)
+        debug!(imported = maps.len(), "Maps imported");
+        Ok(maps.len())
     }
 
     /// Get all maps
@@ -132,13 +234,85 @@ impl Database {
                     size: row.get(7)?,
                     downloaded: row.get::<_, i32>(8)? != 0,
                     local_path: row.get(9)?,
+                    authors: Vec::new(),
+                    companions: Vec::new(),
+                    sha256: None,
+                    removed_at: None,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
 
+        let mut companions = self.get_companions()?;
+        let mut hashes = self.get_hashes()?;
+        let mut removed = self.get_removed()?;
+        let maps = maps
+            .into_iter()
+            .map(|mut map| {
+                map.companions = companions.remove(&map.name).unwrap_or_default();
+                map.sha256 = hashes.remove(&map.name);
+                map.removed_at = removed.remove(&map.name);
+                map
+            })
+            .collect();
+
         Ok(maps)
     }
 
+    /// Replace the companion files listed for a map
+    fn set_companions(&self, map: &str, files: &[CompanionFile]) -> Result<()> {
+        self.conn.execute("DELETE FROM map_companions WHERE map = ?1", params![map])?;
+        for file in files {
+            self.conn.execute(
+                "INSERT OR REPLACE INTO map_companions (map, file, size) VALUES (?1, ?2, ?3)",
+                params![map, file.file, file.size],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Companion files of every map, keyed by map name
+    fn get_companions(&self) -> Result<HashMap<String, Vec<CompanionFile>>> {
+        let mut stmt = self.conn.prepare("SELECT map, file, size FROM map_companions ORDER BY file")?;
+        let mut companions: HashMap<String, Vec<CompanionFile>> = HashMap::new();
+        for row in stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, CompanionFile { file: row.get(1)?, size: row.get(2)? }))
+        })? {
+            let (map, file) = row?;
+            companions.entry(map).or_default().push(file);
+        }
+        Ok(companions)
+    }
+
+    /// Replace (or with None, forget) the expected hash of a map's file
+    fn set_hash(&self, map: &str, sha256: Option<&str>) -> Result<()> {
+        match sha256 {
+            Some(hash) => self.conn.execute(
+                "INSERT OR REPLACE INTO map_hashes (map, sha256) VALUES (?1, ?2)",
+                params![map, hash.to_ascii_lowercase()],
+            )?,
+            None => self.conn.execute("DELETE FROM map_hashes WHERE map = ?1", params![map])?,
+        };
+        Ok(())
+    }
+
+    /// Expected file hash of every map that has one, keyed by map name
+    fn get_hashes(&self) -> Result<HashMap<String, String>> {
+        let mut stmt = self.conn.prepare("SELECT map, sha256 FROM map_hashes")?;
+        let hashes = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashMap<_, _>>>()?;
+        Ok(hashes)
+    }
+
+    /// When each removed map was flagged, keyed by map name
+    fn get_removed(&self) -> Result<HashMap<String, i64>> {
+        let mut stmt = self.conn.prepare("SELECT map, removed_at FROM map_removed")?;
+        let removed = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashMap<_, _>>>()?;
+        Ok(removed)
+    }
+
     /// Mark a map as downloaded
     pub fn mark_downloaded(&self, map_id: i64, local_path: &str) -> Result<()> {
         self.conn.execute(
@@ -196,6 +370,110 @@ impl Database {
         Ok(())
     }
 
+    fn get_metadata(&self, key: &str) -> Result<Option<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT value FROM metadata WHERE key = ?1")?;
+        let mut rows = stmt.query(params![key])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(row.get(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Validators stored for `url`. Validators saved for a different manifest URL
+    /// are discarded, so switching sources always fetches the full manifest.
+    pub fn get_manifest_validators(&self, url: &str) -> Result<ManifestValidators> {
+        if self.get_metadata("manifest_url")?.as_deref() != Some(url) {
+            self.clear_manifest_validators()?;
+            return Ok(ManifestValidators::default());
+        }
+        Ok(ManifestValidators {
+            etag: self.get_metadata("manifest_etag")?,
+            last_modified: self.get_metadata("manifest_last_modified")?,
+        })
+    }
+
+    /// Remember the validators of a manifest that was just imported
+    pub fn set_manifest_validators(&self, url: &str, validators: &ManifestValidators) -> Result<()> {
+        self.clear_manifest_validators()?;
+        let entries = [
+            ("manifest_url", Some(url)),
+            ("manifest_etag", validators.etag.as_deref()),
+            ("manifest_last_modified", validators.last_modified.as_deref()),
+        ];
+        for (key, value) in entries {
+            if let Some(value) = value {
+                self.conn.execute(
+                    "INSERT INTO metadata (key, value) VALUES (?1, ?2)
+                     ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+                    params![key, value],
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn clear_manifest_validators(&self) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM metadata WHERE key IN ('manifest_url', 'manifest_etag', 'manifest_last_modified')",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Parsed .map metadata, if cached for this exact file (size and mtime match)
+    pub fn get_map_file_info(&self, name: &str, file_size: u64, modified: i64) -> Result<Option<MapFileInfo>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT info FROM map_file_info WHERE name = ?1 AND file_size = ?2 AND modified = ?3",
+        )?;
+        let mut rows = stmt.query(params![name, file_size as i64, modified])?;
+
+        if let Some(row) = rows.next()? {
+            let json: String = row.get(0)?;
+            Ok(serde_json::from_str(&json).ok())
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn set_map_file_info(&self, name: &str, modified: i64, info: &MapFileInfo) -> Result<()> {
+        let json = serde_json::to_string(info).unwrap_or_default();
+        self.conn.execute(
+            "INSERT INTO map_file_info (name, file_size, modified, info) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(name) DO UPDATE SET file_size = excluded.file_size,
+                 modified = excluded.modified, info = excluded.info",
+            params![name, info.file_size as i64, modified, json],
+        )?;
+        Ok(())
+    }
+
+    /// SHA-256 of a downloaded file, if cached for this exact file (size and mtime match)
+    pub fn get_file_hash(&self, name: &str, file_size: u64, modified: i64) -> Result<Option<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT sha256 FROM map_file_hash WHERE name = ?1 AND file_size = ?2 AND modified = ?3",
+        )?;
+        let mut rows = stmt.query(params![name, file_size as i64, modified])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(row.get(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn set_file_hash(&self, name: &str, file_size: u64, modified: i64, sha256: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO map_file_hash (name, file_size, modified, sha256) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(name) DO UPDATE SET file_size = excluded.file_size,
+                 modified = excluded.modified, sha256 = excluded.sha256",
+            params![name, file_size as i64, modified, sha256],
+        )?;
+        Ok(())
+    }
+
     /// Get map count
     pub fn map_count(&self) -> Result<usize> {
         let count: i64 = self
@@ -204,3 +482,82 @@ impl Database {
         Ok(count as usize)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_map(name: &str) -> ManifestMap {
+        serde_json::from_value(serde_json::json!({
+            "name": name, "category": "Easy", "stars": 1, "sha256": "ab12",
+            "files": [{"file": "rules.txt", "size": 1}],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn failed_import_commits_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("maps.db")).unwrap();
+        db.conn
+            .execute_batch(
+                "CREATE TRIGGER reject_bad BEFORE INSERT ON maps WHEN NEW.name = 'Bad'
+                 BEGIN SELECT RAISE(ABORT, 'rejected'); END;",
+            )
+            .unwrap();
+
+        let maps = [manifest_map("Good"), manifest_map("Bad"), manifest_map("After")];
+        assert!(db.in_transaction(|db| db.import_maps(&maps)).is_err());
+
+        assert_eq!(db.map_count().unwrap(), 0);
+        assert!(db.get_companions().unwrap().is_empty());
+        assert!(db.get_hashes().unwrap().is_empty());
+    }
+
+    #[test]
+    fn successful_import_commits_every_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("maps.db")).unwrap();
+        let maps = [manifest_map("One"), manifest_map("Two")];
+        assert_eq!(db.in_transaction(|db| db.import_maps(&maps)).unwrap(), 2);
+
+        assert_eq!(db.map_count().unwrap(), 2);
+        assert_eq!(db.get_companions().unwrap().len(), 2);
+        assert_eq!(db.get_hashes().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn purge_removes_every_row_of_stale_maps() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("maps.db")).unwrap();
+        let maps = [manifest_map("Gone"), manifest_map("Kept")];
+        db.import_maps(&maps).unwrap();
+        let info = MapFileInfo {
+            width: 1,
+            height: 1,
+            file_size: 1,
+            images: 0,
+            author: None,
+            version: None,
+            credits: None,
+            license: None,
+        };
+        for name in ["Gone", "Kept"] {
+            db.set_file_hash(name, 1, 1, "ab12").unwrap();
+            db.set_map_file_info(name, 1, &info).unwrap();
+        }
+        db.mark_removed(&maps[1..], 100).unwrap();
+
+        assert_eq!(db.purge_removed(200).unwrap(), 1);
+
+        let names: Vec<String> = db.get_all_maps().unwrap().into_iter().map(|m| m.name).collect();
+        assert_eq!(names, ["Kept"]);
+        assert!(!db.get_companions().unwrap().contains_key("Gone"));
+        assert!(!db.get_hashes().unwrap().contains_key("Gone"));
+        assert!(db.get_removed().unwrap().is_empty());
+        assert_eq!(db.get_file_hash("Gone", 1, 1).unwrap(), None);
+        assert!(db.get_map_file_info("Gone", 1, 1).unwrap().is_none());
+        assert_eq!(db.get_file_hash("Kept", 1, 1).unwrap().as_deref(), Some("ab12"));
+        assert!(db.get_map_file_info("Kept", 1, 1).unwrap().is_some());
+    }
+}
@@ -0,0 +1,54 @@
+//! Optional read-only JSON endpoint on localhost for stream overlays and other tools.
+//! `GET /map` returns the map being previewed (or the last selected one), `null` if none.
+
+use std::sync::{Arc, Mutex};
+use tiny_http::{Header, Method, Response, Server};
+use tracing::{debug, info};
+
+/// JSON of the current map, written by the UI thread and read by the server thread
+pub type SharedMap = Arc<Mutex<String>>;
+
+pub struct LocalServer {
+    server: Arc<Server>,
+    pub port: u16,
+}
+
+impl LocalServer {
+    /// Bind to 127.0.0.1:`port` and serve `current` on a background thread
+    pub fn start(port: u16, current: SharedMap) -> Result<Self, String> {
+        let server = Arc::new(Server::http(("127.0.0.1", port)).map_err(|e| e.to_string())?);
+        let worker = server.clone();
+        std::thread::spawn(move || {
+            // Ends when `unblock` is called on drop
+            for request in worker.incoming_requests() {
+                let path = request.url().split('?').next().unwrap_or_default().to_string();
+                let response = match (request.method(), path.as_str()) {
+                    (Method::Get, "/" | "/map") => {
+                        let body = current.lock().unwrap().clone();
+                        Response::from_string(body)
+                            .with_header(header("Content-Type", "application/json; charset=utf-8"))
+                    }
+                    _ => Response::from_string("Not found").with_status_code(404),
+                };
+                // Overlays usually run as browser sources on another origin
+                let response = response.with_header(header("Access-Control-Allow-Origin", "*"));
+                if let Err(e) = request.respond(response) {
+                    debug!(error = %e, "Local endpoint response failed");
+                }
+            }
+        });
+        info!(port, "Local map endpoint listening");
+        Ok(Self { server, port })
+    }
+}
+
+impl Drop for LocalServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        info!(port = self.port, "Local map endpoint stopped");
+    }
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("static header is valid")
+}
@@ -7,10 +7,16 @@ use mimalloc::MiMalloc;
 static GLOBAL: MiMalloc = MiMalloc;
 
 mod app;
+mod authors;
 mod constants;
 mod db;
+mod local_server;
+mod mapfile;
 mod settings;
+mod sound;
+mod taskbar;
 mod theme;
+mod tray;
 mod types;
 mod ui;
 mod utils;
@@ -23,11 +29,15 @@ use std::collections::HashSet;
 use std::path::PathBuf;
 use tracing::{debug, error, info, warn};
 use types::*;
-use ui::components::{format_release_date, render_stars};
-use utils::{format_bytes, get_cache_dir};
+use ui::components::{
+    ellipsized_galley, format_release_date, paint_map_placeholder, render_stars, star_label, text_overflows,
+    truncate_chars,
+    GridLayout,
+};
+use utils::{format_batch_size, format_bytes, get_cache_dir};
 
 /// Initialize file logging. Returns a guard that must be held for the app lifetime.
-fn init_logging(data_dir: &std::path::Path) -> tracing_appender::non_blocking::WorkerGuard {
+fn init_logging(data_dir: &std::path::Path, log_level: &str) -> tracing_appender::non_blocking::WorkerGuard {
     use tracing_subscriber::{fmt, EnvFilter, prelude::*};
 
     let logs_dir = data_dir.join("logs");
@@ -36,8 +46,15 @@ fn init_logging(data_dir: &std::path::Path) -> tracing_appender::non_blocking::W
     let file_appender = tracing_appender::rolling::daily(&logs_dir, "gores-map-downloader.log");
     let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
 
-    let env_filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("info,gores_map_downloader=debug"));
+    // RUST_LOG wins; otherwise use the level from Settings for our crate
+    let level = if settings::LOG_LEVELS.contains(&log_level) { log_level } else { "debug" };
+    let deps_level = match level {
+        "error" | "warn" => level,
+        _ => "info",
+    };
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::new(format!("{},gores_map_downloader={}", deps_level, level))
+    });
 
     tracing_subscriber::registry()
         .with(env_filter)
@@ -55,6 +72,22 @@ fn init_logging(data_dir: &std::path::Path) -> tracing_appender::non_blocking::W
     guard
 }
 
+/// Value of `--filter "<spec>"` (or `--filter=<spec>`) on the command line
+fn startup_filter_arg() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    let mut filter = None;
+    while let Some(arg) = args.next() {
+        if arg == "--filter" {
+            filter = args.next();
+        } else if let Some(spec) = arg.strip_prefix("--filter=") {
+            filter = Some(spec.to_string());
+        } else {
+            warn!(arg = %arg, "Unknown command-line argument");
+        }
+    }
+    filter
+}
+
 fn main() -> eframe::Result<()> {
     let data_dir = dirs::data_local_dir()
         .unwrap_or_else(|| PathBuf::from("."))
@@ -62,10 +95,24 @@ fn main() -> eframe::Result<()> {
 
     std::fs::create_dir_all(&data_dir).ok();
 
+    // Settings are loaded first so the log level can be applied
+    let (settings, settings_source) = settings::Settings::load(&data_dir);
+
     // Initialize logging - guard must live for entire app lifetime
-    let _log_guard = init_logging(&data_dir);
+    let _log_guard = init_logging(&data_dir, &settings.log_level);
 
     info!(version = APP_VERSION, "Gores Map Downloader starting");
+    let startup_filter = startup_filter_arg();
+    match &settings_source {
+        settings::SettingsSource::Backup(Some(error)) => {
+            warn!(error = %error, "Settings file unreadable, restored from backup")
+        }
+        settings::SettingsSource::Backup(None) => warn!("Settings file missing, restored from backup"),
+        settings::SettingsSource::DefaultsAfterError(error) => {
+            warn!(error = %error, "Settings file and backup unreadable, using defaults")
+        }
+        _ => {}
+    }
 
     let db_path = data_dir.join("maps.db");
     let db = match Database::open(&db_path) {
@@ -82,17 +129,24 @@ fn main() -> eframe::Result<()> {
     // Load initial data if database is empty
     if db.map_count().unwrap_or(0) == 0 {
         info!("Database empty, fetching initial manifest");
-        if let Ok(response) = reqwest::blocking::get(MANIFEST_URL) {
-            if let Ok(manifest) = response.json::<Manifest>() {
-                let imported = db.import_maps(&manifest.maps).unwrap_or(0);
-                db.set_db_version(&manifest.version).ok();
-                info!(count = imported, "Imported maps from manifest");
+        if let Ok(bytes) = reqwest::blocking::get(MANIFEST_URL).and_then(|r| r.bytes()) {
+            match Manifest::parse(&bytes) {
+                Ok(manifest) => {
+                    match db.in_transaction(|db| db.import_maps(&manifest.maps)) {
+                        Ok(imported) => {
+                            db.set_db_version(&manifest.version).ok();
+                            info!(count = imported, "Imported maps from manifest");
+                        }
+                        Err(e) => error!(error = %e, "Initial manifest import failed"),
+                    }
+                }
+                // The update check reports this to the user once the window is up
+                Err(e) => error!(error = %e, "Initial manifest unusable"),
             }
         }
     }
 
     // Load saved window position/size
-    let settings = settings::Settings::load(&data_dir);
     let win_pos = match (settings.window_x, settings.window_y) {
         (Some(x), Some(y)) => Some(egui::pos2(x, y)),
         _ => None,
@@ -131,7 +185,7 @@ fn main() -> eframe::Result<()> {
         "Gores Map Downloader",
         options,
         Box::new(move |cc| {
-            let mut app = App::new(cc, db, settings, data_dir);
+            let mut app = App::new(cc, db, settings, settings_source, data_dir, startup_filter);
             app.needs_center = needs_center;
             Ok(Box::new(app))
         }),
@@ -145,47 +199,81 @@ fn main() -> eframe::Result<()> {
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
 
-        // Track window position/size for saving on exit
+        if self.window_restore_pending {
+            self.window_restore_pending = false;
+            self.restore_window_placement(ctx);
+        }
+
+        // Track window position/size for saving on exit. The floating geometry is
+        // left alone while maximized so un-maximizing after a restart restores it.
         ctx.input(|i| {
-            if let Some(rect) = i.viewport().outer_rect {
+            let viewport = i.viewport();
+            self.window_maximized = viewport.maximized.unwrap_or(false);
+            if let Some(monitor) = viewport.monitor_size {
+                self.window_monitor = Some(monitor);
+            }
+            if self.window_maximized || viewport.fullscreen.unwrap_or(false) {
+                return;
+            }
+            if let Some(rect) = viewport.outer_rect {
                 self.window_pos = Some(rect.min);
             }
-            if let Some(rect) = i.viewport().inner_rect {
+            if let Some(rect) = viewport.inner_rect {
                 self.window_size = Some(rect.size());
             }
         });
 
-        // Global keyboard capture: type anywhere to search (when no modal open)
-        if !self.show_settings && !self.show_download_modal && !ctx.wants_keyboard_input() {
+        // Global keyboard capture: type anywhere to search (when no modal open).
+        // Skipped while label text is selected (preview details) so copying isn't disturbed.
+        // "/" and Ctrl+F focus the search box and select the query.
+        let text_selected = egui::text_selection::LabelSelectionState::load(ctx).has_selection();
+        if !self.show_settings && !self.show_download_modal && !self.show_shortcuts && self.onboarding.is_none() && self.paste_list.is_none() && self.author_popup.is_none() && !ctx.wants_keyboard_input() && !text_selected {
             let mut typed_text = String::new();
             let mut backspace = false;
+            let mut focus_shortcut = ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::F));
             ctx.input(|i| {
+                // Text produced with Ctrl/Alt held (AltGr, shortcuts on some layouts) isn't typing
+                let shortcut_held = i.modifiers.ctrl || i.modifiers.alt || i.modifiers.mac_cmd;
                 for event in &i.events {
                     if let egui::Event::Text(text) = event {
-                        if !text.is_empty() && text.chars().all(|c| !c.is_control()) {
+                        if text == "/" {
+                            focus_shortcut = true;
+                        } else if text == "?" {
+                            // Opens the cheat sheet (handle_global_shortcuts)
+                        } else if self.type_to_search
+                            && !shortcut_held
+                            && !text.is_empty()
+                            && text.chars().all(|c| !c.is_control())
+                        {
                             typed_text.push_str(text);
                         }
                     }
-                    if let egui::Event::Key { key: egui::Key::Backspace, pressed: true, .. } = event {
-                        backspace = true;
+                    if let egui::Event::Key { key: egui::Key::Backspace, pressed: true, modifiers, .. } = event {
+                        backspace |= self.type_to_search && !(modifiers.ctrl || modifiers.alt || modifiers.mac_cmd);
                     }
                 }
             });
-            if !typed_text.is_empty() {
+            if focus_shortcut {
+                self.focus_search = true;
+                self.focus_search_select_all = true;
+            } else if !typed_text.is_empty() {
                 self.search_query.push_str(&typed_text);
                 self.focus_search = true;
             }
             if backspace && !self.search_query.is_empty() {
                 self.search_query.pop();
                 self.focus_search = true;
-                self.apply_filters();
+                self.search_changed(ctx);
             }
         }
 
         // Start thumbnail prefetch on first frame
+        // (held back during first-run setup, which asks about it)
         if !self.prefetch_started {
             self.prefetch_started = true;
-            self.start_thumbnail_prefetch(ctx);
+            if self.onboarding.is_none() {
+                self.start_thumbnail_prefetch(ctx);
+            }
             self.check_for_updates(ctx);
         }
 
@@ -199,12 +287,29 @@ impl eframe::App for App {
 
         // Check for update results from background threads
         self.poll_update_results(ctx);
+        self.poll_download_completion();
+        self.poll_url_precheck(ctx);
+        self.poll_next_batch(ctx);
+        self.poll_tray(ctx);
+        self.poll_download_path(ctx);
+        self.poll_downloaded_scan(ctx);
+        self.poll_cache_repair(ctx);
+        self.poll_outdated_check(ctx);
+        self.poll_thumbnail_prefetch(ctx);
+        self.upload_thumbnails(ctx);
+        self.poll_search_scores(ctx);
+        self.update_local_server_map();
+        self.handle_close_request(ctx);
+        self.handle_global_shortcuts(ctx);
 
         // Render update dialogs
         self.render_update_dialogs(ctx);
 
         // Render download modal
         self.render_download_modal(ctx);
+        self.render_exit_confirm(ctx);
+        self.render_precheck_dialog(ctx);
+        self.render_download_confirm_dialog(ctx);
 
         // Left sidebar - filters (must be added BEFORE CentralPanel)
         egui::SidePanel::left("filter_panel")
@@ -228,17 +333,22 @@ impl eframe::App for App {
 
                 ui.add_space(21.0);
                 ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
-                    let texture = self.logo_texture.get_or_insert_with(|| {
-                        let (pixels, w, h) = utils::rasterize_logo(avail_w as u32 * 2);
-                        ctx.load_texture(
+                    // Rasterized at the display's pixel size, again when the scale factor
+                    // changes (e.g. the window moves to another monitor)
+                    let logo_w = avail_w * 0.5;
+                    let pixel_w = (logo_w * ctx.pixels_per_point()).round().max(1.0) as u32;
+                    if self.logo_texture.as_ref().is_none_or(|(w, _)| *w != pixel_w) {
+                        let (pixels, w, h) = utils::rasterize_logo(pixel_w);
+                        let texture = ctx.load_texture(
                             "logo",
                             egui::ColorImage::from_rgba_unmultiplied([w as usize, h as usize], &pixels),
                             egui::TextureOptions::LINEAR,
-                        )
-                    });
+                        );
+                        self.logo_texture = Some((pixel_w, texture));
+                    }
+                    let Some((_, texture)) = &self.logo_texture else { return };
 
                     let aspect = texture.size()[1] as f32 / texture.size()[0] as f32;
-                    let logo_w = avail_w * 0.5;
                     let logo_size = egui::vec2(logo_w, logo_w * aspect);
                     ui.image(egui::load::SizedTexture::new(texture.id(), logo_size));
 
@@ -274,20 +384,52 @@ impl eframe::App for App {
                                     .id(search_id)
                                     .hint_text("Search map / author...")
                                     .frame(false)
-                                    .desired_width(ui.available_width()),
+                                    .desired_width(ui.available_width() - 52.0), // room for the match toggles
+                            )
+                            .on_hover_text(SEARCH_SYNTAX_HELP);
+                            let mut toggled = theme::search_toggle(
+                                ui,
+                                "Aa",
+                                self.search_case_sensitive,
+                                "Match case",
+                                "Match case: \"Kobra\" no longer finds \"kobra\"",
                             );
+                            if toggled {
+                                self.search_case_sensitive = !self.search_case_sensitive;
+                            }
+                            if theme::search_toggle(
+                                ui,
+                                "ab|",
+                                self.search_whole_word,
+                                "Whole words",
+                                "Whole words: \"Im\" matches the author Im, not Imagine",
+                            ) {
+                                self.search_whole_word = !self.search_whole_word;
+                                toggled = true;
+                            }
+                            if toggled {
+                                self.save_settings();
+                                self.apply_filters();
+                            }
                             if self.focus_search {
                                 self.focus_search = false;
                                 search_response.request_focus();
+                                let select_all = std::mem::take(&mut self.focus_search_select_all);
                                 if let Some(mut state) = egui::TextEdit::load_state(ui.ctx(), search_id) {
-                                    let ccursor = egui::text::CCursor::new(self.search_query.len());
-                                    state.cursor.set_char_range(Some(egui::text::CCursorRange::one(ccursor)));
+                                    // Cursors count chars, not bytes
+                                    let end = egui::text::CCursor::new(self.search_query.chars().count());
+                                    let range = if select_all {
+                                        egui::text::CCursorRange::two(egui::text::CCursor::new(0), end)
+                                    } else {
+                                        egui::text::CCursorRange::one(end)
+                                    };
+                                    state.cursor.set_char_range(Some(range));
                                     state.store(ui.ctx(), search_id);
                                 }
-                                self.apply_filters();
+                                self.search_changed(ui.ctx());
                             }
                             if search_response.changed() {
-                                self.apply_filters();
+                                self.search_changed(ui.ctx());
                             }
                             if search_response.has_focus() {
                                 self.map_list_focused = false;
@@ -299,7 +441,7 @@ impl eframe::App for App {
                     let frame_rect = search_frame_resp.response.rect;
                     let btn_size = 16.0;
                     let btn_rect = egui::Rect::from_center_size(
-                        egui::pos2(frame_rect.right() - 14.0, frame_rect.center().y),
+                        egui::pos2(frame_rect.right() - 66.0, frame_rect.center().y), // left of the match toggles
                         egui::vec2(btn_size, btn_size),
                     );
                     let clear_resp = ui.interact(btn_rect, ui.id().with("search_clear"), egui::Sense::click());
@@ -316,14 +458,72 @@ impl eframe::App for App {
                     );
                     if clear_resp.clicked() {
                         self.search_query.clear();
-                        self.apply_filters();
+                        self.search_changed(ui.ctx());
                     }
                 }
 
-                ui.add_space(12.0);
+                if self.search_query.trim().is_empty() {
+                    ui.add_space(12.0);
+                } else {
+                    ui.add_space(4.0);
+                    self.render_search_count(ui);
+                    ui.add_space(6.0);
+                }
+
+                // Filters header - click to collapse/expand the filter sections
+                let (header_rect, header_resp) = ui.allocate_exact_size(
+                    egui::vec2(ui.available_width(), 18.0),
+                    egui::Sense::click(),
+                );
+                let header_color = if header_resp.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                    theme::TEXT_MUTED
+                } else {
+                    theme::TEXT_DIM
+                };
+                ui.painter().text(
+                    header_rect.left_center() + egui::vec2(4.0, 0.0),
+                    egui::Align2::LEFT_CENTER,
+                    "FILTERS",
+                    egui::FontId::proportional(11.0),
+                    header_color,
+                );
+                ui.painter().text(
+                    header_rect.right_center() - egui::vec2(4.0, 0.0),
+                    egui::Align2::RIGHT_CENTER,
+                    if self.show_filters {
+                        egui_phosphor::regular::CARET_DOWN
+                    } else {
+                        egui_phosphor::regular::CARET_RIGHT
+                    },
+                    egui::FontId::proportional(12.0),
+                    header_color,
+                );
+                theme::focus_ring(ui, &header_resp, header_rect, 4.0);
+                theme::toggle_info(&header_resp, "Show filters", self.show_filters);
+                let header_resp = header_resp.on_hover_text(if self.show_filters {
+                    "Hide filters"
+                } else {
+                    "Show filters"
+                });
+                if header_resp.clicked() {
+                    self.show_filters = !self.show_filters;
+                    self.save_settings();
+                }
+                ui.add_space(4.0);
+
+                // Thumbnail prefetch progress: (done, queued, paused)
+                let prefetch = self
+                    .thumbnail_prefetch_running()
+                    .then(|| {
+                        let p = self.prefetch_progress.lock().unwrap();
+                        (p.fetched + p.failed, p.queued, p.paused)
+                    })
+                    .filter(|&(_, queued, _)| queued > 0);
+                let prefetch_height = if prefetch.is_some() { 24.0 } else { 0.0 };
 
                 // Calculate space for bottom buttons (with padding above)
-                let bottom_height = 28.0 + 32.0 + 4.0 + 6.0 + 36.0 + 6.0 + 14.0 + 8.0;
+                let bottom_height = 28.0 + 32.0 + 4.0 + 6.0 + 36.0 + 6.0 + 14.0 + 8.0 + prefetch_height;
                 let padding_above_buttons = 16.0;
                 let available_for_filters =
                     ui.available_height() - bottom_height - padding_above_buttons;
@@ -402,7 +602,7 @@ impl eframe::App for App {
                                     );
                                     let (rect, response) = ui.allocate_exact_size(
                                         slider_rect.size(),
-                                        egui::Sense::click_and_drag(),
+                                        egui::Sense::CLICK | egui::Sense::DRAG, // handles take keyboard focus
                                     );
                                     if response.hovered() || response.dragged() {
                                         ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
@@ -432,44 +632,55 @@ impl eframe::App for App {
                                     // Draw active range
                                     painter.line_segment(
                                         [egui::pos2(min_x, track_y), egui::pos2(max_x, track_y)],
-                                        egui::Stroke::new(4.0, theme::SLIDER_TRAIL),
+                                        egui::Stroke::new(4.0, theme::slider_trail()),
                                     );
 
                                     // Draw handles
                                     painter.circle_filled(
                                         egui::pos2(min_x, track_y),
                                         8.0,
-                                        theme::SLIDER_HEAD,
+                                        theme::slider_head(),
                                     );
                                     painter.circle_filled(
                                         egui::pos2(max_x, track_y),
                                         8.0,
-                                        theme::SLIDER_HEAD,
+                                        theme::slider_head(),
                                     );
 
-                                    // Handle dragging
-                                    if response.dragged() {
-                                        if let Some(pos) = response.interact_pointer_pos() {
-                                            let rel_x = ((pos.x - track_left) / track_width)
-                                                .clamp(0.0, 1.0);
-                                            let val = (rel_x * 4.0).round() as u8;
-
-                                            // Determine which handle to move
-                                            let dist_min = (pos.x - min_x).abs();
-                                            let dist_max = (pos.x - max_x).abs();
+                                    // Keyboard: each handle is its own Tab stop
+                                    let (lo, hi) = self.category_range;
+                                    if let Some(v) = theme::range_handle(
+                                        ui,
+                                        "category_min",
+                                        egui::pos2(min_x, track_y),
+                                        lo as usize,
+                                        0..=hi as usize,
+                                        "Minimum difficulty",
+                                        DIFF_NAMES[lo as usize],
+                                    ) {
+                                        self.category_range.0 = v as u8;
+                                        filters_changed = true;
+                                    }
+                                    if let Some(v) = theme::range_handle(
+                                        ui,
+                                        "category_max",
+                                        egui::pos2(max_x, track_y),
+                                        hi as usize,
+                                        lo as usize..=4,
+                                        "Maximum difficulty",
+                                        DIFF_NAMES[hi as usize],
+                                    ) {
+                                        self.category_range.1 = v as u8;
+                                        filters_changed = true;
+                                    }
 
-                                            if dist_min < dist_max {
-                                                if val <= self.category_range.1 {
-                                                    self.category_range.0 = val;
-                                                    filters_changed = true;
-                                                }
-                                            } else {
-                                                if val >= self.category_range.0 {
-                                                    self.category_range.1 = val;
-                                                    filters_changed = true;
-                                                }
-                                            }
-                                        }
+                                    // Pointer dragging moves the handle the drag started on
+                                    let (lo, hi) = self.category_range;
+                                    if let Some((lo, hi)) =
+                                        theme::range_drag(ui, &response, track_left, track_width, 4, lo as usize, hi as usize)
+                                    {
+                                        self.category_range = (lo as u8, hi as u8);
+                                        filters_changed = true;
                                     }
                                 } else {
                                     // Individual selection - 3 rows (3/3/2 layout)
@@ -477,7 +688,7 @@ impl eframe::App for App {
                                         "Easy", "Main", "Hard", "Insane", "Extreme", "Solo", "Mod",
                                         "Extra",
                                     ];
-                                    let selected_fill = theme::TOGGLE_SELECTED;
+                                    let selected_fill = theme::toggle_selected();
                                     let unselected_fill = theme::TOGGLE_UNSELECTED;
                                     let btn_width_3 = ((ui.available_width() - 8.0) / 3.0).floor();
                                     let btn_width_2 = ((ui.available_width() - 4.0) / 2.0).floor();
@@ -510,7 +721,10 @@ impl eframe::App for App {
                                                     egui::FontId::proportional(11.0),
                                                     egui::Color32::WHITE,
                                                 );
+                                                theme::filter_count(ui, draw_rect, self.filter_counts.categories[i]);
                                             }
+                                            theme::focus_ring(ui, &response, rect, 4.0);
+                                            theme::toggle_info(&response, names[i], self.filter_categories[i]);
                                             if response.clicked() {
                                                 self.filter_categories[i] =
                                                     !self.filter_categories[i];
@@ -547,7 +761,10 @@ impl eframe::App for App {
                                                     egui::FontId::proportional(11.0),
                                                     egui::Color32::WHITE,
                                                 );
+                                                theme::filter_count(ui, draw_rect, self.filter_counts.categories[i]);
                                             }
+                                            theme::focus_ring(ui, &response, rect, 4.0);
+                                            theme::toggle_info(&response, names[i], self.filter_categories[i]);
                                             if response.clicked() {
                                                 self.filter_categories[i] =
                                                     !self.filter_categories[i];
@@ -584,7 +801,10 @@ impl eframe::App for App {
                                                     egui::FontId::proportional(11.0),
                                                     egui::Color32::WHITE,
                                                 );
+                                                theme::filter_count(ui, draw_rect, self.filter_counts.categories[i]);
                                             }
+                                            theme::focus_ring(ui, &response, rect, 4.0);
+                                            theme::toggle_info(&response, names[i], self.filter_categories[i]);
                                             if response.clicked() {
                                                 self.filter_categories[i] =
                                                     !self.filter_categories[i];
@@ -593,6 +813,69 @@ impl eframe::App for App {
                                         }
                                     });
                                 }
+
+                                ui.add_space(6.0);
+                                if theme::settings_checkbox(
+                                    ui,
+                                    self.per_category_stars,
+                                    "Per-category stars",
+                                    true,
+                                ) {
+                                    self.per_category_stars = !self.per_category_stars;
+                                    filters_changed = true;
+                                }
+                                if self.per_category_stars {
+                                    // One star range per enabled category
+                                    for (i, &(_, max_stars)) in DEFAULT_CATEGORY_STARS.iter().enumerate() {
+                                        let enabled = if self.category_mode_range {
+                                            (self.category_range.0..=self.category_range.1)
+                                                .contains(&(i as u8))
+                                        } else {
+                                            self.filter_categories[i]
+                                        };
+                                        if !enabled {
+                                            continue;
+                                        }
+                                        let (mut lo, mut hi) = self.category_stars[i];
+                                        ui.horizontal(|ui| {
+                                            ui.add(
+                                                egui::Label::new(
+                                                    egui::RichText::new(Self::CATEGORY_NAMES[i])
+                                                        .size(12.0)
+                                                        .color(theme::TEXT_SECONDARY),
+                                                )
+                                                .selectable(false),
+                                            );
+                                            ui.with_layout(
+                                                egui::Layout::right_to_left(egui::Align::Center),
+                                                |ui| {
+                                                    let hi_resp = ui.add(
+                                                        egui::DragValue::new(&mut hi)
+                                                            .range(lo..=max_stars)
+                                                            .suffix("★"),
+                                                    );
+                                                    ui.add(
+                                                        egui::Label::new(
+                                                            egui::RichText::new("to")
+                                                                .size(12.0)
+                                                                .color(theme::TEXT_DIM),
+                                                        )
+                                                        .selectable(false),
+                                                    );
+                                                    let lo_resp = ui.add(
+                                                        egui::DragValue::new(&mut lo)
+                                                            .range(1..=hi)
+                                                            .suffix("★"),
+                                                    );
+                                                    if hi_resp.changed() || lo_resp.changed() {
+                                                        self.category_stars[i] = (lo, hi);
+                                                        filters_changed = true;
+                                                    }
+                                                },
+                                            );
+                                        });
+                                    }
+                                }
                             });
 
                             ui.add_space(4.0);
@@ -619,6 +902,10 @@ impl eframe::App for App {
                             }
 
                             theme::section_frame().show(ui, |ui| {
+                                if self.per_category_stars {
+                                    // Overridden by the ranges in the CATEGORY section
+                                    ui.disable();
+                                }
                                 ui.horizontal(|ui| {
                                     ui.add(
                                         egui::Label::new(
@@ -676,7 +963,7 @@ impl eframe::App for App {
                                     // Dual-handle range slider
                                     let (rect, response) = ui.allocate_exact_size(
                                         egui::vec2(ui.available_width(), 20.0),
-                                        egui::Sense::click_and_drag(),
+                                        egui::Sense::CLICK | egui::Sense::DRAG, // handles take keyboard focus
                                     );
                                     if response.hovered() || response.dragged() {
                                         ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
@@ -704,46 +991,63 @@ impl eframe::App for App {
 
                                     painter.line_segment(
                                         [egui::pos2(min_x, track_y), egui::pos2(max_x, track_y)],
-                                        egui::Stroke::new(4.0, theme::SLIDER_TRAIL),
+                                        egui::Stroke::new(4.0, theme::slider_trail()),
                                     );
 
                                     painter.circle_filled(
                                         egui::pos2(min_x, track_y),
                                         8.0,
-                                        theme::SLIDER_HEAD,
+                                        theme::slider_head(),
                                     );
                                     painter.circle_filled(
                                         egui::pos2(max_x, track_y),
                                         8.0,
-                                        theme::SLIDER_HEAD,
+                                        theme::slider_head(),
                                     );
 
-                                    if response.dragged() {
-                                        if let Some(pos) = response.interact_pointer_pos() {
-                                            let rel_x = ((pos.x - track_left) / track_width)
-                                                .clamp(0.0, 1.0);
-                                            let val =
-                                                ((rel_x * steps).round() as u8 + 1).min(max_stars);
-
-                                            let dist_min = (pos.x - min_x).abs();
-                                            let dist_max = (pos.x - max_x).abs();
+                                    let (lo, hi) = self.stars_range;
+                                    if let Some(v) = theme::range_handle(
+                                        ui,
+                                        "stars_min",
+                                        egui::pos2(min_x, track_y),
+                                        lo as usize,
+                                        1..=hi as usize,
+                                        "Minimum stars",
+                                        &format!("{} stars", lo),
+                                    ) {
+                                        self.stars_range.0 = v as u8;
+                                        filters_changed = true;
+                                    }
+                                    if let Some(v) = theme::range_handle(
+                                        ui,
+                                        "stars_max",
+                                        egui::pos2(max_x, track_y),
+                                        hi as usize,
+                                        lo as usize..=max_stars as usize,
+                                        "Maximum stars",
+                                        &format!("{} stars", hi),
+                                    ) {
+                                        self.stars_range.1 = v as u8;
+                                        filters_changed = true;
+                                    }
 
-                                            if dist_min < dist_max {
-                                                if val <= self.stars_range.1 {
-                                                    self.stars_range.0 = val;
-                                                    filters_changed = true;
-                                                }
-                                            } else {
-                                                if val >= self.stars_range.0 {
-                                                    self.stars_range.1 = val;
-                                                    filters_changed = true;
-                                                }
-                                            }
-                                        }
+                                    // Steps count from 1★
+                                    let (lo, hi) = self.stars_range;
+                                    if let Some((lo, hi)) = theme::range_drag(
+                                        ui,
+                                        &response,
+                                        track_left,
+                                        track_width,
+                                        steps as usize,
+                                        (lo as usize).saturating_sub(1),
+                                        (hi as usize).saturating_sub(1),
+                                    ) {
+                                        self.stars_range = (lo as u8 + 1, hi as u8 + 1);
+                                        filters_changed = true;
                                     }
                                 } else {
                                     // Individual selection - 5 buttons in a row
-                                    let selected_fill = theme::TOGGLE_SELECTED;
+                                    let selected_fill = theme::toggle_selected();
                                     let unselected_fill = theme::TOGGLE_UNSELECTED;
                                     let disabled_fill = egui::Color32::from_rgb(0x1a, 0x1a, 0x1a);
                                     let btn_width = ((ui.available_width() - 16.0) / 5.0).floor();
@@ -781,7 +1085,19 @@ impl eframe::App for App {
                                                     egui::FontId::proportional(11.0),
                                                     egui::Color32::WHITE,
                                                 );
+                                                if enabled {
+                                                    theme::filter_count(ui, draw_rect, self.filter_counts.stars[i]);
+                                                }
                                             }
+                                            theme::focus_ring(ui, &response, rect, 4.0);
+                                            response.widget_info(|| {
+                                                egui::WidgetInfo::selected(
+                                                    egui::WidgetType::SelectableLabel,
+                                                    enabled,
+                                                    self.filter_stars[i],
+                                                    format!("{} stars", i + 1),
+                                                )
+                                            });
                                             if enabled && response.clicked() {
                                                 self.filter_stars[i] = !self.filter_stars[i];
                                                 filters_changed = true;
@@ -824,14 +1140,17 @@ impl eframe::App for App {
 
                                 let years = &self.available_years;
 
-                                if years.len() >= 2 {
-                                    let min_year = *years.first().unwrap();
-                                    let max_year = *years.last().unwrap();
+                                if self.year_mode_range {
+                                    // The slider steps through the years or the months present in the data
+                                    let by_month = self.year_by_month;
+                                    let domain = if by_month { &self.available_months } else { years };
+                                    let label = |v: i32| if by_month { utils::format_month(v) } else { v.to_string() };
+                                    let current = if by_month { self.month_range } else { self.year_range };
+                                    let mut new_range = None;
 
-                                    if self.year_mode_range {
-                                        // Range mode - 2-handle slider
+                                    if domain.len() >= 2 {
                                         let (cur_min, cur_max) =
-                                            self.year_range.unwrap_or((min_year, max_year));
+                                            current.unwrap_or((domain[0], domain[domain.len() - 1]));
 
                                         // Labels row
                                         let (row_rect, _) = ui.allocate_exact_size(
@@ -842,7 +1161,7 @@ impl eframe::App for App {
                                         painter.text(
                                             row_rect.left_center(),
                                             egui::Align2::LEFT_CENTER,
-                                            cur_min.to_string(),
+                                            label(cur_min),
                                             egui::FontId::proportional(12.0),
                                             egui::Color32::WHITE,
                                         );
@@ -856,7 +1175,7 @@ impl eframe::App for App {
                                         painter.text(
                                             row_rect.right_center(),
                                             egui::Align2::RIGHT_CENTER,
-                                            cur_max.to_string(),
+                                            label(cur_max),
                                             egui::FontId::proportional(12.0),
                                             egui::Color32::WHITE,
                                         );
@@ -864,7 +1183,7 @@ impl eframe::App for App {
                                         // Dual-handle range slider
                                         let (rect, response) = ui.allocate_exact_size(
                                             egui::vec2(ui.available_width(), 20.0),
-                                            egui::Sense::click_and_drag(),
+                                            egui::Sense::CLICK | egui::Sense::DRAG, // handles take keyboard focus
                                         );
                                         if response.hovered() || response.dragged() {
                                             ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
@@ -874,7 +1193,7 @@ impl eframe::App for App {
                                         let track_left = rect.left() + 8.0;
                                         let track_right = rect.right() - 8.0;
                                         let track_width = track_right - track_left;
-                                        let steps = (years.len() - 1) as f32;
+                                        let steps = (domain.len() - 1) as f32;
 
                                         let painter = ui.painter();
                                         painter.line_segment(
@@ -885,151 +1204,212 @@ impl eframe::App for App {
                                             egui::Stroke::new(4.0, theme::BORDER_SUBTLE),
                                         );
 
-                                        // Find indices of cur_min and cur_max in available_years
+                                        // Find indices of cur_min and cur_max in the domain
                                         let min_idx =
-                                            years.iter().position(|&y| y >= cur_min).unwrap_or(0);
-                                        let max_idx = years
+                                            domain.iter().position(|&v| v >= cur_min).unwrap_or(0);
+                                        let max_idx = domain
                                             .iter()
-                                            .rposition(|&y| y <= cur_max)
-                                            .unwrap_or(years.len() - 1);
+                                            .rposition(|&v| v <= cur_max)
+                                            .unwrap_or(domain.len() - 1);
 
-                                        let min_x = if steps > 0.0 {
-                                            track_left + (min_idx as f32 / steps) * track_width
-                                        } else {
-                                            track_left
-                                        };
-                                        let max_x = if steps > 0.0 {
-                                            track_left + (max_idx as f32 / steps) * track_width
-                                        } else {
-                                            track_right
-                                        };
+                                        let min_x = track_left + (min_idx as f32 / steps) * track_width;
+                                        let max_x = track_left + (max_idx as f32 / steps) * track_width;
 
                                         painter.line_segment(
                                             [
                                                 egui::pos2(min_x, track_y),
                                                 egui::pos2(max_x, track_y),
                                             ],
-                                            egui::Stroke::new(4.0, theme::SLIDER_TRAIL),
+                                            egui::Stroke::new(4.0, theme::slider_trail()),
                                         );
 
                                         painter.circle_filled(
                                             egui::pos2(min_x, track_y),
                                             8.0,
-                                            theme::SLIDER_HEAD,
+                                            theme::slider_head(),
                                         );
                                         painter.circle_filled(
                                             egui::pos2(max_x, track_y),
                                             8.0,
-                                            theme::SLIDER_HEAD,
+                                            theme::slider_head(),
                                         );
 
-                                        if response.dragged() && years.len() > 1 {
-                                            if let Some(pos) = response.interact_pointer_pos() {
-                                                let rel_x = ((pos.x - track_left) / track_width)
-                                                    .clamp(0.0, 1.0);
-                                                // Snap to nearest valid year in available_years
-                                                let idx = (rel_x * (years.len() - 1) as f32).round()
-                                                    as usize;
-                                                let val = years[idx.min(years.len() - 1)];
+                                        if let Some(v) = theme::range_handle(
+                                            ui,
+                                            "year_min",
+                                            egui::pos2(min_x, track_y),
+                                            min_idx,
+                                            0..=max_idx,
+                                            if by_month { "Earliest month" } else { "Earliest year" },
+                                            &label(cur_min),
+                                        ) {
+                                            new_range = Some((domain[v], cur_max));
+                                        }
+                                        if let Some(v) = theme::range_handle(
+                                            ui,
+                                            "year_max",
+                                            egui::pos2(max_x, track_y),
+                                            max_idx,
+                                            min_idx..=domain.len() - 1,
+                                            if by_month { "Latest month" } else { "Latest year" },
+                                            &label(cur_max),
+                                        ) {
+                                            new_range = Some((cur_min, domain[v]));
+                                        }
+
+                                        // Steps are indices into the domain
+                                        if let Some((lo, hi)) = theme::range_drag(
+                                            ui,
+                                            &response,
+                                            track_left,
+                                            track_width,
+                                            domain.len() - 1,
+                                            min_idx,
+                                            max_idx,
+                                        ) {
+                                            new_range = Some((domain[lo], domain[hi]));
+                                        }
+                                    }
+
+                                    if let Some(range) = new_range {
+                                        if by_month {
+                                            self.month_range = Some(range);
+                                        } else {
+                                            self.year_range = Some(range);
+                                        }
+                                        filters_changed = true;
+                                    }
 
-                                                let dist_min = (pos.x - min_x).abs();
-                                                let dist_max = (pos.x - max_x).abs();
+                                    ui.add_space(6.0);
+                                    if theme::settings_checkbox(ui, self.year_by_month, "By month", true) {
+                                        self.year_by_month = !self.year_by_month;
+                                        filters_changed = true;
+                                    }
+                                } else if years.len() >= 2 {
+                                    // Individual mode - grid of year buttons
+                                    let cols = 4;
+                                    let spacing = 4.0;
+                                    let btn_width = (ui.available_width()
+                                        - spacing * (cols as f32 - 1.0))
+                                        / cols as f32;
+                                    let btn_height = 26.0;
+
+                                    let selected_fill = theme::toggle_selected();
+                                    let unselected_fill = theme::TOGGLE_UNSELECTED;
 
-                                                let (new_min, new_max) = if dist_min < dist_max {
-                                                    (val.min(cur_max), cur_max)
+                                    let years_clone = years.clone();
+                                    for row in years_clone.chunks(cols) {
+                                        ui.horizontal(|ui| {
+                                            ui.spacing_mut().item_spacing.x = spacing;
+                                            for &year in row {
+                                                let selected =
+                                                    self.filter_years.contains(&year);
+                                                let fill = if selected {
+                                                    selected_fill
                                                 } else {
-                                                    (cur_min, val.max(cur_min))
+                                                    unselected_fill
                                                 };
-
-                                                if new_min != cur_min || new_max != cur_max {
-                                                    self.year_range = Some((new_min, new_max));
-                                                    filters_changed = true;
+                                                let (rect, response) = ui.allocate_exact_size(
+                                                    egui::vec2(btn_width, btn_height),
+                                                    egui::Sense::click(),
+                                                );
+                                                if response.hovered() {
+                                                    ui.ctx().set_cursor_icon(
+                                                        egui::CursorIcon::PointingHand,
+                                                    );
                                                 }
-                                            }
-                                        }
-                                    } else {
-                                        // Individual mode - grid of year buttons
-                                        let cols = 4;
-                                        let spacing = 4.0;
-                                        let btn_width = (ui.available_width()
-                                            - spacing * (cols as f32 - 1.0))
-                                            / cols as f32;
-                                        let btn_height = 26.0;
-
-                                        let selected_fill = theme::TOGGLE_SELECTED;
-                                        let unselected_fill = theme::TOGGLE_UNSELECTED;
-
-                                        let years_clone = years.clone();
-                                        for row in years_clone.chunks(cols) {
-                                            ui.horizontal(|ui| {
-                                                ui.spacing_mut().item_spacing.x = spacing;
-                                                for &year in row {
-                                                    let selected =
-                                                        self.filter_years.contains(&year);
-                                                    let fill = if selected {
-                                                        selected_fill
-                                                    } else {
-                                                        unselected_fill
-                                                    };
-                                                    let (rect, response) = ui.allocate_exact_size(
-                                                        egui::vec2(btn_width, btn_height),
-                                                        egui::Sense::click(),
+                                                if ui.is_rect_visible(rect) {
+                                                    let (fill, draw_rect) = theme::button_visual(&response, fill, rect);
+                                                    ui.painter().rect_filled(draw_rect, 4.0, fill);
+                                                    // Show 2-digit year with apostrophe
+                                                    let label = format!("'{}", year % 100);
+                                                    ui.painter().text(
+                                                        draw_rect.center(),
+                                                        egui::Align2::CENTER_CENTER,
+                                                        label,
+                                                        egui::FontId::proportional(12.0),
+                                                        egui::Color32::WHITE,
                                                     );
-                                                    if response.hovered() {
-                                                        ui.ctx().set_cursor_icon(
-                                                            egui::CursorIcon::PointingHand,
-                                                        );
-                                                    }
-                                                    if ui.is_rect_visible(rect) {
-                                                        let (fill, draw_rect) = theme::button_visual(&response, fill, rect);
-                                                ui.painter().rect_filled(draw_rect, 4.0, fill);
-                                                        // Show 2-digit year with apostrophe
-                                                        let label = format!("'{}", year % 100);
-                                                        ui.painter().text(
-                                                            draw_rect.center(),
-                                                            egui::Align2::CENTER_CENTER,
-                                                            label,
-                                                            egui::FontId::proportional(12.0),
-                                                            egui::Color32::WHITE,
-                                                        );
-                                                    }
-                                                    if response.clicked() {
-                                                        if selected {
-                                                            self.filter_years.remove(&year);
-                                                        } else {
-                                                            self.filter_years.insert(year);
-                                                        }
-                                                        filters_changed = true;
+                                                    theme::filter_count(
+                                                        ui,
+                                                        draw_rect,
+                                                        self.filter_counts.years.get(&year).copied().unwrap_or(0),
+                                                    );
+                                                }
+                                                theme::focus_ring(ui, &response, rect, 4.0);
+                                                theme::toggle_info(&response, &year.to_string(), selected);
+                                                if response.clicked() {
+                                                    if selected {
+                                                        self.filter_years.remove(&year);
+                                                    } else {
+                                                        self.filter_years.insert(year);
                                                     }
+                                                    filters_changed = true;
                                                 }
-                                            });
-                                            ui.add_space(2.0);
-                                        }
+                                            }
+                                        });
+                                        ui.add_space(2.0);
                                     }
                                 }
+
+                                // Dates missing or not starting with a year
+                                if theme::settings_checkbox(ui, self.include_undated, "Include undated", true) {
+                                    self.include_undated = !self.include_undated;
+                                    filters_changed = true;
+                                }
                             });
 
                             ui.add_space(4.0);
 
                             // STATUS section (Downloaded filter)
                             theme::section_frame().show(ui, |ui| {
-                                ui.add(
-                                    egui::Label::new(
-                                        egui::RichText::new("STATUS").color(theme::TEXT_DIM).size(11.0),
-                                    )
-                                    .selectable(false),
-                                );
+                                ui.horizontal(|ui| {
+                                    ui.add(
+                                        egui::Label::new(
+                                            egui::RichText::new("STATUS").color(theme::TEXT_DIM).size(11.0),
+                                        )
+                                        .selectable(false),
+                                    );
+                                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                        let rescan = ui
+                                            .add(
+                                                egui::Button::new(
+                                                    egui::RichText::new(egui_phosphor::regular::ARROWS_CLOCKWISE)
+                                                        .size(12.0)
+                                                        .color(theme::TEXT_MUTED),
+                                                )
+                                                .frame(false),
+                                            )
+                                            .on_hover_text("Rescan the download folder");
+                                        if rescan.hovered() {
+                                            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                                        }
+                                        if rescan.clicked() {
+                                            self.rescan_downloaded();
+                                        }
+                                        ui.add(
+                                            egui::Label::new(
+                                                egui::RichText::new(format!("{} downloaded", self.downloaded_count))
+                                                    .color(theme::TEXT_DIM)
+                                                    .size(11.0),
+                                            )
+                                            .selectable(false),
+                                        );
+                                    });
+                                });
                                 ui.add_space(8.0);
 
-                                let selected_fill = theme::TOGGLE_SELECTED;
+                                let selected_fill = theme::toggle_selected();
                                 let unselected_fill = theme::TOGGLE_UNSELECTED;
-                                let btn_width = ((ui.available_width() - 8.0) / 3.0).floor();
+                                let btn_width = ((ui.available_width() - 16.0) / 5.0).floor();
 
                                 // Icons with tooltips for equal-width buttons
                                 let icons = [
                                     (egui_phosphor::regular::CIRCLE, "All"),
                                     (egui_phosphor::regular::CHECK_CIRCLE, "Downloaded"),
                                     (egui_phosphor::regular::X_CIRCLE, "Not Downloaded"),
+                                    (egui_phosphor::regular::ARROW_CIRCLE_UP, "Update Available"),
+                                    (egui_phosphor::regular::ARCHIVE, "Removed from the database"),
                                 ];
 
                                 ui.horizontal(|ui| {
@@ -1059,6 +1439,8 @@ impl eframe::App for App {
                                                 egui::Color32::WHITE,
                                             );
                                         }
+                                        theme::focus_ring(ui, &response, rect, 4.0);
+                                        theme::toggle_info(&response, tooltip, self.filter_downloaded == i as u8);
                                         if response.clicked() && self.filter_downloaded != i as u8 {
                                             self.filter_downloaded = i as u8;
                                             filters_changed = true;
@@ -1066,6 +1448,22 @@ impl eframe::App for App {
                                         response.on_hover_text(*tooltip);
                                     }
                                 });
+
+                                if !self.outdated_names.is_empty() {
+                                    ui.add_space(6.0);
+                                    let label = format!(
+                                        "{}  Update outdated maps ({})",
+                                        egui_phosphor::regular::ARROW_CIRCLE_UP,
+                                        self.outdated_names.len()
+                                    );
+                                    if ui
+                                        .add_sized([ui.available_width(), 24.0], theme::button(label))
+                                        .on_hover_text("Download these maps again, replacing your copies")
+                                        .clicked()
+                                    {
+                                        self.update_outdated_maps(&ui.ctx().clone());
+                                    }
+                                }
                             });
                         });
 
@@ -1103,6 +1501,10 @@ impl eframe::App for App {
                     ui.set_min_width(bottom_rect.width());
                     ui.spacing_mut().item_spacing.y = 0.0; // Remove default vertical spacing
 
+                    if let Some((done, queued, paused)) = prefetch {
+                        self.render_prefetch_progress(ui, done, queued, paused);
+                    }
+
                     // Clear / Select All buttons (same line)
                     let btn_width = (ui.available_width() - 4.0) / 2.0;
                     ui.horizontal(|ui| {
@@ -1126,6 +1528,7 @@ impl eframe::App for App {
                             egui::Color32::WHITE,
                         );
                         if response.clicked() {
+                            self.remember_selection();
                             self.selected_indices.clear();
                         }
                         response.on_hover_text("Escape");
@@ -1149,11 +1552,32 @@ impl eframe::App for App {
                             egui::Color32::WHITE,
                         );
                         if response.clicked() {
-                            for &idx in &self.filtered_indices {
-                                self.selected_indices.insert(idx);
-                            }
+                            self.select_filtered(SelectScope::All);
                         }
-                        response.on_hover_text("Ctrl+A");
+                        // Right-click or long-press picks a subset by download status
+                        response.context_menu(|ui| {
+                            let labels = SelectScope::ALL.map(|s| s.label());
+                            theme::set_menu_width(ui, &[&labels[..], &[PASTE_LIST_LABEL]].concat());
+                            for scope in SelectScope::ALL {
+                                let icon = match scope {
+                                    SelectScope::All => egui_phosphor::regular::CHECK_SQUARE,
+                                    SelectScope::NotDownloaded => egui_phosphor::regular::CLOUD_ARROW_DOWN,
+                                    SelectScope::Downloaded => egui_phosphor::regular::CHECK_CIRCLE,
+                                };
+                                if theme::menu_item(ui, icon, scope.label()) {
+                                    self.select_filtered(scope);
+                                    ui.close_menu();
+                                }
+                            }
+                            ui.separator();
+                            if theme::menu_item(ui, egui_phosphor::regular::LIST_CHECKS, PASTE_LIST_LABEL) {
+                                self.paste_list = Some(PasteList::Input(String::new()));
+                                ui.close_menu();
+                            }
+                        });
+                        response.on_hover_text(
+                            "Ctrl+A\nRight-click for \"Select not downloaded\" (Ctrl+Shift+A)\nor \"Select downloaded\" (Ctrl+Alt+A)\nCtrl+Shift+V selects maps named in the clipboard",
+                        );
                     });
 
                     ui.add_space(4.0);
@@ -1218,7 +1642,7 @@ impl eframe::App for App {
                     let download_state = self.download_state.lock().unwrap();
                     let is_downloading = download_state.active_count > 0;
                     drop(download_state);
-                    let download_enabled = !is_downloading && selected_count > 0;
+                    let download_enabled = !is_downloading && selected_count > 0 && self.download_path_ok;
 
                     let download_rect = ui.available_rect_before_wrap();
                     let download_rect = egui::Rect::from_min_size(
@@ -1228,14 +1652,14 @@ impl eframe::App for App {
                     let download_response = ui.allocate_rect(download_rect, egui::Sense::click());
 
                     let download_fill = if download_enabled {
-                        theme::BTN_ACCENT
+                        theme::btn_accent()
                     } else {
                         disabled_fill
                     };
                     let (download_fill, download_draw) = if download_enabled { theme::button_visual(&download_response, download_fill, download_rect) } else { (download_fill, download_rect) };
                     ui.painter().rect_filled(download_draw, 4.0, download_fill);
                     let download_text_color = if download_enabled {
-                        egui::Color32::from_rgb(0x04, 0x2f, 0x2e) // teal-950
+                        theme::on_accent()
                     } else {
                         theme::TEXT_DIM
                     };
@@ -1268,7 +1692,16 @@ impl eframe::App for App {
                     }
                     let download_clicked = download_enabled && download_response.clicked();
                     if download_enabled {
-                        download_response.on_hover_text("Ctrl+D");
+                        download_response.on_hover_ui(|ui| {
+                            let sizes: Vec<u64> = self
+                                .selected_indices
+                                .iter()
+                                .filter_map(|&idx| self.maps.get(idx).map(|m| m.size.max(0) as u64))
+                                .collect();
+                            ui.label(format!("{} · Ctrl+D", format_batch_size(&sizes)));
+                        });
+                    } else if !self.download_path_ok {
+                        download_response.on_hover_text("Download folder is unreachable");
                     }
                     if download_clicked {
                         self.download_selected(ctx);
@@ -1302,265 +1735,52 @@ impl eframe::App for App {
             });
 
         // Settings modal (centered overlay)
-        if self.show_settings {
-            let modal_response = egui::Modal::new(egui::Id::new("settings_modal"))
-                .backdrop_color(egui::Color32::from_black_alpha(120))
-                .frame(egui::Frame::new()
-                    .fill(egui::Color32::from_rgb(0x1a, 0x1a, 0x1e))
-                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(0x2a, 0x2a, 0x2e)))
-                    .corner_radius(8.0)
-                    .inner_margin(egui::Margin::same(20)))
-                .show(ctx, |ui| {
-                    ui.set_width(320.0);
-
-                    // Title bar with close button (matches preview window style)
-                    ui.horizontal(|ui| {
-                        ui.add(egui::Label::new(
-                            egui::RichText::new("Settings").size(16.0).strong(),
-                        ).selectable(false));
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            let close_size = 24.0;
-                            let (rect, response) = ui.allocate_exact_size(
-                                egui::vec2(close_size, close_size),
-                                egui::Sense::click(),
-                            );
-                            let close_color = if response.hovered() {
-                                ui.painter().rect_filled(rect, 4.0, theme::BG_SURFACE);
-                                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-                                theme::STATUS_ERROR
-                            } else {
-                                theme::TEXT_DIM
-                            };
-                            ui.painter().text(
-                                rect.center(),
-                                egui::Align2::CENTER_CENTER,
-                                egui_phosphor::regular::X,
-                                egui::FontId::proportional(16.0),
-                                close_color,
-                            );
-                            if response.clicked() {
-                                self.show_settings = false;
-                            }
-                        });
-                    });
-                    ui.add_space(4.0);
-                    ui.separator();
-                    ui.add_space(theme::SPACING_SM);
-
-                    let mut changed = false;
-
-                    // — View —
-                    ui.add(egui::Label::new(
-                        egui::RichText::new("View").size(13.0).color(theme::ACCENT),
-                    ).selectable(false));
-                    ui.add_space(2.0);
-                    if theme::settings_checkbox(ui, self.large_thumbnails, "Large Thumbnails", true) {
-                        self.large_thumbnails = !self.large_thumbnails;
-                    }
-
-                    ui.add_space(theme::SPACING_MD);
-                    ui.separator();
-                    ui.add_space(theme::SPACING_SM);
+        self.render_settings_modal(ctx);
+        self.render_author_modal(ctx);
 
-                    // — Columns —
-                    ui.add(egui::Label::new(
-                        egui::RichText::new("Info Visibility").size(13.0).color(theme::ACCENT),
-                    ).selectable(false));
-                    ui.add_space(2.0);
-                    theme::settings_checkbox(ui, true, "Name", false); // Always enabled, dimmed
-                    for (val, label) in [
-                        (&mut self.show_category, "Category"),
-                        (&mut self.show_stars, "Stars"),
-                        (&mut self.show_points, "Points"),
-                        (&mut self.show_author, "Author"),
-                        (&mut self.show_release_date, "Release Date"),
-                    ] {
-                        if theme::settings_checkbox(ui, *val, label, true) {
-                            *val = !*val;
-                            changed = true;
-                        }
-                    }
+        // Right panel for scroll index (jump markers) and scrollbar
+        let index_panel_width = 44.0; // 20 for markers + 8 padding + 12 scrollbar + 4 padding
+        egui::SidePanel::right("scroll_index_panel")
+            .resizable(false)
+            .exact_width(index_panel_width)
+            .frame(egui::Frame::new().fill(theme::BG_BASE))
+            .show(ctx, |ui| {
+                let panel_rect = ui.available_rect_before_wrap();
+                let total_rows = self.filtered_indices.len();
 
-                    if changed {
-                        self.save_column_settings();
-                    }
+                // Get current row - use pending jump target if set (side panel renders before central panel updates memory)
+                let current_row = self.scroll_target_row.unwrap_or_else(|| {
+                    ui.ctx().memory(|mem| {
+                        mem.data
+                            .get_temp::<usize>("scroll_index_current_row".into())
+                            .unwrap_or(0)
+                    })
+                });
 
-                    ui.add_space(theme::SPACING_MD);
-                    ui.separator();
-                    ui.add_space(theme::SPACING_SM);
+                // Layout: [markers 20px] [padding 4px] [scrollbar 12px] [padding 4px]
+                let markers_width = 20.0;
+                let scrollbar_width = 12.0;
+                let padding = 4.0;
 
-                    // — Notifications —
-                    ui.add(egui::Label::new(
-                        egui::RichText::new("Notifications").size(13.0).color(theme::ACCENT),
-                    ).selectable(false));
-                    ui.add_space(2.0);
-                    if theme::settings_checkbox(ui, self.play_sound_on_complete, "Play sound on download complete", true) {
-                        self.play_sound_on_complete = !self.play_sound_on_complete;
-                    }
+                // Index markers on the left side of panel
+                let index_rect = egui::Rect::from_min_max(
+                    egui::pos2(panel_rect.min.x, panel_rect.min.y + theme::SPACING_MD),
+                    egui::pos2(panel_rect.min.x + markers_width, panel_rect.max.y),
+                );
+                if let Some(target_row) =
+                    self.render_scroll_index(ui, index_rect, total_rows, current_row)
+                {
+                    self.scroll_target_row = Some(target_row);
+                }
 
-                    ui.add_space(theme::SPACING_MD);
-                    ui.separator();
-                    ui.add_space(theme::SPACING_SM);
-
-                    // — Download Path —
-                    ui.add(egui::Label::new(
-                        egui::RichText::new("Download Path").size(13.0).color(theme::ACCENT),
-                    ).selectable(false));
-                    ui.add_space(2.0);
-
-                    let path_changed = ui.horizontal(|ui| {
-                        ui.spacing_mut().item_spacing.x = 4.0;
-                        let browse_width = 28.0 + 4.0; // button + spacing
-                        let frame_padding = 12.0 + 2.0; // inner_margin (6*2) + stroke (1*2)
-                        let text_width = (ui.available_width() - browse_width - frame_padding).max(40.0);
-                        // Text input styled like search box
-                        let te = egui::Frame::new()
-                            .fill(theme::BG_INPUT)
-                            .stroke(egui::Stroke::new(1.0, theme::BORDER_SUBTLE))
-                            .corner_radius(4.0)
-                            .inner_margin(egui::Margin::symmetric(6, 4))
-                            .show(ui, |ui| {
-                                ui.add(
-                                    egui::TextEdit::singleline(&mut self.download_path_str)
-                                        .frame(false)
-                                        .desired_width(text_width)
-                                        .font(egui::FontId::proportional(13.0)),
-                                )
-                            }).inner;
-                        // Browse button (aligned to text input height)
-                        let (rect, resp) = ui.allocate_exact_size(
-                            egui::vec2(28.0, 28.0), egui::Sense::click(),
-                        );
-                        if resp.hovered() {
-                            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-                            ui.painter().rect_filled(rect, 4.0, theme::BG_SURFACE);
-                        }
-                        ui.painter().text(
-                            rect.center(), egui::Align2::CENTER_CENTER,
-                            egui_phosphor::regular::FOLDER_OPEN,
-                            egui::FontId::proportional(16.0), theme::TEXT_SECONDARY,
-                        );
-                        let open_browser = resp.clicked() || te.double_clicked();
-                        if open_browser {
-                            std::fs::create_dir_all(&self.download_path).ok();
-                            if let Some(path) = rfd::FileDialog::new()
-                                .set_directory(&self.download_path)
-                                .pick_folder()
-                            {
-                                self.download_path = path;
-                                self.download_path_str = self.download_path.to_string_lossy().to_string();
-                                self.save_settings();
-                            }
-                        }
-                        te.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))
-                    }).inner;
-
-                    if path_changed {
-                        self.download_path = PathBuf::from(&self.download_path_str);
-                        self.save_settings();
-                    }
-
-                    ui.add_space(4.0);
-                    // Open Folder button
-                    let base = theme::BTN_DEFAULT;
-                    let (rect, response) = ui.allocate_exact_size(
-                        egui::vec2(120.0, 26.0), egui::Sense::click(),
-                    );
-                    if response.hovered() {
-                        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-                    }
-                    let (fill, draw_rect) = theme::button_visual(&response, base, rect);
-                    ui.painter().rect_filled(draw_rect, 4.0, fill);
-                    ui.painter().text(
-                        draw_rect.center(), egui::Align2::CENTER_CENTER,
-                        &format!("{}  Open Folder", egui_phosphor::regular::FOLDER_OPEN), egui::FontId::proportional(12.0),
-                        egui::Color32::WHITE,
-                    );
-                    if response.clicked() {
-                        std::fs::create_dir_all(&self.download_path).ok();
-                        let _ = open::that(&self.download_path);
-                    }
-
-                    ui.add_space(theme::SPACING_MD);
-                    ui.separator();
-                    ui.add_space(theme::SPACING_SM);
-
-                    // — Cache —
-                    ui.add(egui::Label::new(
-                        egui::RichText::new("Cache").size(13.0).color(theme::ACCENT),
-                    ).selectable(false));
-                    ui.add_space(2.0);
-                    let base = theme::BTN_DANGER;
-                    let (rect, response) = ui.allocate_exact_size(
-                        egui::vec2(120.0, 26.0), egui::Sense::click(),
-                    );
-                    if response.hovered() {
-                        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-                    }
-                    let (fill, draw_rect) = theme::button_visual(&response, base, rect);
-                    ui.painter().rect_filled(draw_rect, 4.0, fill);
-                    ui.painter().text(
-                        draw_rect.center(), egui::Align2::CENTER_CENTER,
-                        &format!("{}  Clear Cache", egui_phosphor::regular::TRASH), egui::FontId::proportional(12.0),
-                        egui::Color32::WHITE,
-                    );
-                    if response.clicked() {
-                        let _ = std::fs::remove_dir_all(self.cache_dir.join("thumbnails"));
-                        let _ = std::fs::remove_dir_all(self.cache_dir.join("full"));
-                        self.thumbnail_cache.clear();
-                        self.preview_textures.clear();
-                        self.start_thumbnail_prefetch(ui.ctx());
-                    }
-                });
-
-            if modal_response.should_close() {
-                self.show_settings = false;
-            }
-        }
-
-        // Right panel for scroll index (jump markers) and scrollbar
-        let index_panel_width = 44.0; // 20 for markers + 8 padding + 12 scrollbar + 4 padding
-        egui::SidePanel::right("scroll_index_panel")
-            .resizable(false)
-            .exact_width(index_panel_width)
-            .frame(egui::Frame::new().fill(theme::BG_BASE))
-            .show(ctx, |ui| {
-                let panel_rect = ui.available_rect_before_wrap();
-                let total_rows = self.filtered_indices.len();
-
-                // Get current row - use pending jump target if set (side panel renders before central panel updates memory)
-                let current_row = self.scroll_target_row.unwrap_or_else(|| {
-                    ui.ctx().memory(|mem| {
-                        mem.data
-                            .get_temp::<usize>("scroll_index_current_row".into())
-                            .unwrap_or(0)
-                    })
-                });
-
-                // Layout: [markers 20px] [padding 4px] [scrollbar 12px] [padding 4px]
-                let markers_width = 20.0;
-                let scrollbar_width = 12.0;
-                let padding = 4.0;
-
-                // Index markers on the left side of panel
-                let index_rect = egui::Rect::from_min_max(
-                    egui::pos2(panel_rect.min.x, panel_rect.min.y + theme::SPACING_MD),
-                    egui::pos2(panel_rect.min.x + markers_width, panel_rect.max.y),
-                );
-                if let Some(target_row) =
-                    self.render_scroll_index(ui, index_rect, total_rows, current_row)
-                {
-                    self.scroll_target_row = Some(target_row);
-                }
-
-                // Scrollbar on the right side of panel
-                let scrollbar_rect = egui::Rect::from_min_max(
-                    egui::pos2(
-                        panel_rect.max.x - scrollbar_width - padding,
-                        panel_rect.min.y,
-                    ),
-                    egui::pos2(panel_rect.max.x - padding, panel_rect.max.y),
-                );
+                // Scrollbar on the right side of panel
+                let scrollbar_rect = egui::Rect::from_min_max(
+                    egui::pos2(
+                        panel_rect.max.x - scrollbar_width - padding,
+                        panel_rect.min.y,
+                    ),
+                    egui::pos2(panel_rect.max.x - padding, panel_rect.max.y),
+                );
 
                 // Only show scrollbar if content exceeds viewport
                 if self.main_content_height > self.main_viewport_height
@@ -1604,6 +1824,7 @@ impl eframe::App for App {
 
                     // Handle drag
                     if thumb_response.dragged() {
+                        self.scroll_target_offset = None;
                         let delta_y = thumb_response.drag_delta().y;
                         if track_height > 0.0 {
                             self.main_scroll_offset += delta_y * (max_scroll / track_height);
@@ -1622,9 +1843,11 @@ impl eframe::App for App {
                         if let Some(pos) = ui.input(|i| i.pointer.hover_pos()) {
                             let click_ratio =
                                 (pos.y - scrollbar_rect.min.y) / scrollbar_rect.height();
-                            self.main_scroll_offset = (click_ratio * self.main_content_height
-                                - self.main_viewport_height / 2.0)
-                                .clamp(0.0, max_scroll);
+                            self.scroll_target_offset = Some(
+                                (click_ratio * self.main_content_height
+                                    - self.main_viewport_height / 2.0)
+                                    .clamp(0.0, max_scroll),
+                            );
                         }
                     }
                 }
@@ -1640,6 +1863,15 @@ impl eframe::App for App {
             .show(ctx, |ui| {
                 // Store panel rect for toast positioning
                 self.central_panel_rect = Some(ui.max_rect());
+
+                if !self.download_path_ok {
+                    self.render_download_path_banner(ui);
+                    ui.add_space(8.0);
+                }
+                if self.featured_map.is_some() {
+                    self.render_map_of_the_day_banner(ui);
+                    ui.add_space(8.0);
+                }
                 
                 // Header bar with "Showing X of Y maps" and icons
                 ui.horizontal(|ui| {
@@ -1654,22 +1886,45 @@ impl eframe::App for App {
                     } else {
                         status_text
                     };
+                    // Flash the count and show the delta briefly after a filter changes it
+                    let change = self
+                        .match_count_change
+                        .map(|(delta, at)| (delta, at.elapsed().as_secs_f32() / COUNT_FLASH_DURATION.as_secs_f32()))
+                        .filter(|&(_, t)| t < 1.0);
+                    let color = change.map_or(theme::TEXT_DIM, |(_, t)| {
+                        theme::accent().lerp_to_gamma(theme::TEXT_DIM, (t * 2.0).min(1.0))
+                    });
                     ui.add(
                         egui::Label::new(
                             egui::RichText::new(full_text)
-                                .color(theme::TEXT_DIM),
+                                .color(color),
                         )
                         .selectable(false),
                     );
+                    if self.search_pending {
+                        ui.add(egui::Spinner::new().size(12.0).color(theme::TEXT_DIM))
+                            .on_hover_text("Searching...");
+                    }
+                    if let Some((delta, t)) = change {
+                        let alpha = ((1.0 - t) * 4.0).min(1.0);
+                        ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(format!("({:+})", delta))
+                                    .color(theme::TEXT_MUTED.gamma_multiply(alpha)),
+                            )
+                            .selectable(false),
+                        );
+                        ui.ctx().request_repaint();
+                    }
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         // Settings gear
                         if ui
                             .add(egui::Button::new(egui_phosphor::regular::GEAR).frame(false))
-                            .on_hover_text("Settings")
+                            .on_hover_text("Settings (Ctrl+,)")
                             .clicked()
                         {
-                            self.show_settings = !self.show_settings;
+                            self.toggle_settings();
                         }
 
                         // View toggle (list/grid) - show icon for the view we'll switch TO
@@ -1692,7 +1947,8 @@ impl eframe::App for App {
                             let top_item = if self.compact_view {
                                 // List view: item index from scroll offset using actual row height
                                 // Add half row to land solidly in the current row, not the boundary
-                                ((self.main_scroll_offset + self.list_row_height * 0.5) / self.list_row_height).floor() as usize
+                                let row = ((self.main_scroll_offset + self.list_row_height * 0.5) / self.list_row_height).floor() as usize;
+                                self.list_pos_of(row)
                             } else {
                                 // Grid view: stored in memory
                                 ui.ctx().memory(|mem| {
@@ -1701,8 +1957,8 @@ impl eframe::App for App {
                                         .unwrap_or(0)
                                 })
                             };
-                            self.scroll_sync_item = Some(top_item);
-                            self.compact_view = !self.compact_view;
+                            // Sorts can differ per view, so sync to the same map rather than row
+                            self.scroll_sync_item = Some(self.switch_view(top_item));
                             self.view_switch_count += 1;
                             self.save_column_settings();
                         }
@@ -1717,6 +1973,77 @@ impl eframe::App for App {
                         {
                             let _ = open::that(&self.download_path);
                         }
+
+                        // Group-by menu (list only; the grid has no room for headers)
+                        if self.compact_view {
+                            let mut group_by = self.group_by;
+                            ui.menu_button(egui_phosphor::regular::STACK, |ui| {
+                                theme::set_menu_width(ui, &["Category"]);
+                                for option in GroupBy::ALL {
+                                    let icon = if self.group_by == option {
+                                        egui_phosphor::regular::CHECK
+                                    } else {
+                                        " "
+                                    };
+                                    if theme::menu_item(ui, icon, option.label()) {
+                                        group_by = option;
+                                        ui.close_menu();
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Group by");
+                            self.set_group_by(group_by);
+                        }
+
+                        // Sort menu (grid has no column headers)
+                        if !self.compact_view {
+                            let sort_icon = match self.sort_direction {
+                                SortDirection::Descending if self.sort_column.is_some() => {
+                                    egui_phosphor::regular::SORT_DESCENDING
+                                }
+                                _ => egui_phosphor::regular::SORT_ASCENDING,
+                            };
+                            let mut sort_changed = false;
+                            ui.menu_button(sort_icon, |ui| {
+                                theme::set_menu_width(ui, &["Release Date  ▲"]);
+                                for (col, label) in [
+                                    (SortColumn::Name, "Name"),
+                                    (SortColumn::Category, "Category"),
+                                    (SortColumn::Stars, "Stars"),
+                                    (SortColumn::Points, "Points"),
+                                    (SortColumn::Author, "Author"),
+                                    (SortColumn::ReleaseDate, "Release Date"),
+                                ] {
+                                    let icon = if self.sort_column == Some(col) {
+                                        match self.sort_direction {
+                                            SortDirection::Ascending => egui_phosphor::regular::CARET_UP,
+                                            SortDirection::Descending => egui_phosphor::regular::CARET_DOWN,
+                                        }
+                                    } else {
+                                        " "
+                                    };
+                                    if theme::menu_item(ui, icon, label) {
+                                        if self.sort_column == Some(col) {
+                                            self.sort_direction = match self.sort_direction {
+                                                SortDirection::Ascending => SortDirection::Descending,
+                                                SortDirection::Descending => SortDirection::Ascending,
+                                            };
+                                        } else {
+                                            self.sort_column = Some(col);
+                                            self.sort_direction = SortDirection::Ascending;
+                                        }
+                                        sort_changed = true;
+                                        ui.close_menu();
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Sort");
+                            if sort_changed {
+                                self.apply_filters();
+                            }
+                        }
                     });
                 });
 
@@ -1725,22 +2052,30 @@ impl eframe::App for App {
                 // Handle keyboard input - only when map list is focused
                 let modifiers = ui.input(|i| i.modifiers);
                 let mut nav_delta: i32 = 0;
-                let mut select_all = false;
-                let mut deselect_all = false;
+                let mut select_all: Option<SelectScope> = None;
                 let mut download_shortcut = false;
                 let mut preview_shortcut = false;
+                let mut page_delta: f32 = 0.0;
 
                 ui.input(|i| {
+                    if i.key_pressed(egui::Key::PageDown) {
+                        page_delta = 1.0;
+                    } else if i.key_pressed(egui::Key::PageUp) {
+                        page_delta = -1.0;
+                    }
                     if i.key_pressed(egui::Key::ArrowDown) {
                         nav_delta = 1;
                     } else if i.key_pressed(egui::Key::ArrowUp) {
                         nav_delta = -1;
                     }
                     if self.map_list_focused && i.modifiers.ctrl && i.key_pressed(egui::Key::A) {
-                        select_all = true;
-                    }
-                    if i.key_pressed(egui::Key::Escape) {
-                        deselect_all = true;
+                        select_all = Some(if i.modifiers.shift {
+                            SelectScope::NotDownloaded
+                        } else if i.modifiers.alt {
+                            SelectScope::Downloaded
+                        } else {
+                            SelectScope::All
+                        });
                     }
                     // Ctrl+D to download selected
                     if i.modifiers.ctrl
@@ -1755,15 +2090,14 @@ impl eframe::App for App {
                     }
                 });
 
-                if deselect_all {
-                    self.selected_indices.clear();
-                    self.last_selected = None;
+                if page_delta != 0.0 {
+                    // Chain from a pending target so repeated presses keep paging
+                    let from = self.scroll_target_offset.unwrap_or(self.main_scroll_offset);
+                    self.scroll_target_offset = Some(from + page_delta * self.main_viewport_height * 0.9);
                 }
 
-                if select_all {
-                    for &idx in &self.filtered_indices {
-                        self.selected_indices.insert(idx);
-                    }
+                if let Some(scope) = select_all {
+                    self.select_filtered(scope);
                 }
 
                 if nav_delta != 0 && !self.filtered_indices.is_empty() {
@@ -1772,20 +2106,25 @@ impl eframe::App for App {
                         .and_then(|sel| self.filtered_indices.iter().position(|&i| i == sel))
                         .unwrap_or(0);
 
-                    let new_pos = (current_pos as i32 + nav_delta)
-                        .max(0)
-                        .min(self.filtered_indices.len() as i32 - 1)
-                        as usize;
+                    let new_pos = self.step_list_pos(current_pos, nav_delta).unwrap_or(current_pos);
 
                     let new_idx = self.filtered_indices[new_pos];
 
                     if modifiers.shift {
+                        self.remember_selection_toggle();
                         self.selected_indices.insert(new_idx);
                     } else {
+                        // Moving collapses a multi-selection; a single one just moves along
+                        if self.selected_indices.len() > 1 {
+                            self.remember_selection();
+                        }
                         self.selected_indices.clear();
                         self.selected_indices.insert(new_idx);
                     }
                     self.last_selected = Some(new_idx);
+                    if self.auto_scroll_selection {
+                        self.scroll_into_view_pos = Some(new_pos);
+                    }
                 }
 
                 // Handle keyboard shortcuts
@@ -1818,18 +2157,24 @@ impl eframe::App for App {
                                 .color(theme::TEXT_MUTED),
                         );
                         ui.add_space(16.0);
+                        if self.search_elsewhere > 0 {
+                            let label = format!(
+                                "{}  Search everywhere ({})",
+                                egui_phosphor::regular::MAGNIFYING_GLASS,
+                                self.search_elsewhere
+                            );
+                            if ui
+                                .add(theme::button_accent(label))
+                                .on_hover_text("Clear the sidebar filters until this search is cleared")
+                                .clicked()
+                            {
+                                self.search_everywhere();
+                            }
+                            ui.add_space(4.0);
+                        }
                         if ui.add(theme::button(format!("{}  Clear Filters", egui_phosphor::regular::FUNNEL_X))).clicked() {
                             self.search_query.clear();
-                            self.filter_categories = [true; 8];
-                            self.category_mode_range = true;
-                            self.category_range = (0, 4);
-                            self.filter_stars = [true; 5];
-                            self.stars_mode_range = true;
-                            self.stars_range = (1, 5);
-                            self.filter_downloaded = 0;
-                            self.year_mode_range = true;
-                            self.year_range = None;
-                            self.filter_years = self.available_years.iter().copied().collect();
+                            self.clear_sidebar_filters();
                             self.apply_filters();
                         }
                     });
@@ -1848,10 +2193,15 @@ impl eframe::App for App {
 
         // Render preview window if open
         self.render_preview_window(ctx);
+        self.render_paste_list_modal(ctx);
+        self.render_onboarding(ctx);
+        // Last, so it opens above any other modal
+        self.render_shortcut_sheet(ctx);
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
         info!("Application shutting down");
+        self.shutdown_downloads();
         self.save_settings();
     }
 }
@@ -1863,6 +2213,199 @@ impl eframe::App for App {
 impl App {
     /// Render indexed scrollbar overlay and handle click-to-jump
     /// Returns row_index if a marker was clicked
+    /// Re-maximize if the window was maximized on exit. If the monitor the window
+    /// opened on isn't the one it was saved on (unplugged, layout changed), the saved
+    /// position is mapped proportionally onto it so the window isn't left off-screen.
+    fn restore_window_placement(&mut self, ctx: &egui::Context) {
+        let current = ctx.input(|i| i.viewport().monitor_size);
+        if let (Some(saved), Some(current), Some(pos), Some(size)) =
+            (self.window_monitor, current, self.window_pos, self.window_size)
+        {
+            let moved = (saved - current).length() > 1.0;
+            if moved && saved.x > 1.0 && saved.y > 1.0 && current.x > 1.0 && current.y > 1.0 {
+                // Offset within the saved monitor, as if monitors tile at its size
+                let frac = egui::vec2(pos.x.rem_euclid(saved.x) / saved.x, pos.y.rem_euclid(saved.y) / saved.y);
+                let size = size.min(current);
+                let pos = (frac * current).min(current - size).max(egui::Vec2::ZERO).to_pos2();
+                info!(?saved, ?current, x = pos.x, y = pos.y, "Saved monitor not found, moving window");
+                ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(pos));
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(size));
+                self.window_pos = Some(pos);
+                self.window_size = Some(size);
+            }
+        }
+        if self.window_maximized {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(true));
+        }
+    }
+
+    /// Live match count under the search box. When the filters hide every match,
+    /// say how many a search without them finds and offer to run it.
+    fn render_search_count(&mut self, ui: &mut egui::Ui) {
+        let matched = self.match_count.unwrap_or(self.filtered_indices.len());
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 6.0;
+            let text = match matched {
+                0 if self.search_elsewhere > 0 => {
+                    format!("0 here · {} with filters cleared", self.search_elsewhere)
+                }
+                0 => "No matches".to_string(),
+                1 => "1 match".to_string(),
+                n => format!("{} matches", n),
+            };
+            let color = if matched == 0 { theme::STATUS_WARNING } else { theme::TEXT_DIM };
+            ui.add(egui::Label::new(egui::RichText::new(text).size(11.0).color(color)).selectable(false));
+            if matched == 0 && self.search_elsewhere > 0 {
+                let search_all = ui
+                    .add(egui::Label::new(
+                        egui::RichText::new("Search everywhere").size(11.0).color(theme::accent()).underline(),
+                    ).sense(egui::Sense::click()).selectable(false))
+                    .on_hover_text("Clear the sidebar filters until this search is cleared");
+                if search_all.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                }
+                if search_all.clicked() {
+                    self.search_everywhere();
+                }
+            } else if self.relaxed_filters.is_some() {
+                ui.add(egui::Label::new(
+                    egui::RichText::new("· filters off").size(11.0).color(theme::TEXT_DIM),
+                ).selectable(false))
+                .on_hover_text("Your filters come back when the search is cleared");
+            }
+        });
+    }
+
+    /// "Fetching thumbnails… 240/1800" with a pause button and a thin bar beneath,
+    /// above the sidebar's bottom buttons while the prefetch runs
+    fn render_prefetch_progress(&mut self, ui: &mut egui::Ui, done: usize, queued: usize, paused: bool) {
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 24.0), egui::Sense::hover());
+        let text = if paused {
+            format!("Thumbnails paused · {}/{}", done, queued)
+        } else {
+            format!("Fetching thumbnails… {}/{}", done, queued)
+        };
+        ui.painter().text(
+            rect.left_center() - egui::vec2(0.0, 2.0),
+            egui::Align2::LEFT_CENTER,
+            text,
+            egui::FontId::proportional(11.0),
+            theme::TEXT_DIM,
+        );
+
+        let icon = if paused { egui_phosphor::regular::PLAY } else { egui_phosphor::regular::PAUSE };
+        let button_rect = egui::Rect::from_center_size(
+            egui::pos2(rect.right() - 10.0, rect.center().y - 2.0),
+            egui::vec2(20.0, 20.0),
+        );
+        let response = ui.interact(button_rect, ui.id().with("prefetch_pause"), egui::Sense::click());
+        let color = if response.hovered() { theme::TEXT_PRIMARY } else { theme::TEXT_DIM };
+        ui.painter().text(button_rect.center(), egui::Align2::CENTER_CENTER, icon, egui::FontId::proportional(12.0), color);
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        }
+        if response.on_hover_text(if paused { "Resume" } else { "Pause" }).clicked() {
+            self.toggle_prefetch_paused();
+        }
+
+        let track = egui::Rect::from_min_size(egui::pos2(rect.left(), rect.bottom() - 4.0), egui::vec2(rect.width(), 2.0));
+        ui.painter().rect_filled(track, 1.0, theme::BORDER_SUBTLE);
+        let fraction = done as f32 / queued as f32;
+        let fill = egui::Rect::from_min_size(track.min, egui::vec2(track.width() * fraction, track.height()));
+        ui.painter().rect_filled(fill, 1.0, if paused { theme::TEXT_DIM } else { theme::accent() });
+    }
+
+    fn toggle_settings(&mut self) {
+        self.show_settings = !self.show_settings;
+        self.cache_sizes = None; // re-measured when the Cache section shows
+    }
+
+    /// Ctrl+, toggles Settings, Ctrl+Z undoes selection changes and Ctrl+Shift+V selects the
+    /// maps named in the clipboard. Escape closes the topmost overlay (author popup, preview,
+    /// download modal, Settings) and otherwise clears the selection. It's consumed here so
+    /// the modals' own Escape handling can't close a second layer in the same frame;
+    /// confirmation dialogs keep theirs, where Escape means cancel.
+    fn handle_global_shortcuts(&mut self, ctx: &egui::Context) {
+        if self.show_shortcuts {
+            // Any key closes the cheat sheet and goes no further
+            let pressed = ctx.input_mut(|i| {
+                let pressed = i.events.iter().any(|e| matches!(e, egui::Event::Key { pressed: true, .. }));
+                if pressed {
+                    i.events.retain(|e| !matches!(e, egui::Event::Key { .. } | egui::Event::Text(_)));
+                }
+                pressed
+            });
+            if pressed {
+                self.show_shortcuts = false;
+            }
+            return;
+        }
+        let question_mark = !ctx.wants_keyboard_input()
+            && ctx.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Text(t) if t == "?")));
+        if question_mark || ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F1)) {
+            self.show_shortcuts = true;
+            return;
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Comma)) {
+            self.toggle_settings();
+        }
+        // Text fields keep Ctrl+Z for their own undo
+        let overlay_open = self.show_settings || self.show_download_modal || !self.preview_maps.is_empty();
+        if !overlay_open
+            && !ctx.wants_keyboard_input()
+            && ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z))
+        {
+            self.undo_selection();
+        }
+        // egui-winit turns Ctrl+V into a Paste event carrying the clipboard text
+        if !overlay_open && self.paste_list.is_none() && !ctx.wants_keyboard_input() {
+            let pasted = ctx.input_mut(|i| {
+                if !(i.modifiers.command && i.modifiers.shift) {
+                    return None;
+                }
+                let pos = i.events.iter().position(|e| matches!(e, egui::Event::Paste(_)))?;
+                match i.events.remove(pos) {
+                    egui::Event::Paste(text) => Some(text),
+                    _ => None,
+                }
+            });
+            if let Some(text) = pasted {
+                self.select_names(&text);
+            }
+        }
+
+        let dialog_open = self.show_exit_confirm
+            || self.onboarding.is_some()
+            || self.paste_list.is_some()
+            || self.show_app_update_dialog
+            || !self.precheck_dead.is_empty()
+            || self.download_confirm.is_some();
+        // A focused text field uses Escape to give up focus
+        if dialog_open || ctx.wants_keyboard_input() {
+            return;
+        }
+        if !ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape)) {
+            return;
+        }
+
+        if self.author_popup.is_some() {
+            self.author_popup = None;
+        } else if !self.preview_maps.is_empty() {
+            self.close_preview();
+        } else if self.show_download_modal {
+            // Same rule as clicking the backdrop: stays open while a batch runs
+            if !self.downloads_in_progress() {
+                self.close_download_modal();
+            }
+        } else if self.show_settings {
+            self.show_settings = false;
+        } else if !self.selected_indices.is_empty() {
+            self.remember_selection();
+            self.selected_indices.clear();
+            self.last_selected = None;
+        }
+    }
+
     fn render_scroll_index(
         &mut self,
         ui: &mut egui::Ui,
@@ -1929,7 +2472,7 @@ impl App {
             // Background for current/hovered
             if is_current || is_hovered {
                 let bg_color = if is_current {
-                    theme::SELECTION_SCROLL_ACTIVE
+                    theme::selection_scroll_active()
                 } else {
                     egui::Color32::from_rgba_unmultiplied(0xff, 0xff, 0xff, 30)
                 };
@@ -1963,11 +2506,92 @@ impl App {
             if response.clicked() {
                 clicked_row = Some(marker.row_index);
             }
+            theme::focus_ring(ui, &response, marker_rect, 2.0);
+            response.widget_info(|| {
+                egui::WidgetInfo::labeled(
+                    egui::WidgetType::Button,
+                    true,
+                    format!("Jump to {}", marker.label),
+                )
+            });
         }
 
         clicked_row
     }
 
+    /// Ease the shared scroll offset toward `scroll_target_offset` (exponential decay, ~0.2s feel).
+    /// Snaps when smooth scrolling is off; wheel input cancels it so the user stays in control.
+    fn step_scroll_animation(&mut self, ctx: &egui::Context) {
+        let Some(target) = self.scroll_target_offset else {
+            return;
+        };
+        if ctx.input(|i| i.smooth_scroll_delta.y != 0.0) {
+            self.scroll_target_offset = None;
+            return;
+        }
+        // Clamp so an out-of-range target (e.g. paging past the end) can't animate forever
+        let max_scroll = (self.main_content_height - self.main_viewport_height).max(0.0);
+        let target = target.clamp(0.0, max_scroll);
+        let diff = target - self.main_scroll_offset;
+        if diff.abs() < 0.5 || !self.smooth_scroll {
+            self.main_scroll_offset = target;
+            self.scroll_target_offset = None;
+        } else {
+            let dt = ctx.input(|i| i.stable_dt).min(0.1);
+            let t = 1.0 - (-10.0 * dt).exp();
+            self.main_scroll_offset += diff * t;
+            ctx.request_repaint();
+        }
+    }
+
+    /// Scroll just enough that `top..top + height` sits `margin` inside the viewport,
+    /// easing like other programmatic scrolls. Nothing happens if it's already visible.
+    fn scroll_into_view(&mut self, top: f32, height: f32, margin: f32) {
+        let offset = self.scroll_target_offset.unwrap_or(self.main_scroll_offset);
+        let viewport = self.main_viewport_height;
+        // Too short for the margin on both sides: just show the item at the top
+        let margin = margin.min(((viewport - height) / 2.0).max(0.0));
+        if top - margin < offset {
+            self.scroll_target_offset = Some((top - margin).max(0.0));
+        } else if top + height + margin > offset + viewport {
+            self.scroll_target_offset = Some(top + height + margin - viewport);
+        }
+    }
+
+    /// Group header row of the list view: caret, name and map count
+    fn paint_group_header(painter: &egui::Painter, rect: egui::Rect, group: &ListGroup, hovered: bool) {
+        let bg = if hovered { theme::BG_HOVER_SUBTLE } else { theme::BG_ELEVATED };
+        painter.rect_filled(rect, 0.0, bg);
+        painter.hline(rect.x_range(), rect.bottom() - 0.5, egui::Stroke::new(1.0, theme::BORDER_DEFAULT));
+        let caret = if group.collapsed {
+            egui_phosphor::regular::CARET_RIGHT
+        } else {
+            egui_phosphor::regular::CARET_DOWN
+        };
+        let mid = rect.left_center();
+        painter.text(
+            mid + egui::vec2(14.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            caret,
+            egui::FontId::proportional(13.0),
+            theme::TEXT_MUTED,
+        );
+        let name = painter.text(
+            mid + egui::vec2(34.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            &group.key,
+            egui::FontId::proportional(13.0),
+            theme::TEXT_PRIMARY,
+        );
+        painter.text(
+            name.right_center() + egui::vec2(8.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            group.count.to_string(),
+            egui::FontId::proportional(12.0),
+            theme::TEXT_DIM,
+        );
+    }
+
     fn render_list_view(
         &mut self,
         ui: &mut egui::Ui,
@@ -1978,7 +2602,8 @@ impl App {
         let mut preview_to_open: Option<Vec<String>> = None;
         let mut download_requested = false;
 
-        let row_height = 29.0;
+        let row_height = self.row_density.list_row_height(self.list_thumbnails);
+        let font_size = self.row_density.font_size();
         let header_height = 42.0;
         let header_bg = theme::BG_ELEVATED;
 
@@ -1995,11 +2620,25 @@ impl App {
         let modifiers = ui.input(|i| i.modifiers);
 
         // Handle view sync - scroll to item index
-        let sync_row = self.scroll_sync_item.take();
+        // Positions in filtered_indices become display rows once group headers are interleaved
+        let sync_row = self.scroll_sync_item.take().map(|pos| self.list_row_of(pos));
         if let Some(item_idx) = sync_row {
             self.main_scroll_offset = item_idx as f32 * row_height;
+            self.scroll_target_offset = None;
         }
 
+        // Index jumps ease toward the row instead of snapping
+        if let Some(target_row) = self.scroll_target_row.take() {
+            self.scroll_target_offset = Some(self.list_row_of(target_row) as f32 * self.list_row_height);
+        }
+        if let Some(pos) = self.scroll_into_view_pos.take() {
+            let h = self.list_row_height;
+            // Keep the sticky group header from covering the row
+            let margin = if self.list_groups.is_empty() { h } else { h * 2.0 };
+            self.scroll_into_view(self.list_row_of(pos) as f32 * h, h, margin);
+        }
+        self.step_scroll_animation(ui.ctx());
+
         // Build columns - full width (index overlays scrollbar)
         let available_width = ui.available_width() - 40.0; // minus checkbox column
         let ctx = ui.ctx().clone();
@@ -2013,13 +2652,11 @@ impl App {
             .scroll_bar_visibility(egui::scroll_area::ScrollBarVisibility::AlwaysHidden)
             .vertical_scroll_offset(self.main_scroll_offset);
 
-        // Apply scroll target if set (from index click or view sync)
-        let scroll_to = self.scroll_target_row.take().or(sync_row);
-        if let Some(target_row) = scroll_to {
-            table = table.scroll_to_row(target_row, Some(egui::Align::TOP));
-            if sync_row.is_some() {
-                table = table.animate_scrolling(false);
-            }
+        // View sync snaps straight to the row (opts out of easing)
+        if let Some(target_row) = sync_row {
+            table = table
+                .scroll_to_row(target_row, Some(egui::Align::TOP))
+                .animate_scrolling(false);
         }
 
         // Add checkbox column first (fixed width)
@@ -2057,6 +2694,7 @@ impl App {
             .copied()
             .collect();
 
+        let mut toggle_group: Option<usize> = None;
         let scroll_output = table
             .header(header_height, |mut header| {
                 let mut sort_changed = false;
@@ -2135,16 +2773,45 @@ impl App {
             })
             .body(|mut body| {
                 // Override selection color to teal for table rows only
-                body.ui_mut().visuals_mut().selection.bg_fill = theme::TABLE_ROW_SELECTED;
+                body.ui_mut().visuals_mut().selection.bg_fill = theme::table_row_selected();
 
                 let indices = self.filtered_indices.clone();
-
-                body.rows(row_height, indices.len(), |mut row| {
-                    let row_idx = row.index();
+                let rows = self.list_rows.clone();
+                let groups = self.list_groups.clone();
+
+                body.rows(row_height, self.list_row_count(), |mut row| {
+                    let row_idx = match rows.get(row.index()) {
+                        Some(&ListRow::Header(group)) => {
+                            // Checkbox cell isn't clipped, so it can paint across the whole row
+                            let (cell, _) = row.col(|_| {});
+                            for _ in &visible_cols {
+                                row.col(|_| {});
+                            }
+                            let response = row.response();
+                            let rect = egui::Rect::from_x_y_ranges(
+                                cell.left()..=response.rect.right(),
+                                response.rect.y_range(),
+                            );
+                            Self::paint_group_header(
+                                &ctx.layer_painter(response.layer_id).with_clip_rect(response.rect),
+                                rect,
+                                &groups[group],
+                                response.hovered(),
+                            );
+                            if response.clicked() {
+                                toggle_group = Some(group);
+                            }
+                            return;
+                        }
+                        Some(&ListRow::Map(pos)) => pos,
+                        None => row.index(),
+                    };
 
                     let map_idx = indices[row_idx];
+                    let map_name = self.maps[map_idx].name.clone();
+                    // Shares the grid's decode queue and upload budget
+                    let thumbnail = self.list_thumbnails.then(|| self.load_thumbnail(&ctx, &map_name));
                     let map = &self.maps[map_idx];
-                    let map_name = map.name.clone();
                     let is_selected = self.selected_indices.contains(&map_idx);
 
                     row.set_selected(is_selected);
@@ -2162,11 +2829,11 @@ impl App {
                                 ui.painter().rect_stroke(
                                     rect,
                                     3.0,
-                                    egui::Stroke::new(1.5, theme::ACCENT),
+                                    egui::Stroke::new(1.5, theme::accent()),
                                     egui::StrokeKind::Inside,
                                 );
                                 let inner = rect.shrink(3.0);
-                                ui.painter().rect_filled(inner, 2.0, theme::ACCENT);
+                                ui.painter().rect_filled(inner, 2.0, theme::accent());
                             } else {
                                 ui.painter().rect_stroke(
                                     rect,
@@ -2177,13 +2844,63 @@ impl App {
                             }
                         });
                     });
+                    let mut name_cell: Option<egui::Rect> = None;
+                    let mut name_inset = 0.0; // thumbnail and update badge before the name text
+                    let mut author_cell: Option<egui::Rect> = None;
+                    let mut author_text: Option<egui::Rect> = None; // clickable, opens the author popup
+                    let mut date_cell: Option<egui::Rect> = None;
                     for &col_idx in &visible_cols {
-                        row.col(|ui| {
+                        let (cell_rect, _) = row.col(|ui| {
                             match col_idx {
                                 0 => {
+                                    if let Some(texture) = &thumbnail {
+                                        let h = row_height - 6.0;
+                                        let (rect, _) = ui.allocate_exact_size(egui::vec2(h * 16.0 / 9.0, h), egui::Sense::hover());
+                                        match texture {
+                                            Some(texture) => {
+                                                // Crop to the slot's aspect instead of stretching
+                                                let [w, th] = texture.size().map(|v| v.max(1) as f32);
+                                                let (slot, tex) = (rect.aspect_ratio(), w / th);
+                                                let uv = if tex > slot {
+                                                    let u = slot / tex;
+                                                    egui::Rect::from_min_max(egui::pos2((1.0 - u) / 2.0, 0.0), egui::pos2((1.0 + u) / 2.0, 1.0))
+                                                } else {
+                                                    let v = tex / slot;
+                                                    egui::Rect::from_min_max(egui::pos2(0.0, (1.0 - v) / 2.0), egui::pos2(1.0, (1.0 + v) / 2.0))
+                                                };
+                                                egui::Image::from_texture(texture).uv(uv).corner_radius(3.0).paint_at(ui, rect);
+                                            }
+                                            None => {
+                                                ui.painter().rect_filled(rect, 3.0, theme::BG_ELEVATED);
+                                            }
+                                        }
+                                    }
+                                    if self.outdated_names.contains(&map.name) {
+                                        ui.add(
+                                            egui::Label::new(
+                                                egui::RichText::new(egui_phosphor::regular::ARROW_CIRCLE_UP)
+                                                    .size(font_size + 2.0)
+                                                    .color(theme::STATUS_WARNING),
+                                            )
+                                            .selectable(false),
+                                        )
+                                        .on_hover_text("Update available: your copy differs from the current version");
+                                    }
+                                    if map.removed_at.is_some() {
+                                        ui.add(
+                                            egui::Label::new(
+                                                egui::RichText::new(egui_phosphor::regular::ARCHIVE)
+                                                    .size(font_size + 2.0)
+                                                    .color(theme::STATUS_ERROR),
+                                            )
+                                            .selectable(false),
+                                        )
+                                        .on_hover_text("Removed from the database: it can't be downloaded any more");
+                                    }
+                                    name_inset = ui.cursor().left() - ui.max_rect().left();
                                     ui.add(
                                         egui::Label::new(
-                                            egui::RichText::new(&map.name).strong().size(14.0),
+                                            egui::RichText::new(&map.name).strong().size(font_size + 2.0),
                                         )
                                         .truncate()
                                         .selectable(false),
@@ -2193,7 +2910,7 @@ impl App {
                                     // Category badge - fixed size for all categories
                                     let (bg, fg) = theme::category_colors(&map.category);
                                     let (rect, _response) = ui.allocate_exact_size(
-                                        egui::vec2(62.0, 26.0),
+                                        egui::vec2(62.0, self.row_density.badge_height()),
                                         egui::Sense::hover(),
                                     );
                                     ui.painter().rect_filled(rect, 3.0, bg);
@@ -2201,7 +2918,7 @@ impl App {
                                         rect.center(),
                                         egui::Align2::CENTER_CENTER,
                                         &map.category,
-                                        egui::FontId::proportional(12.0),
+                                        egui::FontId::proportional(font_size),
                                         fg,
                                     );
                                 }
@@ -2215,7 +2932,7 @@ impl App {
                                         ui.add(
                                             egui::Label::new(
                                                 egui::RichText::new(&filled)
-                                                    .size(12.0)
+                                                    .size(font_size)
                                                     .color(theme::STAR_FILLED),
                                             )
                                             .selectable(false),
@@ -2223,41 +2940,54 @@ impl App {
                                         ui.add(
                                             egui::Label::new(
                                                 egui::RichText::new(&empty)
-                                                    .size(12.0)
+                                                    .size(font_size)
                                                     .color(theme::STAR_EMPTY),
                                             )
                                             .selectable(false),
                                         );
+                                        if self.show_star_label {
+                                            ui.add_space(6.0);
+                                            ui.add(
+                                                egui::Label::new(
+                                                    egui::RichText::new(star_label(map.stars))
+                                                        .size(font_size)
+                                                        .color(theme::TEXT_DIM),
+                                                )
+                                                .selectable(false),
+                                            );
+                                        }
                                     });
                                 }
                                 3 => {
                                     ui.add(
                                         egui::Label::new(
                                             egui::RichText::new(map.points.to_string())
-                                                .size(12.0)
+                                                .size(font_size)
                                                 .color(theme::TEXT_DIM),
                                         )
                                         .selectable(false),
                                     );
                                 }
                                 4 => {
-                                    ui.add(
+                                    let label = ui.add(
                                         egui::Label::new(
                                             egui::RichText::new(&map.author)
-                                                .size(12.0)
+                                                .size(font_size)
                                                 .color(theme::TEXT_DIM),
                                         )
                                         .truncate()
                                         .selectable(false),
                                     );
+                                    author_text = Some(label.rect);
                                 }
                                 5 => {
                                     ui.add(
                                         egui::Label::new(
                                             egui::RichText::new(format_release_date(
                                                 &map.release_date,
+                                                self.date_format,
                                             ))
-                                            .size(12.0)
+                                            .size(font_size)
                                             .color(theme::TEXT_DIM),
                                         )
                                         .selectable(false),
@@ -2266,13 +2996,56 @@ impl App {
                                 _ => {}
                             };
                         });
+                        match col_idx {
+                            0 => name_cell = Some(cell_rect),
+                            4 => author_cell = Some(cell_rect),
+                            5 => date_cell = Some(cell_rect),
+                            _ => {}
+                        }
                     }
 
-                    let response = row.response();
+                    let mut response = row.response();
+                    theme::toggle_info(&response, &map.name, is_selected);
+
+                    // The author text acts as a link; the row handles the click so selection still works elsewhere
+                    let on_author = author_text.is_some_and(|rect| {
+                        response.hovered()
+                            && ctx.input(|i| i.pointer.hover_pos()).is_some_and(|p| rect.contains(p))
+                    });
+                    if let Some(rect) = author_text.filter(|_| on_author) {
+                        ctx.layer_painter(response.layer_id).hline(
+                            rect.x_range(),
+                            rect.bottom(),
+                            egui::Stroke::new(1.0, theme::TEXT_DIM),
+                        );
+                    }
 
                     // Hand cursor on hover
                     if response.hovered() {
                         ctx.set_cursor_icon(egui::CursorIcon::PointingHand);
+
+                        // Full text tooltip for truncated name/author (measured on hovered row only)
+                        let hover_pos = ctx.input(|i| i.pointer.hover_pos());
+                        let truncated = [
+                            (name_cell.map(|c| c.with_min_x(c.left() + name_inset)), &map.name, font_size + 2.0),
+                            (author_cell, &map.author, font_size),
+                        ]
+                        .into_iter()
+                        .find_map(|(cell, text, size)| {
+                            let cell = cell?;
+                            let inside = hover_pos.is_some_and(|p| cell.contains(p));
+                            (inside
+                                && text_overflows(&ctx, text, egui::FontId::proportional(size), cell.width()))
+                            .then(|| text.clone())
+                        });
+                        // Relative dates show the absolute date on hover
+                        let on_date = self.date_format == DateFormat::Relative
+                            && date_cell.zip(hover_pos).is_some_and(|(c, p)| c.contains(p));
+                        if let Some(text) = truncated {
+                            response = response.on_hover_text(text);
+                        } else if on_date {
+                            response = response.on_hover_text(&map.release_date);
+                        }
                     }
 
                     // Right-click: select item if not already selected
@@ -2293,7 +3066,9 @@ impl App {
                         self.selected_indices.insert(map_idx);
                     }
 
-                    if response.clicked_by(egui::PointerButton::Primary) {
+                    if on_author && response.clicked_by(egui::PointerButton::Primary) && !modifiers.any() {
+                        self.author_popup = map.authors.first().cloned();
+                    } else if response.clicked_by(egui::PointerButton::Primary) {
                         self.map_list_focused = true;
                         self.last_clicked_item = Some(map_idx);
 
@@ -2302,6 +3077,7 @@ impl App {
                             // Selection behavior
                             if modifiers.shift && self.last_selected.is_some() {
                                 // Shift-click: range selection
+                                self.remember_selection();
                                 let last = self.last_selected.unwrap();
                                 let start = last.min(map_idx);
                                 let end = last.max(map_idx);
@@ -2312,6 +3088,7 @@ impl App {
                                 }
                             } else {
                                 // Normal click: toggle selection
+                                self.remember_selection_toggle();
                                 if self.selected_indices.contains(&map_idx) {
                                     self.selected_indices.remove(&map_idx);
                                 } else {
@@ -2332,6 +3109,19 @@ impl App {
                 });
             });
 
+        // Empty space below a short list deselects, like the grid background
+        let below = ui.available_rect_before_wrap();
+        if below.height() > 0.0
+            && ui.interact(below, ui.id().with("list_background"), egui::Sense::click()).clicked()
+            && self.preview_maps.is_empty()
+            && !self.show_download_modal
+            && !self.selected_indices.is_empty()
+        {
+            self.remember_selection();
+            self.selected_indices.clear();
+            self.last_selected = None;
+        }
+
         // Update shared scroll state from table's scroll area
         let new_offset = scroll_output.state.offset.y;
         self.main_scroll_offset = new_offset;
@@ -2339,18 +3129,52 @@ impl App {
         self.main_content_height = scroll_output.content_size.y;
 
         // Calculate current row from scroll offset using ACTUAL row height from content
-        // Add 1 pixel to offset to ensure we land IN the section at boundaries
-        let total_rows = self.filtered_indices.len();
+        // Offset by a sixth of a row to ensure we land IN the section at boundaries
+        let total_rows = self.list_row_count();
         let actual_row_height = if total_rows > 0 {
             scroll_output.content_size.y / total_rows as f32
         } else {
             row_height
         };
         self.list_row_height = actual_row_height;
-        let current_row =
-            ((scroll_output.state.offset.y + 5.0) / actual_row_height).floor() as usize;
+        let top_row = ((scroll_output.state.offset.y + row_height / 6.0) / actual_row_height).floor() as usize;
+        let current_row = self.list_pos_of(top_row);
 
-        ui.ctx().memory_mut(|mem| {
+        // Sticky header: the group of the top row stays pinned once its own header scrolls off
+        let sticky = self.list_rows[..(top_row + 1).min(self.list_rows.len())]
+            .iter()
+            .rev()
+            .find_map(|r| match r {
+                ListRow::Header(group) => Some(*group),
+                ListRow::Map(_) => None,
+            });
+        if let Some(group) = sticky.filter(|_| scroll_output.state.offset.y > 0.0) {
+            let body = scroll_output.inner_rect;
+            let rect = egui::Rect::from_min_size(body.min, egui::vec2(body.width(), row_height));
+            let response = ui.interact(rect, ui.id().with("sticky_group_header"), egui::Sense::click());
+            Self::paint_group_header(
+                &ui.ctx().layer_painter(ui.layer_id()).with_clip_rect(rect),
+                rect,
+                &self.list_groups[group],
+                response.hovered(),
+            );
+            if response.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+            }
+            if response.clicked() {
+                toggle_group = Some(group);
+            }
+        }
+        if let Some(group) = toggle_group {
+            // Keep the header in view when collapsing a long group from its sticky copy
+            let header_row = self.list_rows.iter().position(|r| *r == ListRow::Header(group));
+            self.toggle_group(group);
+            if let Some(row) = header_row.filter(|&row| row < top_row) {
+                self.main_scroll_offset = row as f32 * actual_row_height;
+            }
+        }
+
+        ui.ctx().memory_mut(|mem| {
             mem.data
                 .insert_temp("scroll_index_current_row".into(), current_row)
         });
@@ -2359,16 +3183,19 @@ impl App {
     }
 
     fn render_grid_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        let spacing = theme::SPACING_MD;
         let (base_w, base_h) = if self.large_thumbnails {
             theme::CARD_LARGE
         } else {
             theme::CARD_SMALL
         };
-        let available = ui.available_width();
-        let num_cols = ((available + spacing) / (base_w + spacing)).floor().max(3.0);
-        let card_w = ((available - spacing * (num_cols - 1.0)) / num_cols).floor();
-        let card_h = (base_h * (card_w / base_w)).floor();
+        let scale = self
+            .grid_card_scale
+            .clamp(theme::CARD_SCALE_MIN, theme::CARD_SCALE_MAX);
+        let layout = GridLayout::new(
+            ui.available_width(),
+            egui::vec2(base_w, base_h) * scale,
+            theme::SPACING_MD,
+        );
 
         let mut preview_to_open: Option<Vec<String>> = None;
         let mut download_requested = false;
@@ -2376,19 +3203,9 @@ impl App {
         // Capture modifiers before closures
         let modifiers = ui.input(|i| i.modifiers);
 
-        // Store full rect for index positioning
-        let full_rect = ui.available_rect_before_wrap();
-
-        // Calculate scroll offset if jumping to a row
-        let available_width = ui.available_width();
-        let cards_per_row = ((available_width + theme::SPACING_MD) / (card_w + theme::SPACING_MD))
-            .floor()
-            .max(1.0) as usize;
-
         // Handle view sync - calculate offset from item index
         if let Some(item_idx) = self.scroll_sync_item.take() {
-            let target_visual_row = item_idx / cards_per_row;
-            self.main_scroll_offset = target_visual_row as f32 * (card_h + theme::SPACING_MD);
+            self.main_scroll_offset = layout.row_of(item_idx) as f32 * layout.row_stride();
             // Force scroll area state so it picks up the new offset
             let scroll_id = ui.make_persistent_id("grid_scroll");
             let mut state = egui::scroll_area::State::default();
@@ -2396,27 +3213,18 @@ impl App {
             ui.ctx().memory_mut(|mem| {
                 mem.data.insert_persisted(scroll_id, state);
             });
+            self.scroll_target_offset = None;
         }
 
         // Handle scroll target from marker click
         if let Some(target_row) = self.scroll_target_row.take() {
-            let target_visual_row = target_row / cards_per_row;
-            self.grid_scroll_target = Some(target_visual_row as f32 * (card_h + theme::SPACING_MD));
+            self.scroll_target_offset = Some(layout.row_of(target_row) as f32 * layout.row_stride());
         }
-
-        // Animate scroll toward target with easing (exponential decay, ~0.2s feel)
-        if let Some(target) = self.grid_scroll_target {
-            let diff = target - self.main_scroll_offset;
-            if diff.abs() < 0.5 {
-                self.main_scroll_offset = target;
-                self.grid_scroll_target = None;
-            } else {
-                let dt = ctx.input(|i| i.stable_dt).min(0.1);
-                let t = 1.0 - (-10.0 * dt).exp();
-                self.main_scroll_offset += diff * t;
-                ctx.request_repaint();
-            }
+        if let Some(pos) = self.scroll_into_view_pos.take() {
+            let stride = layout.row_stride();
+            self.scroll_into_view(layout.row_of(pos) as f32 * stride, stride, stride * 0.25);
         }
+        self.step_scroll_animation(ctx);
 
         // Use shared scroll offset, hide scrollbar (it's in side panel)
         let scroll_area = egui::ScrollArea::vertical()
@@ -2425,212 +3233,290 @@ impl App {
             .id_salt("grid_scroll")
             .vertical_scroll_offset(self.main_scroll_offset);
 
+        let indices = self.filtered_indices.clone();
         let scroll_response = scroll_area.show(ui, |ui| {
-            let mut any_card_clicked = false;
-
-            ui.horizontal_wrapped(|ui| {
-                ui.spacing_mut().item_spacing = egui::vec2(theme::SPACING_MD, theme::SPACING_MD);
-                let indices = self.filtered_indices.clone();
-                for &map_idx in &indices {
-                    // Clone map data to avoid borrow issues
-                    let map = self.maps[map_idx].clone();
-                    let map_name = map.name.clone();
-                    let is_selected = self.selected_indices.contains(&map_idx);
-
-                    let (rect, response) =
-                        ui.allocate_exact_size(egui::vec2(card_w, card_h), egui::Sense::click());
-
-                    if ui.is_rect_visible(rect) {
-                        let painter = ui.painter();
-
-                        // Try to draw thumbnail as background
-                        // Paint base background (covers corners behind sharp-cornered image)
-                        painter.rect_filled(rect, theme::RADIUS_DEFAULT, theme::BG_BASE);
+            // Reserve the full content height, then place only the visible cards
+            let (_, content_rect) = ui.allocate_space(egui::vec2(
+                ui.available_width(),
+                layout.content_height(indices.len()),
+            ));
+            let origin = content_rect.min;
+            // Background under the cards: only clicks that reach it (gaps, empty space) deselect.
+            // Cards interact later so they sit on top; other panels and layers never hit it.
+            let background = ui.interact(ui.clip_rect(), ui.id().with("grid_background"), egui::Sense::click());
+            for (map_idx, rect) in layout.visible_cards(origin, &indices, ui.clip_rect()) {
+                // Clone map data to avoid borrow issues
+                let map = self.maps[map_idx].clone();
+                let map_name = map.name.clone();
+                let is_selected = self.selected_indices.contains(&map_idx);
+
+                let mut response =
+                    ui.interact(rect, ui.id().with(("grid_card", map_idx)), egui::Sense::click());
+                theme::toggle_info(&response, &map.name, is_selected);
+
+                // Full name tooltip when the name doesn't fit on the card
+                if response.hovered()
+                    && text_overflows(ctx, &map.name, egui::FontId::proportional(13.0), rect.shrink(8.0).width())
+                {
+                    response = response.on_hover_text(&map.name);
+                }
 
-                        if let Some(tex) = self.load_thumbnail(ctx, &map_name) {
-                            // Use a textured RectShape to clip the image to rounded corners
-                            let uv = egui::Rect::from_min_max(
-                                egui::pos2(0.0, 0.0),
-                                egui::pos2(1.0, 1.0),
-                            );
-                            let brush = egui::epaint::Brush {
-                                fill_texture_id: tex.id(),
-                                uv,
-                            };
-                            let mut shape = egui::epaint::RectShape::filled(
-                                rect,
-                                egui::CornerRadius::same(theme::RADIUS_DEFAULT as u8),
-                                egui::Color32::WHITE,
-                            );
-                            shape.brush = Some(std::sync::Arc::new(brush));
-                            painter.add(shape);
+                if ui.is_rect_visible(rect) {
+                    let painter = ui.painter();
 
-                            // Dark overlay for text readability
-                            painter.rect_filled(
-                                rect,
-                                theme::RADIUS_DEFAULT,
-                                egui::Color32::from_rgba_unmultiplied(0, 0, 0, 160),
-                            );
-                        } else {
-                            // Fallback solid background
-                            painter.rect_filled(rect, theme::RADIUS_DEFAULT, theme::BG_ELEVATED);
-                        }
+                    // Try to draw thumbnail as background
+                    // Paint base background (covers corners behind sharp-cornered image)
+                    painter.rect_filled(rect, theme::RADIUS_DEFAULT, theme::BG_BASE);
 
-                        // Selection/hover overlay (matching list view color #1b1829)
-                        if is_selected {
-                            painter.rect_filled(
-                                rect,
-                                theme::RADIUS_DEFAULT,
-                                egui::Color32::from_rgba_unmultiplied(0x0f, 0x1a, 0x19, 140),
-                            );
-                        } else if response.hovered() {
-                            painter.rect_filled(
-                                rect,
-                                4.0,
-                                egui::Color32::from_rgba_unmultiplied(0x0f, 0x1a, 0x19, 100),
-                            );
-                        }
+                    if let Some(tex) = self.load_thumbnail(ctx, &map_name) {
+                        // Use a textured RectShape to clip the image to rounded corners
+                        let uv = egui::Rect::from_min_max(
+                            egui::pos2(0.0, 0.0),
+                            egui::pos2(1.0, 1.0),
+                        );
+                        let brush = egui::epaint::Brush {
+                            fill_texture_id: tex.id(),
+                            uv,
+                        };
+                        let mut shape = egui::epaint::RectShape::filled(
+                            rect,
+                            egui::CornerRadius::same(theme::RADIUS_DEFAULT as u8),
+                            egui::Color32::WHITE,
+                        );
+                        shape.brush = Some(std::sync::Arc::new(brush));
+                        painter.add(shape);
 
-                        // Hand cursor on hover
-                        if response.hovered() {
-                            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-                        }
+                        // Dark overlay for text readability
+                        painter.rect_filled(
+                            rect,
+                            theme::RADIUS_DEFAULT,
+                            egui::Color32::from_rgba_unmultiplied(0, 0, 0, 160),
+                        );
+                    } else if self.thumbnail_missing.contains(&map_name) {
+                        paint_map_placeholder(painter, rect, &map.name, &map.category, theme::RADIUS_DEFAULT);
+                    } else if self.thumbnail_prefetch_running() || self.thumbnail_pending(&map_name) {
+                        // Skeleton pulse while the thumbnail may still arrive
+                        let t = (ctx.input(|i| i.time) * 2.0).sin() as f32 * 0.5 + 0.5;
+                        let fill = theme::BG_ELEVATED.lerp_to_gamma(theme::BG_SURFACE, t * 0.6);
+                        painter.rect_filled(rect, theme::RADIUS_DEFAULT, fill);
+                        ctx.request_repaint_after(std::time::Duration::from_millis(50));
+                    } else {
+                        // Fallback solid background
+                        painter.rect_filled(rect, theme::RADIUS_DEFAULT, theme::BG_ELEVATED);
+                    }
 
-                        let border_color = if is_selected {
-                            egui::Color32::from_rgba_unmultiplied(0x2d, 0xd4, 0xbf, 140)
-                        } else {
-                            egui::Color32::from_rgb(0x3a, 0x35, 0x42)
-                        };
-                        painter.rect_stroke(
+                    // Selection/hover overlay (matching list view color #1b1829)
+                    if is_selected {
+                        painter.rect_filled(
+                            rect,
+                            theme::RADIUS_DEFAULT,
+                            theme::with_alpha(theme::table_row_selected(), 140),
+                        );
+                    } else if response.hovered() {
+                        painter.rect_filled(
                             rect,
                             4.0,
-                            egui::Stroke::new(1.0, border_color),
-                            egui::StrokeKind::Outside,
+                            theme::with_alpha(theme::bg_hover(), 100),
                         );
+                    }
+
+                    // Hand cursor on hover
+                    if response.hovered() {
+                        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                    }
+
+                    let border_color = if is_selected {
+                        theme::with_alpha(theme::accent(), 140)
+                    } else {
+                        egui::Color32::from_rgb(0x3a, 0x35, 0x42)
+                    };
+                    painter.rect_stroke(
+                        rect,
+                        4.0,
+                        egui::Stroke::new(1.0, border_color),
+                        egui::StrokeKind::Outside,
+                    );
 
-                        let text_rect = rect.shrink(8.0);
+                    let text_rect = rect.shrink(8.0);
 
-                        // Name (top)
+                    // Removed / update-available badge (top right), the name ellipsizes before it
+                    let badge = if map.removed_at.is_some() {
+                        Some((egui_phosphor::regular::ARCHIVE, theme::STATUS_ERROR))
+                    } else if self.outdated_names.contains(&map.name) {
+                        Some((egui_phosphor::regular::ARROW_CIRCLE_UP, theme::STATUS_WARNING))
+                    } else {
+                        None
+                    };
+                    let badge_width = if let Some((icon, color)) = badge {
                         painter.text(
-                            text_rect.left_top(),
-                            egui::Align2::LEFT_TOP,
-                            &map.name,
-                            egui::FontId::proportional(13.0),
-                            egui::Color32::WHITE,
+                            text_rect.right_top(),
+                            egui::Align2::RIGHT_TOP,
+                            icon,
+                            egui::FontId::proportional(14.0),
+                            color,
                         );
+                        18.0
+                    } else {
+                        0.0
+                    };
 
-                        // Category + Stars (middle)
-                        let mut info_y = 18.0;
-                        {
-                            let mut parts = Vec::new();
-                            if self.show_category { parts.push(map.category.clone()); }
-                            if self.show_stars { parts.push(render_stars(map.stars)); }
-                            if !parts.is_empty() {
-                                painter.text(
-                                    text_rect.left_top() + egui::vec2(0.0, info_y),
-                                    egui::Align2::LEFT_TOP,
-                                    parts.join(" • "),
-                                    egui::FontId::proportional(10.0),
-                                    egui::Color32::from_rgb(0xcc, 0xcc, 0xcc),
-                                );
-                                info_y += 14.0;
+                    // Name (top)
+                    let name_galley = ellipsized_galley(
+                        ctx,
+                        &map.name,
+                        egui::FontId::proportional(13.0),
+                        egui::Color32::WHITE,
+                        text_rect.width() - badge_width,
+                    );
+                    painter.galley(text_rect.left_top(), name_galley, egui::Color32::WHITE);
+
+                    // Category + Stars (middle)
+                    let mut info_y = 18.0;
+                    {
+                        let mut parts = Vec::new();
+                        if self.grid_info.category { parts.push(map.category.clone()); }
+                        if self.grid_info.stars {
+                            let mut stars = render_stars(map.stars);
+                            if self.grid_info.star_label {
+                                stars = format!("{} {}", stars, star_label(map.stars));
                             }
+                            parts.push(stars);
                         }
-
-                        // Author (under category/stars, only for large thumbnails)
-                        if self.show_author && self.large_thumbnails {
+                        if !parts.is_empty() {
                             painter.text(
                                 text_rect.left_top() + egui::vec2(0.0, info_y),
                                 egui::Align2::LEFT_TOP,
-                                &map.author,
-                                egui::FontId::proportional(10.0),
-                                egui::Color32::from_rgb(0x90, 0x90, 0x98),
-                            );
-                        }
-
-                        // Points (bottom left)
-                        if self.show_points {
-                            painter.text(
-                                text_rect.left_bottom(),
-                                egui::Align2::LEFT_BOTTOM,
-                                format!("{} pts", map.points),
+                                parts.join(" • "),
                                 egui::FontId::proportional(10.0),
-                                theme::ACCENT_MUTED,
+                                egui::Color32::from_rgb(0xcc, 0xcc, 0xcc),
                             );
+                            info_y += 14.0;
                         }
+                    }
 
-                        // Release date (bottom right, only if enabled)
-                        if self.show_release_date {
-                            painter.text(
-                                text_rect.right_bottom(),
-                                egui::Align2::RIGHT_BOTTOM,
-                                format_release_date(&map.release_date),
-                                egui::FontId::proportional(9.0),
-                                theme::TEXT_DIM,
-                            );
-                        }
+                    // Author (under category/stars, only for large thumbnails)
+                    if self.grid_info.author && self.large_thumbnails {
+                        painter.text(
+                            text_rect.left_top() + egui::vec2(0.0, info_y),
+                            egui::Align2::LEFT_TOP,
+                            &map.author,
+                            egui::FontId::proportional(10.0),
+                            egui::Color32::from_rgb(0x90, 0x90, 0x98),
+                        );
                     }
 
-                    // Double-click to preview (only if both clicks were on same item)
-                    let is_valid_double_click =
-                        response.double_clicked() && self.last_clicked_item == Some(map_idx);
-                    if is_valid_double_click {
-                        preview_to_open = Some(vec![map_name.clone()]);
-                        // Ensure item is selected after preview
-                        self.selected_indices.insert(map_idx);
+                    // Points (bottom left)
+                    if self.grid_info.points {
+                        painter.text(
+                            text_rect.left_bottom(),
+                            egui::Align2::LEFT_BOTTOM,
+                            format!("{} pts", map.points),
+                            egui::FontId::proportional(10.0),
+                            theme::accent_muted(),
+                        );
                     }
 
-                    // Right-click: select item if not already selected
-                    if response.clicked_by(egui::PointerButton::Secondary) {
-                        any_card_clicked = true;
-                        if !self.selected_indices.contains(&map_idx) {
-                            self.selected_indices.insert(map_idx);
-                            self.last_selected = Some(map_idx);
+                    // Release date (bottom right, only if enabled)
+                    if self.grid_info.release_date {
+                        let date_rect = painter.text(
+                            text_rect.right_bottom(),
+                            egui::Align2::RIGHT_BOTTOM,
+                            format_release_date(&map.release_date, self.date_format),
+                            egui::FontId::proportional(9.0),
+                            theme::TEXT_DIM,
+                        );
+                        // Relative dates show the absolute date on hover
+                        let on_date = response.hovered()
+                            && ctx.pointer_hover_pos().is_some_and(|p| date_rect.contains(p));
+                        if self.date_format == DateFormat::Relative && on_date {
+                            response = response.on_hover_text(&map.release_date);
                         }
                     }
+                }
 
-                    // Left click for selection
-                    if response.clicked_by(egui::PointerButton::Primary) {
-                        any_card_clicked = true;
-                        self.map_list_focused = true;
-                        self.last_clicked_item = Some(map_idx);
+                theme::focus_ring(ui, &response, rect, 4.0);
 
-                        // Skip selection toggle on double-click
-                        if !is_valid_double_click {
-                            if modifiers.shift && self.last_selected.is_some() {
-                                // Shift-click: range selection
-                                let last = self.last_selected.unwrap();
-                                let start = last.min(map_idx);
-                                let end = last.max(map_idx);
-                                for i in start..=end {
-                                    if self.filtered_indices.contains(&i) {
-                                        self.selected_indices.insert(i);
-                                    }
+                // Double-click to preview (only if both clicks were on same item)
+                let is_valid_double_click =
+                    response.double_clicked() && self.last_clicked_item == Some(map_idx);
+                if is_valid_double_click {
+                    preview_to_open = Some(vec![map_name.clone()]);
+                    // Ensure item is selected after preview
+                    self.selected_indices.insert(map_idx);
+                }
+
+                // Right-click: select item if not already selected
+                if response.clicked_by(egui::PointerButton::Secondary) {
+                    if !self.selected_indices.contains(&map_idx) {
+                        self.selected_indices.insert(map_idx);
+                        self.last_selected = Some(map_idx);
+                    }
+                }
+
+                // Left click for selection
+                if response.clicked_by(egui::PointerButton::Primary) {
+                    self.map_list_focused = true;
+                    self.last_clicked_item = Some(map_idx);
+
+                    // Skip selection toggle on double-click
+                    if !is_valid_double_click {
+                        if modifiers.shift && self.last_selected.is_some() {
+                            // Shift-click: range selection
+                            self.remember_selection();
+                            let last = self.last_selected.unwrap();
+                            let start = last.min(map_idx);
+                            let end = last.max(map_idx);
+                            for i in start..=end {
+                                if self.filtered_indices.contains(&i) {
+                                    self.selected_indices.insert(i);
                                 }
+                            }
+                        } else {
+                            // Normal click: toggle selection
+                            self.remember_selection_toggle();
+                            if self.selected_indices.contains(&map_idx) {
+                                self.selected_indices.remove(&map_idx);
                             } else {
-                                // Normal click: toggle selection
-                                if self.selected_indices.contains(&map_idx) {
-                                    self.selected_indices.remove(&map_idx);
-                                } else {
-                                    self.selected_indices.insert(map_idx);
-                                }
+                                self.selected_indices.insert(map_idx);
                             }
-
-                            self.last_selected = Some(map_idx);
                         }
+
+                        self.last_selected = Some(map_idx);
                     }
+                }
 
-                    // Context menu
-                    response.context_menu(|ui| {
-                        let action = self.map_context_menu(ui, map_idx, &map_name);
-                        if let Some(names) = action.preview { preview_to_open = Some(names); }
-                        if action.download { download_requested = true; }
-                    });
+                // Context menu
+                response.context_menu(|ui| {
+                    let action = self.map_context_menu(ui, map_idx, &map_name);
+                    if let Some(names) = action.preview { preview_to_open = Some(names); }
+                    if action.download { download_requested = true; }
+                });
+            }
+
+            // Match-tier labels pinned over the first card of each section
+            let clip = ui.clip_rect();
+            for (pos, _, label) in &self.grid_sections {
+                let card = layout.card_rect(origin, *pos);
+                if !clip.intersects(card) {
+                    continue;
                 }
-            });
+                let painter = ui.painter();
+                let galley = painter.layout_no_wrap(
+                    label.clone(),
+                    egui::FontId::proportional(11.0),
+                    theme::TEXT_PRIMARY,
+                );
+                let pill = egui::Rect::from_min_size(
+                    card.min + egui::vec2(6.0, 6.0),
+                    galley.size() + egui::vec2(12.0, 4.0),
+                );
+                painter.rect_filled(pill, theme::RADIUS_DEFAULT, theme::BG_ELEVATED);
+                painter.galley(pill.min + egui::vec2(6.0, 2.0), galley, theme::TEXT_PRIMARY);
+            }
 
-            any_card_clicked
+            background.clicked()
         });
+        let background_clicked = scroll_response.inner;
 
         // Open preview if requested
         if let Some(names) = preview_to_open {
@@ -2643,16 +3529,10 @@ impl App {
         }
 
         // Left click on empty area to deselect (but not if preview window or download modal is open)
-        if !scroll_response.inner && self.preview_maps.is_empty() && !self.show_download_modal {
-            if ui.input(|i| i.pointer.button_clicked(egui::PointerButton::Primary)) {
-                if scroll_response
-                    .inner_rect
-                    .contains(ui.input(|i| i.pointer.hover_pos().unwrap_or_default()))
-                {
-                    self.selected_indices.clear();
-                    self.last_selected = None;
-                }
-            }
+        if background_clicked && self.preview_maps.is_empty() && !self.show_download_modal && !self.selected_indices.is_empty() {
+            self.remember_selection();
+            self.selected_indices.clear();
+            self.last_selected = None;
         }
 
         // Update shared scroll state from scroll area
@@ -2663,8 +3543,8 @@ impl App {
 
         // Store current row for scroll index panel
         let current_visual_row =
-            (scroll_response.state.offset.y / (card_h + theme::SPACING_MD)).floor() as usize;
-        let current_row = current_visual_row * cards_per_row;
+            (scroll_response.state.offset.y / layout.row_stride()).floor() as usize;
+        let current_row = current_visual_row * layout.cols;
         ctx.memory_mut(|mem| {
             mem.data
                 .insert_temp("scroll_index_current_row".into(), current_row)
@@ -2694,10 +3574,7 @@ impl App {
         {
             ctx.memory_mut(|mem| mem.data.remove::<String>("db_auto_updated".into()));
             // Reload maps
-            if let Ok(maps) = self.db.get_all_maps() {
-                self.maps = maps;
-                self.apply_filters();
-            }
+            self.reload_maps();
             // Parse result: comma-separated new map names
             let new_maps: Vec<&str> = result.split(',').filter(|s| !s.is_empty()).collect();
             let msg = if new_maps.is_empty() {
@@ -2707,7 +3584,53 @@ impl App {
             } else {
                 format!("Database updated: {}", new_maps.join(", "))
             };
-            ctx.memory_mut(|mem| mem.data.insert_temp("db_updated".into(), msg));
+            if new_maps.is_empty() {
+                self.push_toast(msg, ToastSeverity::Success);
+            } else {
+                let names = new_maps.iter().map(|s| s.to_string()).collect();
+                self.push_toast_with_button(msg, ToastSeverity::Success, ToastAction::ShowMaps(names));
+            }
+            if self.sound_on_db_update {
+                self.play_notification(false);
+            }
+        }
+
+        // Check for manual DB sync completion ("version:count")
+        if let Some(result) = ctx.memory(|mem| mem.data.get_temp::<String>("db_update_done".into()))
+        {
+            ctx.memory_mut(|mem| mem.data.remove::<String>("db_update_done".into()));
+            self.update_in_progress = false;
+            self.reload_maps();
+            let count = result.rsplit(':').next().unwrap_or_default();
+            self.push_toast(format!("Database synced: {} maps", count), ToastSeverity::Success);
+            if self.sound_on_db_update {
+                self.play_notification(false);
+            }
+        }
+        if let Some(message) =
+            ctx.memory(|mem| mem.data.get_temp::<String>("db_schema_unsupported".into()))
+        {
+            ctx.memory_mut(|mem| mem.data.remove::<String>("db_schema_unsupported".into()));
+            self.push_toast(message, ToastSeverity::Error);
+        }
+        if let Some(message) =
+            ctx.memory(|mem| mem.data.get_temp::<String>("db_manifest_older".into()))
+        {
+            ctx.memory_mut(|mem| mem.data.remove::<String>("db_manifest_older".into()));
+            self.update_in_progress = false;
+            self.push_toast(message, ToastSeverity::Warning);
+        }
+        if ctx.memory(|mem| mem.data.get_temp::<bool>("db_update_unchanged".into())).is_some() {
+            ctx.memory_mut(|mem| mem.data.remove::<bool>("db_update_unchanged".into()));
+            self.update_in_progress = false;
+            self.push_toast("Database is already up to date", ToastSeverity::Info);
+        }
+        self.poll_manifest_import(ctx);
+        if let Some(err) = ctx.memory(|mem| mem.data.get_temp::<String>("db_update_error".into()))
+        {
+            ctx.memory_mut(|mem| mem.data.remove::<String>("db_update_error".into()));
+            self.update_in_progress = false;
+            self.push_toast(format!("Database sync failed: {}", err), ToastSeverity::Error);
         }
 
         // Check for app update completion
@@ -2715,6 +3638,7 @@ impl App {
             ctx.memory(|mem| mem.data.get_temp::<String>("app_update_done".into()))
         {
             self.update_in_progress = false;
+            self.app_update_cancel = None;
             self.app_update_success = Some(version.clone());
             ctx.memory_mut(|mem| mem.data.remove::<String>("app_update_done".into()));
         }
@@ -2723,6 +3647,7 @@ impl App {
         if let Some(err) = ctx.memory(|mem| mem.data.get_temp::<String>("app_update_error".into()))
         {
             self.update_in_progress = false;
+            self.app_update_cancel = None;
             self.app_update_error = Some(err);
             ctx.memory_mut(|mem| mem.data.remove::<String>("app_update_error".into()));
         }
@@ -2749,7 +3674,7 @@ impl App {
                         // === Success state ===
                         ui.vertical_centered(|ui| {
                             ui.add_space(8.0);
-                            ui.label(egui::RichText::new(egui_phosphor::regular::CHECK_CIRCLE).size(36.0).color(theme::ACCENT));
+                            ui.label(egui::RichText::new(egui_phosphor::regular::CHECK_CIRCLE).size(36.0).color(theme::accent()));
                             ui.add_space(8.0);
                             ui.label(egui::RichText::new(format!("Updated to v{}!", new_ver)).size(16.0).strong());
                             ui.add_space(4.0);
@@ -2769,7 +3694,7 @@ impl App {
                         // Version header
                         ui.vertical_centered(|ui| {
                             ui.add_space(4.0);
-                            ui.label(egui::RichText::new(format!("v{}", version)).size(22.0).strong().color(theme::ACCENT));
+                            ui.label(egui::RichText::new(format!("v{}", version)).size(22.0).strong().color(theme::accent()));
                             ui.add_space(2.0);
                             ui.label(egui::RichText::new(format!("Current: v{}", APP_VERSION)).size(12.0).color(theme::TEXT_DIM));
                         });
@@ -2795,8 +3720,8 @@ impl App {
                                             } else if let Some(heading) = line.strip_prefix("# ") {
                                                 ui.add_space(6.0);
                                                 ui.label(egui::RichText::new(heading).strong().size(16.0));
-                                            } else if line.starts_with("- ") {
-                                                ui.label(format!("  •  {}", &line[2..]));
+                                            } else if let Some(item) = line.strip_prefix("- ") {
+                                                ui.label(format!("  •  {}", item));
                                             } else if line.is_empty() {
                                                 ui.add_space(2.0);
                                             } else {
@@ -2827,13 +3752,44 @@ impl App {
 
                         ui.add_space(16.0);
 
+                        // Download progress: bytes so far, of the total when the server sends it
+                        let progress = self.update_in_progress.then(|| {
+                            let p = self.app_update_progress.lock().unwrap();
+                            (p.downloaded, p.total, p.installing)
+                        });
+                        if let Some((downloaded, total, _)) = progress {
+                            ui.ctx().request_repaint_after(std::time::Duration::from_millis(100));
+                            if let Some(total) = total.filter(|&t| t > 0) {
+                                ui.add(
+                                    egui::ProgressBar::new(downloaded as f32 / total as f32)
+                                        .desired_height(6.0)
+                                        .fill(theme::accent()),
+                                );
+                                ui.add_space(6.0);
+                            }
+                        }
+
                         // Button area
                         ui.horizontal(|ui| {
                             ui.set_min_height(28.0);
                             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                if self.update_in_progress {
-                                    ui.spinner();
-                                    ui.label("Downloading update...");
+                                if let Some((downloaded, total, installing)) = progress {
+                                    if installing {
+                                        ui.spinner();
+                                        ui.label("Installing update...");
+                                    } else {
+                                        let cancel_btn = ui.add(theme::button(format!("{}  Cancel", egui_phosphor::regular::X)));
+                                        if cancel_btn.clicked() {
+                                            self.cancel_app_update();
+                                        }
+                                        ui.add_space(8.0);
+                                        let size = match total {
+                                            Some(total) => format!("{} / {}", format_bytes(downloaded), format_bytes(total)),
+                                            None => format_bytes(downloaded),
+                                        };
+                                        ui.label(egui::RichText::new(size).color(theme::TEXT_MUTED));
+                                        ui.label("Downloading update...");
+                                    }
                                 } else {
                                     let update_label = if self.app_update_error.is_some() { "Retry" } else { "Update" };
                                     let update_btn = ui.add(theme::button_accent(format!("{}  {}", egui_phosphor::regular::DOWNLOAD_SIMPLE, update_label)));
@@ -2859,61 +3815,122 @@ impl App {
             }
         }
 
-        // Check for DB update success - trigger toast
-        if let Some(msg) = ctx.memory(|mem| mem.data.get_temp::<String>("db_updated".into())) {
-            ctx.memory_mut(|mem| mem.data.remove::<String>("db_updated".into()));
-            self.toast_message = Some(msg);
-            self.toast_start = Some(std::time::Instant::now());
-        }
-
-        // Render toast notification (bottom-right of central panel, 3s visible then fade, pause on hover)
-        if let (Some(msg), Some(panel_rect)) = (&self.toast_message.clone(), self.central_panel_rect) {
-            let visible_duration = 3.0;
-            let fade_duration = 0.5;
-            let total_duration = visible_duration + fade_duration;
-            let margin = 12.0;
-            
-            // Position at bottom-right of central panel
-            let toast_pos = egui::pos2(panel_rect.right() - margin, panel_rect.bottom() - margin);
-            
-            let response = egui::Area::new(egui::Id::new("db_toast"))
-                .fixed_pos(toast_pos)
+        self.render_toasts(ctx);
+    }
+
+    /// Render queued toasts stacked at the bottom-right of the central panel.
+    /// Each is visible 3s then fades; hovering pauses only that toast. Warnings and
+    /// errors with a button stay until it's used or the toast is clicked.
+    fn render_toasts(&mut self, ctx: &egui::Context) {
+        let Some(panel_rect) = self.central_panel_rect else {
+            return;
+        };
+        let visible_duration = 3.0;
+        let fade_duration = 0.5;
+        let total_duration = visible_duration + fade_duration;
+        let margin = 12.0;
+        let gap = 8.0;
+        let max_visible = 3;
+
+        let mut bottom = panel_rect.bottom() - margin;
+        let mut clicked_action: Option<Option<ToastAction>> = None;
+        let mut expired: Vec<u64> = Vec::new();
+
+        for toast in self.toasts.iter_mut().take(max_visible) {
+            let sticky = toast.button.is_some()
+                && matches!(toast.severity, ToastSeverity::Warning | ToastSeverity::Error);
+            if sticky {
+                toast.shown_at = Some(std::time::Instant::now());
+            }
+            let shown_at = *toast.shown_at.get_or_insert_with(std::time::Instant::now);
+            let elapsed = shown_at.elapsed().as_secs_f32();
+            let alpha = if elapsed > visible_duration && self.toast_fade {
+                ((total_duration - elapsed) / fade_duration).max(0.0)
+            } else {
+                1.0
+            };
+            let border = match toast.severity {
+                ToastSeverity::Info => theme::accent(),
+                ToastSeverity::Success => theme::STATUS_SUCCESS,
+                ToastSeverity::Warning => theme::STATUS_WARNING,
+                ToastSeverity::Error => theme::STATUS_ERROR,
+            };
+
+            let mut button_clicked = false;
+            let response = egui::Area::new(egui::Id::new(("toast", toast.id)))
+                .fixed_pos(egui::pos2(panel_rect.right() - margin, bottom))
                 .pivot(egui::Align2::RIGHT_BOTTOM)
+                .order(egui::Order::Tooltip) // stay above the download modal
+                .sense(egui::Sense::click())
                 .show(ctx, |ui| {
-                    let elapsed = self.toast_start.map(|t| t.elapsed().as_secs_f32()).unwrap_or(0.0);
-                    let alpha = if elapsed > visible_duration { 
-                        (total_duration - elapsed) / fade_duration 
-                    } else { 
-                        1.0 
-                    };
-                    
                     egui::Frame::new()
                         .fill(egui::Color32::from_rgba_unmultiplied(0x1a, 0x1a, 0x1e, (230.0 * alpha) as u8))
                         .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(
-                            theme::ACCENT.r(), theme::ACCENT.g(), theme::ACCENT.b(), (100.0 * alpha) as u8
+                            border.r(), border.g(), border.b(), (100.0 * alpha) as u8
                         )))
                         .corner_radius(6.0)
                         .inner_margin(egui::Margin::symmetric(16, 10))
                         .show(ui, |ui| {
-                            ui.label(egui::RichText::new(msg).color(
-                                egui::Color32::from_rgba_unmultiplied(255, 255, 255, (255.0 * alpha) as u8)
-                            ));
+                            ui.horizontal(|ui| {
+                                ui.add(egui::Label::new(egui::RichText::new(&toast.message).color(
+                                    egui::Color32::from_rgba_unmultiplied(255, 255, 255, (255.0 * alpha) as u8)
+                                )).selectable(false));
+                                if let Some(button) = &toast.button {
+                                    ui.add_space(theme::SPACING_SM);
+                                    let fill = theme::btn_accent().gamma_multiply(alpha);
+                                    let text = egui::RichText::new(button.label())
+                                        .size(12.0)
+                                        .color(theme::on_accent().gamma_multiply(alpha));
+                                    if ui.add(egui::Button::new(text).fill(fill).corner_radius(theme::RADIUS_DEFAULT)).clicked() {
+                                        button_clicked = true;
+                                    }
+                                }
+                            });
                         });
-                });
-            
-            // Pause timer while hovering
-            if response.response.hovered() {
-                self.toast_start = Some(std::time::Instant::now());
+                })
+                .response;
+            bottom -= response.rect.height() + gap;
+
+            // Pause this toast's timer while hovering
+            if response.hovered() {
+                toast.shown_at = Some(std::time::Instant::now());
+                if toast.action.is_some() {
+                    ctx.set_cursor_icon(egui::CursorIcon::PointingHand);
+                }
             }
-            
-            let elapsed = self.toast_start.map(|t| t.elapsed().as_secs_f32()).unwrap_or(0.0);
-            if elapsed >= total_duration {
-                self.toast_message = None;
-                self.toast_start = None;
-            } else {
-                ctx.request_repaint();
+            if button_clicked {
+                clicked_action = Some(toast.button.clone());
+                expired.push(toast.id);
+            } else if response.clicked() {
+                clicked_action = Some(toast.action.clone());
+                expired.push(toast.id);
+            } else if elapsed >= total_duration || (!self.toast_fade && elapsed >= visible_duration) {
+                expired.push(toast.id);
+            }
+        }
+
+        self.toasts.retain(|t| !expired.contains(&t.id));
+        if let Some(Some(action)) = clicked_action {
+            match action {
+                ToastAction::OpenDownloads(filter) => {
+                    self.show_download_modal = true;
+                    self.show_download_log = true;
+                    self.download_log_filter = filter;
+                }
+                ToastAction::RetryFailed => {
+                    self.retry_failed_downloads(ctx, true);
+                    self.show_download_modal = true;
+                    self.show_download_log = true;
+                    self.download_log_filter = Some("failed");
+                }
+                ToastAction::ShowMaps(names) => self.show_maps(&names),
+                ToastAction::UndoSelection => self.undo_selection(),
+                ToastAction::CancelPrecheck => self.cancel_url_precheck(),
             }
         }
+        if !self.toasts.is_empty() {
+            ctx.request_repaint();
+        }
     }
 
     // ========================================================================
@@ -2931,8 +3948,10 @@ impl App {
         let failed = state.failed_count;
         let skipped = state.skipped_count;
         let cancelled = state.cancelled_count;
+        let paused = state.paused;
         let total_bytes = state.total_bytes;
         let downloaded_bytes = state.downloaded_bytes;
+        let companions = (state.companions_done, state.companions_failed, state.companions_total);
         let pending = total.saturating_sub(completed + failed + skipped + cancelled);
         let is_downloading = state.active_count > 0
             || state
@@ -2941,22 +3960,15 @@ impl App {
                 .any(|s| matches!(s, DownloadStatus::Pending));
         let download_order = state.download_order.clone();
         let downloads = state.downloads.clone();
+        let finished_at = state.finished_at.clone();
+        let queue: Vec<usize> = state.queue.iter().copied().collect();
+        let exhausted: HashSet<usize> = download_order
+            .iter()
+            .copied()
+            .filter(|&idx| state.retries_exhausted(idx, self.max_retries))
+            .collect();
         drop(state);
 
-        // Play sound when downloads finish
-        if self.was_downloading && !is_downloading && self.play_sound_on_complete {
-            #[cfg(windows)]
-            {
-                use std::os::windows::process::CommandExt;
-                const CREATE_NO_WINDOW: u32 = 0x08000000;
-                let _ = std::process::Command::new("powershell")
-                    .args(["-c", "[System.Media.SystemSounds]::Asterisk.Play()"])
-                    .creation_flags(CREATE_NO_WINDOW)
-                    .spawn();
-            }
-        }
-        self.was_downloading = is_downloading;
-
         // Calculate in-progress bytes from active downloads
         let in_progress_bytes: u64 = downloads
             .values()
@@ -2970,6 +3982,15 @@ impl App {
             .sum();
         let current_downloaded = downloaded_bytes + in_progress_bytes;
 
+        // Mirror overall progress on the taskbar icon
+        let finished = (completed + skipped + failed + cancelled) as u64;
+        let (done, of) = if total_bytes > 0 {
+            (current_downloaded, total_bytes)
+        } else {
+            (finished, total as u64)
+        };
+        self.taskbar.set(done, of, failed > 0, ctx);
+
         // Collect active downloads (currently downloading)
         let active_downloads: Vec<(usize, u64, u64)> = download_order
             .iter()
@@ -2984,9 +4005,13 @@ impl App {
 
         // Semi-transparent overlay - clickable to close when not downloading
         // Fixed position: compute top-left from constants to prevent any content-size jitter
-        let modal_width = 400.0 + theme::SPACING_XL * 2.0;
-        let modal_height = if self.show_download_log { 340.0 } else { 240.0 };
         let screen = ctx.screen_rect();
+        let log_height = self.download_log_height(ctx);
+        let modal_width = 400.0 + theme::SPACING_XL * 2.0;
+        let mut modal_height = if self.show_download_log { 266.0 + log_height } else { 240.0 };
+        if !queue.is_empty() {
+            modal_height += if self.show_download_queue { 128.0 } else { 24.0 };
+        }
         let pos = egui::pos2(
             (screen.center().x - modal_width / 2.0).round(),
             (screen.center().y - modal_height / 2.0).round(),
@@ -3008,10 +4033,17 @@ impl App {
                 // Batch Summary Header
                 ui.add_space(4.0);
                 ui.horizontal(|ui| {
-                    ui.colored_label(theme::ACCENT, egui_phosphor::regular::DOWNLOAD_SIMPLE);
+                    ui.colored_label(theme::accent(), egui_phosphor::regular::DOWNLOAD_SIMPLE);
                     ui.strong(format!("Downloading {} maps", total));
+                    if paused && is_downloading {
+                        ui.colored_label(theme::STATUS_WARNING, "· Paused");
+                    }
                     if total_bytes > 0 {
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            let remaining = total_bytes.saturating_sub(current_downloaded);
+                            if is_downloading && remaining > 0 {
+                                ui.colored_label(theme::TEXT_DIM, format!("· {} left", format_bytes(remaining)));
+                            }
                             ui.label(format!("{}/{}", format_bytes(current_downloaded), format_bytes(total_bytes)));
                         });
                     }
@@ -3051,10 +4083,12 @@ impl App {
                         ui.set_height(row_height);
                         // Fixed-width name column
                         let (_, name_rect) = ui.allocate_space(egui::vec2(name_width, row_height));
-                        let name_galley = ui.painter().layout_no_wrap(
-                            map_name.to_string(),
+                        let name_galley = ellipsized_galley(
+                            ctx,
+                            map_name,
                             egui::FontId::proportional(13.0),
                             egui::Color32::WHITE,
+                            name_width - 8.0,
                         );
                         ui.painter().galley(
                             name_rect.left_center() - egui::vec2(0.0, name_galley.size().y / 2.0),
@@ -3066,7 +4100,7 @@ impl App {
                         let bar = egui::ProgressBar::new(progress)
                             .desired_width(bar_width)
                             .corner_radius(3.0)
-                            .fill(theme::ACCENT);
+                            .fill(theme::accent());
                         ui.add(bar);
                         // Fixed-width percentage
                         ui.add_sized(
@@ -3176,6 +4210,21 @@ impl App {
                         ui.label(format!("{}", pending));
                     }
 
+                    let (companions_done, companions_failed, companions_total) = companions;
+                    if companions_total > 0 {
+                        ui.add_space(8.0);
+                        let color = if companions_failed > 0 { theme::STATUS_ERROR } else { theme::TEXT_DIM };
+                        ui.colored_label(
+                            color,
+                            format!("{} {}/{}", egui_phosphor::regular::PAPERCLIP, companions_done, companions_total),
+                        )
+                        .on_hover_text(if companions_failed > 0 {
+                            format!("Companion files ({} failed, see the log file)", companions_failed)
+                        } else {
+                            "Companion files".to_string()
+                        });
+                    }
+
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(format!(
                             "{}/{}",
@@ -3189,6 +4238,26 @@ impl App {
                 ui.separator();
                 ui.add_space(4.0);
 
+                // Collapsible Queue Section (pending maps, drag to reorder)
+                if !queue.is_empty() {
+                    let queue_icon = if self.show_download_queue {
+                        egui_phosphor::regular::CARET_DOWN
+                    } else {
+                        egui_phosphor::regular::CARET_RIGHT
+                    };
+                    if ui
+                        .selectable_label(false, format!("{} Queue ({})", queue_icon, queue.len()))
+                        .clicked()
+                    {
+                        self.show_download_queue = !self.show_download_queue;
+                    }
+                    if self.show_download_queue {
+                        ui.add_space(4.0);
+                        self.render_download_queue(ui, &queue);
+                    }
+                    ui.add_space(4.0);
+                }
+
                 // Collapsible Log Section
                 let log_icon = if self.show_download_log {
                     egui_phosphor::regular::CARET_DOWN
@@ -3196,82 +4265,61 @@ impl App {
                     egui_phosphor::regular::CARET_RIGHT
                 };
 
-                if ui
-                    .selectable_label(false, format!("{} Show Log", log_icon))
-                    .clicked()
-                {
-                    self.show_download_log = !self.show_download_log;
-                }
-
-                if self.show_download_log {
-                    ui.add_space(4.0);
-                    egui::ScrollArea::vertical()
-                        .max_height(100.0)
-                        .auto_shrink([false, false])
-                        .show(ui, |ui| {
-                            for &map_idx in &download_order {
-                                let status = downloads.get(&map_idx);
-
-                                // Apply filter
-                                let show = match (status, self.download_log_filter) {
-                                    (Some(DownloadStatus::Complete), None | Some("complete")) => {
-                                        true
-                                    }
-                                    (Some(DownloadStatus::Skipped), None | Some("skipped")) => true,
-                                    (Some(DownloadStatus::Failed(_)), None | Some("failed")) => {
-                                        true
-                                    }
-                                    (Some(DownloadStatus::Cancelled), None | Some("cancelled")) => {
-                                        true
-                                    }
-                                    _ => false,
-                                };
-                                if !show {
-                                    continue;
-                                }
-
-                                let map_name = self
-                                    .maps
-                                    .get(map_idx)
-                                    .map(|m| m.name.as_str())
-                                    .unwrap_or("Unknown");
-                                let (icon, color) = match status {
-                                    Some(DownloadStatus::Complete) => (
-                                        egui_phosphor::regular::CHECK,
-                                        egui::Color32::from_rgb(0x22, 0xc5, 0x5e),
-                                    ),
-                                    Some(DownloadStatus::Skipped) => (
-                                        egui_phosphor::regular::FAST_FORWARD,
-                                        theme::TEXT_DIM,
-                                    ),
-                                    Some(DownloadStatus::Cancelled) => (
-                                        egui_phosphor::regular::X,
-                                        theme::TEXT_DIM,
-                                    ),
-                                    Some(DownloadStatus::Failed(_)) => (
-                                        egui_phosphor::regular::X_CIRCLE,
-                                        egui::Color32::from_rgb(0xef, 0x44, 0x44),
-                                    ),
-                                    _ => continue,
-                                };
-
-                                ui.horizontal(|ui| {
-                                    ui.colored_label(color, icon);
-                                    ui.label(map_name);
-                                    if let Some(DownloadStatus::Failed(err)) = status {
-                                        ui.with_layout(
-                                            egui::Layout::right_to_left(egui::Align::Center),
-                                            |ui| {
-                                                ui.colored_label(
-                                                    theme::TEXT_DIM,
-                                                    err,
-                                                );
-                                            },
-                                        );
+                ui.horizontal(|ui| {
+                    if ui
+                        .selectable_label(false, format!("{} Show Log", log_icon))
+                        .clicked()
+                    {
+                        self.show_download_log = !self.show_download_log;
+                    }
+                    if self.show_download_log {
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            let (icon, tip) = if self.download_log_expanded {
+                                (egui_phosphor::regular::ARROWS_IN_SIMPLE, "Shrink log")
+                            } else {
+                                (egui_phosphor::regular::ARROWS_OUT_SIMPLE, "Expand log")
+                            };
+                            if ui.small_button(icon).on_hover_text(tip).clicked() {
+                                self.download_log_expanded = !self.download_log_expanded;
+                            }
+                            if self.download_log_filter == Some("failed")
+                                && ui
+                                    .small_button(format!("{} Copy all failures", egui_phosphor::regular::COPY))
+                                    .clicked()
+                            {
+                                ctx.copy_text(self.failure_report(&download_order));
+                                self.push_toast(format!("Copied {} failures", failed), ToastSeverity::Info);
+                            }
+                            let has_finished = download_order.iter().any(|idx| {
+                                matches!(downloads.get(idx), Some(DownloadStatus::Complete | DownloadStatus::Skipped))
+                            });
+                            if has_finished {
+                                // Declutters the log only; counts and the queue are untouched
+                                let mut clear: Option<bool> = None;
+                                let resp = ui
+                                    .small_button(format!("{} Clear completed", egui_phosphor::regular::BROOM))
+                                    .on_hover_text("Hide finished maps from the log\nRight-click to hide skipped ones too");
+                                if resp.clicked() {
+                                    clear = Some(false);
+                                }
+                                resp.context_menu(|ui| {
+                                    theme::set_menu_width(ui, &["Clear completed and skipped"]);
+                                    if theme::menu_item(ui, egui_phosphor::regular::BROOM, "Clear completed and skipped") {
+                                        clear = Some(true);
+                                        ui.close_menu();
                                     }
                                 });
+                                if let Some(include_skipped) = clear {
+                                    self.download_state.lock().unwrap().clear_finished(include_skipped);
+                                }
                             }
                         });
+                    }
+                });
+
+                if self.show_download_log {
+                    ui.add_space(4.0);
+                    self.render_download_log(ui, ctx, &download_order, &downloads, &finished_at, &exhausted);
                 }
 
                 ui.add_space(4.0);
@@ -3291,20 +4339,47 @@ impl App {
                                     token.cancel();
                                 }
                             }
+                            let (icon, label) = if paused {
+                                (egui_phosphor::regular::PLAY, "Resume")
+                            } else {
+                                (egui_phosphor::regular::PAUSE, "Pause")
+                            };
+                            if ui
+                                .add(theme::button(format!("{} {}", icon, label)))
+                                .on_hover_text("Maps already downloading will finish")
+                                .clicked()
+                            {
+                                self.toggle_downloads_paused();
+                            }
                         } else {
                             if ui.add(theme::button(format!("{}  Close", egui_phosphor::regular::X))).clicked() {
                                 self.close_download_modal();
                             }
-                            if failed > 0 {
-                                if ui
+                            if failed > exhausted.len()
+                                && ui
                                     .add(theme::button_accent(format!(
                                         "{} Retry Failed",
                                         egui_phosphor::regular::ARROW_CLOCKWISE
                                     )))
                                     .clicked()
-                                {
-                                    self.retry_failed_downloads(ctx);
-                                }
+                            {
+                                self.retry_failed_downloads(ctx, false);
+                            }
+                            if !exhausted.is_empty()
+                                && ui
+                                    .add(theme::button(format!(
+                                        "{} Force Retry All",
+                                        egui_phosphor::regular::ARROWS_CLOCKWISE
+                                    )))
+                                    .on_hover_text(format!(
+                                        "Also retry {} map{} that failed {} retries",
+                                        exhausted.len(),
+                                        if exhausted.len() == 1 { "" } else { "s" },
+                                        self.max_retries
+                                    ))
+                                    .clicked()
+                            {
+                                self.retry_failed_downloads(ctx, true);
                             }
                         }
                     });
@@ -3315,19 +4390,667 @@ impl App {
         }
     }
 
+    /// Log scroll height: compact, or whatever the window leaves room for when expanded
+    fn download_log_height(&self, ctx: &egui::Context) -> f32 {
+        if self.download_log_expanded {
+            // Rest of the modal (header, slots, open queue, footer) plus margins
+            (ctx.screen_rect().height() - 420.0).max(100.0).round()
+        } else {
+            100.0
+        }
+    }
+
+    fn render_download_queue(&mut self, ui: &mut egui::Ui, queue: &[usize]) {
+        let row_height = 20.0;
+        let mut move_before: Option<(usize, usize)> = None;
+        let mut move_to_front: Option<usize> = None;
+        let mut remove: Option<usize> = None;
+
+        egui::ScrollArea::vertical()
+            .id_salt("download_queue")
+            .max_height(100.0)
+            .auto_shrink([false, true])
+            .show_rows(ui, row_height, queue.len(), |ui, range| {
+                for pos in range {
+                    let map_idx = queue[pos];
+                    let map_name = self
+                        .maps
+                        .get(map_idx)
+                        .map(|m| m.name.as_str())
+                        .unwrap_or("Unknown");
+
+                    let row = ui
+                        .dnd_drag_source(egui::Id::new(("download_queue", map_idx)), map_idx, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.set_height(row_height);
+                                ui.colored_label(theme::TEXT_DIM, egui_phosphor::regular::DOTS_SIX_VERTICAL);
+                                ui.add(
+                                    egui::Label::new(
+                                        egui::RichText::new(format!("{}. {}", pos + 1, map_name))
+                                            .color(theme::TEXT_MUTED),
+                                    )
+                                    .selectable(false),
+                                );
+                            });
+                        })
+                        .response;
+
+                    // Drop indicator above the hovered row
+                    if let Some(dragged) = row.dnd_hover_payload::<usize>() {
+                        if *dragged != map_idx {
+                            ui.painter().hline(
+                                row.rect.x_range(),
+                                row.rect.top(),
+                                egui::Stroke::new(2.0, theme::accent()),
+                            );
+                        }
+                    }
+                    if let Some(dragged) = row.dnd_release_payload::<usize>() {
+                        move_before = Some((*dragged, map_idx));
+                    }
+                    // Drag-only sense doesn't report clicks, so the menu gets its own interaction
+                    let menu_resp = ui.interact(
+                        row.rect,
+                        egui::Id::new(("download_queue_menu", map_idx)),
+                        egui::Sense::click(),
+                    );
+                    menu_resp.context_menu(|ui| {
+                        theme::set_menu_width(ui, &["Move to front", "Remove from queue"]);
+                        if theme::menu_item(ui, egui_phosphor::regular::ARROW_LINE_UP, "Move to front") {
+                            move_to_front = Some(map_idx);
+                            ui.close_menu();
+                        }
+                        if theme::menu_item(ui, egui_phosphor::regular::X, "Remove from queue") {
+                            remove = Some(map_idx);
+                            ui.close_menu();
+                        }
+                    });
+                    // Added after the row interactions so it sits on top of them
+                    let remove_rect = egui::Rect::from_center_size(
+                        egui::pos2(ui.max_rect().right() - 12.0, row.rect.center().y),
+                        egui::vec2(18.0, 18.0),
+                    );
+                    if ui
+                        .put(remove_rect, egui::Button::new(egui_phosphor::regular::X).frame(false).small())
+                        .on_hover_text("Remove from queue")
+                        .clicked()
+                    {
+                        remove = Some(map_idx);
+                    }
+                }
+            });
+
+        if let Some(idx) = remove {
+            self.cancel_queued_download(idx);
+        }
+
+        if move_before.is_some() || move_to_front.is_some() {
+            let mut s = self.download_state.lock().unwrap();
+            if let Some((idx, target)) = move_before {
+                s.move_before(idx, target);
+            }
+            if let Some(idx) = move_to_front {
+                s.move_to_front(idx);
+            }
+        }
+    }
+
+    fn render_download_log(
+        &mut self,
+        ui: &mut egui::Ui,
+        ctx: &egui::Context,
+        download_order: &[usize],
+        downloads: &std::collections::HashMap<usize, DownloadStatus>,
+        finished_at: &std::collections::HashMap<usize, chrono::DateTime<chrono::Local>>,
+        exhausted: &HashSet<usize>,
+    ) {
+        let saved_as = self.download_state.lock().unwrap().saved_as.clone();
+
+        // Search box + sort headers
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.download_log_search)
+                    .hint_text(format!("{} Search log...", egui_phosphor::regular::MAGNIFYING_GLASS))
+                    .desired_width(160.0),
+            );
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                for (key, label) in [("time", "Time"), ("status", "Status"), ("name", "Name")] {
+                    let active = self.download_log_sort == Some(key);
+                    let arrow = match (active, self.download_log_sort_desc) {
+                        (true, false) => format!(" {}", egui_phosphor::regular::CARET_UP),
+                        (true, true) => format!(" {}", egui_phosphor::regular::CARET_DOWN),
+                        _ => String::new(),
+                    };
+                    let resp = ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(format!("{}{}", label, arrow))
+                                .size(12.0)
+                                .color(if active { theme::TEXT_PRIMARY } else { theme::TEXT_DIM }),
+                        )
+                        .sense(egui::Sense::click()),
+                    );
+                    // Cycle: ascending -> descending -> queue order
+                    if resp.clicked() {
+                        if !active {
+                            self.download_log_sort = Some(key);
+                            self.download_log_sort_desc = false;
+                        } else if !self.download_log_sort_desc {
+                            self.download_log_sort_desc = true;
+                        } else {
+                            self.download_log_sort = None;
+                        }
+                    }
+                }
+            });
+        });
+        ui.add_space(2.0);
+
+        let query = self.download_log_search.trim().to_lowercase();
+        let mut entries: Vec<usize> = download_order
+            .iter()
+            .copied()
+            .filter(|idx| {
+                let show = match (downloads.get(idx), self.download_log_filter) {
+                    (Some(DownloadStatus::Complete), None | Some("complete")) => true,
+                    (Some(DownloadStatus::Skipped), None | Some("skipped")) => true,
+                    (Some(DownloadStatus::Failed(_)), None | Some("failed")) => true,
+                    (Some(DownloadStatus::Cancelled), None | Some("cancelled")) => true,
+                    _ => false,
+                };
+                show && (query.is_empty()
+                    || self
+                        .maps
+                        .get(*idx)
+                        .is_some_and(|m| m.name.to_lowercase().contains(&query)))
+            })
+            .collect();
+
+        let status_rank = |idx: &usize| match downloads.get(idx) {
+            Some(DownloadStatus::Complete) => 0,
+            Some(DownloadStatus::Skipped) => 1,
+            Some(DownloadStatus::Failed(_)) => 2,
+            Some(DownloadStatus::Cancelled) => 3,
+            _ => 4,
+        };
+        match self.download_log_sort {
+            Some("name") => entries.sort_by_cached_key(|idx| {
+                self.maps.get(*idx).map(|m| m.name.to_lowercase()).unwrap_or_default()
+            }),
+            Some("status") => entries.sort_by_key(status_rank),
+            Some("time") => entries.sort_by_key(|idx| finished_at.get(idx).copied()),
+            _ => {}
+        }
+        if self.download_log_sort.is_some() && self.download_log_sort_desc {
+            entries.reverse();
+        }
+
+        // Variable row heights (expanded failures) so rows can be virtualized
+        let row_height = 20.0;
+        let detail_line_height = 14.0;
+        let heights: Vec<f32> = entries
+            .iter()
+            .map(|idx| match downloads.get(idx) {
+                Some(DownloadStatus::Failed(err)) if self.expanded_failures.contains(idx) => {
+                    row_height + err.lines().count() as f32 * detail_line_height + 4.0
+                }
+                _ => row_height,
+            })
+            .collect();
+        let mut offsets = Vec::with_capacity(heights.len());
+        let mut total_height = 0.0;
+        for h in &heights {
+            offsets.push(total_height);
+            total_height += h;
+        }
+
+        egui::ScrollArea::vertical()
+            .max_height(self.download_log_height(ctx))
+            .auto_shrink([false, false])
+            .show_viewport(ui, |ui, viewport| {
+                ui.set_height(total_height);
+                let origin = ui.max_rect().min;
+                let width = ui.available_width();
+                let first = offsets.partition_point(|&y| y < viewport.min.y).saturating_sub(1);
+
+                for i in first..entries.len() {
+                    if offsets[i] > viewport.max.y {
+                        break;
+                    }
+                    let map_idx = entries[i];
+                    let status = downloads.get(&map_idx);
+                    let map_name = self
+                        .maps
+                        .get(map_idx)
+                        .map_or("Unknown".to_string(), |m| m.name.clone());
+                    let (icon, color) = match status {
+                        Some(DownloadStatus::Complete) => (
+                            egui_phosphor::regular::CHECK,
+                            egui::Color32::from_rgb(0x22, 0xc5, 0x5e),
+                        ),
+                        Some(DownloadStatus::Skipped) => {
+                            (egui_phosphor::regular::FAST_FORWARD, theme::TEXT_DIM)
+                        }
+                        Some(DownloadStatus::Cancelled) => (egui_phosphor::regular::X, theme::TEXT_DIM),
+                        // Out of retries: skipped by "Retry Failed"
+                        Some(DownloadStatus::Failed(_)) if exhausted.contains(&map_idx) => {
+                            (egui_phosphor::regular::PROHIBIT, theme::TEXT_MUTED)
+                        }
+                        Some(DownloadStatus::Failed(_)) => (
+                            egui_phosphor::regular::X_CIRCLE,
+                            egui::Color32::from_rgb(0xef, 0x44, 0x44),
+                        ),
+                        _ => continue,
+                    };
+                    let time = finished_at
+                        .get(&map_idx)
+                        .map(|t| t.format("%H:%M:%S").to_string())
+                        .unwrap_or_default();
+
+                    let entry_rect = egui::Rect::from_min_size(
+                        origin + egui::vec2(0.0, offsets[i]),
+                        egui::vec2(width, heights[i]),
+                    );
+                    let row_rect = entry_rect.with_max_y(entry_rect.min.y + row_height);
+                    ui.scope_builder(egui::UiBuilder::new().max_rect(entry_rect), |ui| {
+                        ui.horizontal(|ui| {
+                            ui.set_height(row_height);
+                            ui.colored_label(color, icon);
+                            ui.label(&map_name);
+                            if let Some(file) = saved_as.get(&map_idx) {
+                                ui.add(
+                                    egui::Label::new(
+                                        egui::RichText::new(format!("saved as {}", file))
+                                            .size(11.0)
+                                            .color(theme::TEXT_DIM),
+                                    )
+                                    .selectable(false),
+                                );
+                            }
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                ui.add(
+                                    egui::Label::new(
+                                        egui::RichText::new(&time).size(11.0).color(theme::TEXT_DIM),
+                                    )
+                                    .selectable(false),
+                                );
+                                if let Some(DownloadStatus::Failed(err)) = status {
+                                    let expanded = self.expanded_failures.contains(&map_idx);
+                                    ui.colored_label(
+                                        theme::TEXT_DIM,
+                                        if expanded {
+                                            egui_phosphor::regular::CARET_DOWN
+                                        } else {
+                                            egui_phosphor::regular::CARET_RIGHT
+                                        },
+                                    );
+                                    let summary = err.lines().next().unwrap_or("");
+                                    let summary = if exhausted.contains(&map_idx) {
+                                        format!("Gave up after {} retries · {}", self.max_retries, summary)
+                                    } else {
+                                        summary.to_string()
+                                    };
+                                    ui.add(
+                                        egui::Label::new(
+                                            egui::RichText::new(summary).color(theme::TEXT_DIM),
+                                        )
+                                        .truncate()
+                                        .selectable(false),
+                                    );
+                                }
+                            });
+                        });
+
+                        // Failed entries expand on click to show the full error
+                        if let Some(DownloadStatus::Failed(err)) = status {
+                            let resp = ui.interact(
+                                row_rect,
+                                egui::Id::new(("download_log_failed", map_idx)),
+                                egui::Sense::click(),
+                            );
+                            if resp.hovered() {
+                                ctx.set_cursor_icon(egui::CursorIcon::PointingHand);
+                            }
+                            if resp.clicked() && !self.expanded_failures.remove(&map_idx) {
+                                self.expanded_failures.insert(map_idx);
+                            }
+                            resp.context_menu(|ui| {
+                                theme::set_menu_width(ui, &["Copy error"]);
+                                if theme::menu_item(ui, egui_phosphor::regular::COPY, "Copy error") {
+                                    ctx.copy_text(self.failure_report(&[map_idx]));
+                                    self.push_toast("Copied error details", ToastSeverity::Info);
+                                    ui.close_menu();
+                                }
+                            });
+                            if self.expanded_failures.contains(&map_idx) {
+                                ui.indent(("download_log_error", map_idx), |ui| {
+                                    ui.add(egui::Label::new(
+                                        egui::RichText::new(err)
+                                            .monospace()
+                                            .size(11.0)
+                                            .color(theme::TEXT_MUTED),
+                                    ));
+                                });
+                            }
+                        }
+                    });
+                }
+            });
+    }
+
+    /// Review maps whose URL failed the pre-download HEAD check
+    fn render_precheck_dialog(&mut self, ctx: &egui::Context) {
+        if self.precheck_dead.is_empty() {
+            return;
+        }
+        let dead = self.precheck_dead.clone();
+        let total = self.precheck_order.as_ref().map_or(0, |o| o.len());
+        let mut choice: Option<Option<bool>> = None; // Some(None) = cancel batch
+
+        let modal_response = egui::Modal::new(egui::Id::new("precheck_modal"))
+            .backdrop_color(egui::Color32::from_black_alpha(180))
+            .frame(theme::modal_frame())
+            .show(ctx, |ui| {
+                ui.set_width(380.0);
+                ui.add(egui::Label::new(
+                    egui::RichText::new("Some maps look unavailable").size(16.0).strong(),
+                ).selectable(false));
+                ui.add_space(theme::SPACING_SM);
+                ui.add(egui::Label::new(
+                    egui::RichText::new(format!(
+                        "{} of {} map links didn't respond. Their category/star folder may not exist.",
+                        dead.len(),
+                        total
+                    ))
+                    .color(theme::TEXT_SECONDARY),
+                ));
+                ui.add_space(theme::SPACING_SM);
+                egui::ScrollArea::vertical()
+                    .max_height(180.0)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        for (idx, reason) in &dead {
+                            let Some(map) = self.maps.get(*idx) else { continue };
+                            ui.horizontal(|ui| {
+                                ui.colored_label(theme::STATUS_ERROR, egui_phosphor::regular::X_CIRCLE);
+                                ui.label(truncate_chars(&map.name, 32));
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    ui.colored_label(theme::TEXT_DIM, reason)
+                                        .on_hover_text(self.get_map_url(map));
+                                });
+                            });
+                        }
+                    });
+                ui.add_space(theme::SPACING_MD);
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add(theme::button_accent(format!("Skip these ({})", dead.len())))
+                            .clicked()
+                        {
+                            choice = Some(Some(false));
+                        }
+                        if ui.add(theme::button("Download anyway")).clicked() {
+                            choice = Some(Some(true));
+                        }
+                        if ui.add(theme::button("Cancel")).clicked() {
+                            choice = Some(None);
+                        }
+                    });
+                });
+            });
+        if modal_response.should_close() && choice.is_none() {
+            choice = Some(None);
+        }
+
+        match choice {
+            Some(Some(include_dead)) => self.finish_url_precheck(ctx, include_dead),
+            Some(None) => {
+                self.precheck_dead.clear();
+                self.precheck_order = None;
+                self.batch_collision = None;
+            }
+            None => {}
+        }
+    }
+
+    /// Summary of a large batch before anything is queued
+    fn render_download_confirm_dialog(&mut self, ctx: &egui::Context) {
+        let Some(selected) = self.download_confirm.clone() else {
+            return;
+        };
+        let existing = selected
+            .iter()
+            .filter(|&&idx| self.maps.get(idx).is_some_and(|m| self.downloaded_names.contains(&m.name)))
+            .count();
+        // The checkbox starts ticked under Overwrite; unticking it means keep the files
+        let collision = match (self.download_confirm_overwrite, self.collision_strategy) {
+            (true, _) => CollisionStrategy::Overwrite,
+            (false, CollisionStrategy::Overwrite) => CollisionStrategy::Skip,
+            (false, strategy) => strategy,
+        };
+        // Existing files are only skipped under the Skip strategy
+        let to_download: Vec<usize> = selected
+            .iter()
+            .copied()
+            .filter(|&idx| {
+                collision != CollisionStrategy::Skip
+                    || self.maps.get(idx).is_some_and(|m| !self.downloaded_names.contains(&m.name))
+            })
+            .collect();
+        let sizes: Vec<u64> = to_download
+            .iter()
+            .filter_map(|&idx| self.maps.get(idx).map(|m| m.size.max(0) as u64))
+            .collect();
+
+        let mut choice: Option<bool> = None;
+        let modal_response = egui::Modal::new(egui::Id::new("download_confirm_modal"))
+            .backdrop_color(egui::Color32::from_black_alpha(180))
+            .frame(theme::modal_frame())
+            .show(ctx, |ui| {
+                ui.set_width(380.0);
+                ui.add(egui::Label::new(
+                    egui::RichText::new(format!("Download {} maps?", selected.len())).size(16.0).strong(),
+                ).selectable(false));
+                ui.add_space(theme::SPACING_SM);
+
+                let line = |ui: &mut egui::Ui, icon: &str, text: String| {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(theme::TEXT_DIM, icon);
+                        ui.label(egui::RichText::new(text).color(theme::TEXT_SECONDARY));
+                    });
+                };
+                line(ui, egui_phosphor::regular::DOWNLOAD_SIMPLE, format!("{} to download", to_download.len()));
+                if existing > 0 {
+                    let action = match collision {
+                        CollisionStrategy::Skip => "skipped",
+                        CollisionStrategy::Overwrite => "overwritten",
+                        CollisionStrategy::Rename => "kept alongside the new copy",
+                    };
+                    line(ui, egui_phosphor::regular::FAST_FORWARD, format!("{} already exist and will be {}", existing, action));
+                }
+                if !sizes.is_empty() {
+                    line(ui, egui_phosphor::regular::HARD_DRIVES, format_batch_size(&sizes));
+                }
+                line(ui, egui_phosphor::regular::FOLDER_OPEN, self.download_path.display().to_string());
+
+                ui.add_space(theme::SPACING_SM);
+                if existing > 0
+                    && theme::settings_checkbox(ui, self.download_confirm_overwrite, "Overwrite existing files", true)
+                {
+                    self.download_confirm_overwrite = !self.download_confirm_overwrite;
+                }
+                if theme::settings_checkbox(ui, !self.confirm_downloads, "Don't ask again", true) {
+                    self.confirm_downloads = !self.confirm_downloads;
+                    self.save_settings();
+                }
+
+                ui.add_space(theme::SPACING_MD);
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add(theme::button_accent(format!("{} Download", egui_phosphor::regular::DOWNLOAD_SIMPLE)))
+                            .clicked()
+                        {
+                            choice = Some(true);
+                        }
+                        if ui.add(theme::button("Cancel")).clicked() {
+                            choice = Some(false);
+                        }
+                    });
+                });
+            });
+        if modal_response.should_close() && choice.is_none() {
+            choice = Some(false);
+        }
+
+        if let Some(confirmed) = choice {
+            self.download_confirm = None;
+            if confirmed {
+                if collision != self.collision_strategy {
+                    self.batch_collision = Some(collision);
+                }
+                self.begin_download(ctx, selected);
+            }
+        }
+    }
+
+    /// Persistent warning while the download folder (e.g. a removed USB drive) is unreachable
+    fn render_download_path_banner(&mut self, ui: &mut egui::Ui) {
+        egui::Frame::new()
+            .fill(theme::with_alpha(theme::STATUS_WARNING, 20))
+            .stroke(egui::Stroke::new(1.0, theme::with_alpha(theme::STATUS_WARNING, 90)))
+            .corner_radius(6.0)
+            .inner_margin(egui::Margin::symmetric(10, 8))
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| {
+                    ui.colored_label(theme::STATUS_WARNING, egui_phosphor::regular::WARNING);
+                    // Leave room for the buttons on the right
+                    let text_width = (ui.available_width() - 200.0).max(80.0);
+                    ui.scope(|ui| {
+                        ui.set_max_width(text_width);
+                        ui.add(egui::Label::new(
+                            egui::RichText::new(format!(
+                                "Download folder unreachable: {}. Reconnect the drive or choose a new folder. Downloads are disabled until then.",
+                                self.download_path.display()
+                            ))
+                            .color(theme::TEXT_SECONDARY),
+                        ).truncate())
+                        .on_hover_text(self.download_path.display().to_string());
+                    });
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add(theme::button(format!("{}  Choose Folder", egui_phosphor::regular::FOLDER_OPEN)))
+                            .clicked()
+                        {
+                            if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                                self.download_path = path;
+                                self.download_path_str = self.download_path.to_string_lossy().to_string();
+                                self.check_download_path_now();
+                                self.save_settings();
+                            }
+                        }
+                        if ui.add(theme::button("Retry")).clicked() {
+                            self.check_download_path_now();
+                        }
+                    });
+                });
+            });
+    }
+
+    fn render_exit_confirm(&mut self, ctx: &egui::Context) {
+        if !self.show_exit_confirm {
+            return;
+        }
+        let (active, queued) = {
+            let s = self.download_state.lock().unwrap();
+            let queued = s
+                .downloads
+                .values()
+                .filter(|st| matches!(st, DownloadStatus::Pending))
+                .count();
+            (s.active_count, queued)
+        };
+
+        let modal_response = egui::Modal::new(egui::Id::new("exit_confirm_modal"))
+            .backdrop_color(egui::Color32::from_black_alpha(180))
+            .frame(theme::modal_frame())
+            .show(ctx, |ui| {
+                ui.set_width(360.0);
+                ui.add(egui::Label::new(
+                    egui::RichText::new("Downloads in progress").size(16.0).strong(),
+                ).selectable(false));
+                ui.add_space(theme::SPACING_SM);
+
+                if self.exit_when_idle {
+                    ui.add(egui::Label::new(
+                        egui::RichText::new(format!(
+                            "Finishing {} download{}, the app will close when done…",
+                            active,
+                            if active == 1 { "" } else { "s" }
+                        ))
+                        .color(theme::TEXT_SECONDARY),
+                    ));
+                    ui.add_space(theme::SPACING_MD);
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add(theme::button_danger(format!("{}  Cancel and exit now", egui_phosphor::regular::X)))
+                            .clicked()
+                        {
+                            self.cancel_downloads_and_exit(ctx);
+                        }
+                    });
+                    return;
+                }
+
+                ui.add(egui::Label::new(
+                    egui::RichText::new(format!(
+                        "{} map{} downloading and {} queued. Closing now stops them.",
+                        active,
+                        if active == 1 { " is" } else { "s are" },
+                        queued
+                    ))
+                    .color(theme::TEXT_SECONDARY),
+                ));
+                ui.add_space(theme::SPACING_MD);
+                ui.vertical(|ui| {
+                    ui.spacing_mut().item_spacing.y = theme::SPACING_SM;
+                    let width = ui.available_width();
+                    if ui
+                        .add_sized([width, 28.0], theme::button_danger("Cancel downloads and exit"))
+                        .clicked()
+                    {
+                        self.cancel_downloads_and_exit(ctx);
+                    }
+                    if ui
+                        .add_sized([width, 28.0], theme::button("Finish current files, then exit"))
+                        .on_hover_text("Queued maps are skipped")
+                        .clicked()
+                    {
+                        self.drain_downloads_then_exit();
+                    }
+                    if ui
+                        .add_sized([width, 28.0], theme::button_accent("Keep downloading"))
+                        .clicked()
+                    {
+                        self.show_exit_confirm = false;
+                    }
+                });
+            });
+        if modal_response.should_close() && !self.exit_when_idle {
+            self.show_exit_confirm = false;
+        }
+    }
+
     fn close_download_modal(&mut self) {
         self.show_download_modal = false;
         self.show_download_log = false;
         self.download_log_filter = None;
-        let mut state = self.download_state.lock().unwrap();
-        state.downloads.clear();
-        state.download_order.clear();
-        state.total_queued = 0;
-        state.completed_count = 0;
-        state.failed_count = 0;
-        state.skipped_count = 0;
-        state.cancelled_count = 0;
-        state.active_count = 0;
+        self.expanded_failures.clear();
+        self.taskbar.clear();
+        self.download_log_search.clear();
+        self.download_log_sort = None;
+        // Batch state is kept so the completion toast can reopen it; the next batch resets it
     }
 
     fn render_preview_window(&mut self, ctx: &egui::Context) {
@@ -3342,15 +5065,17 @@ impl App {
 
         let current_map = self.preview_maps[self.preview_active_tab].clone();
         let mut close = false;
-        let mut close_tab: Option<usize> = None;
+        let mut close_tabs: Vec<usize> = Vec::new();
+        let mut download_tab: Option<String> = None;
 
         // Try to load preview if not loaded yet
         if !self.preview_textures.contains_key(&current_map) {
-            let full_path = self
-                .cache_dir
-                .join("full")
-                .join(format!("{}.png", current_map));
-            if full_path.exists() {
+            let full_path = self.full_preview_path(&current_map);
+            if !full_path.exists() && self.full_preview_missing(&current_map) {
+                // The fetch hit a 404; stop waiting for it
+                self.preview_textures.insert(current_map.clone(), None);
+                self.preview_loading.remove(&current_map);
+            } else if full_path.exists() {
                 let tex = image::open(&full_path).ok().map(|img| {
                     let rgba = img.to_rgba8();
                     let size = [rgba.width() as usize, rgba.height() as usize];
@@ -3361,16 +5086,14 @@ impl App {
                         egui::TextureOptions::LINEAR,
                     )
                 });
+                if tex.is_none() {
+                    self.cache_suspects.insert(current_map.clone());
+                }
                 self.preview_textures.insert(current_map.clone(), tex);
                 self.preview_loading.remove(&current_map);
             }
         }
 
-        // Close on Escape
-        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
-            close = true;
-        }
-
         let title = if self.preview_maps.len() == 1 {
             current_map.clone()
         } else {
@@ -3380,10 +5103,10 @@ impl App {
         // Window sizing - header(36) + toolbar(34) + margins
         let has_tabs = self.preview_maps.len() > 1;
         let chrome_height = if has_tabs {
-            36.0 + 32.0 + 34.0 + 4.0
+            36.0 + 32.0 + 34.0 + 26.0 + 4.0
         } else {
-            36.0 + 34.0 + 4.0
-        }; // header + tabs? + toolbar + margins
+            36.0 + 34.0 + 26.0 + 4.0
+        }; // header + tabs? + toolbar + details + margins
         let target_img_height = 525.0;
         let target_img_width = target_img_height * (16.0 / 9.0);
 
@@ -3399,20 +5122,25 @@ impl App {
                 bottom: 2,
             });
 
-        // Dim backdrop behind preview - blocks interaction with main UI
+        // Dim backdrop behind preview - blocks interaction with main UI.
+        // Its click closes the preview: egui only reports one when press and release
+        // both land on the backdrop, so a pan released outside the window or a drag
+        // on the window's resize edge (which belongs to the window layer) won't count.
         let screen = ctx.screen_rect();
-        egui::Area::new(egui::Id::new("preview_dim"))
+        let backdrop_clicked = egui::Area::new(egui::Id::new("preview_dim"))
             .fixed_pos(screen.min)
             .order(egui::Order::Middle)
             .interactable(true)
             .show(ctx, |ui| {
-                ui.allocate_response(screen.size(), egui::Sense::click());
+                let response = ui.allocate_response(screen.size(), egui::Sense::click());
                 ui.painter().rect_filled(screen, 0.0, egui::Color32::from_black_alpha(120));
-            });
+                response.clicked()
+            })
+            .inner;
 
         let default_w = target_img_width;
         let default_h = target_img_height + chrome_height;
-        let win_resp = egui::Window::new("preview_window")
+        egui::Window::new("preview_window")
             .title_bar(false)
             .collapsible(false)
             .resizable(true)
@@ -3448,7 +5176,7 @@ impl App {
                     egui::Align2::LEFT_CENTER,
                     egui_phosphor::regular::IMAGE,
                     egui::FontId::proportional(16.0),
-                    theme::ACCENT,
+                    theme::accent(),
                 );
 
                 let title_x = icon_x + 24.0;
@@ -3531,11 +5259,7 @@ impl App {
 
                                         // Calculate tab width based on actual text width + close button
                                         let font_id = egui::FontId::proportional(12.0);
-                                        let display_name: String = if name.len() > 22 {
-                                            format!("{}…", &name[..21])
-                                        } else {
-                                            name.clone()
-                                        };
+                                        let display_name = truncate_chars(name, 22).into_owned();
                                         let text_width = ui.fonts(|f| {
                                             f.layout_no_wrap(display_name.clone(), font_id.clone(), theme::TEXT_PRIMARY)
                                                 .rect.width()
@@ -3550,7 +5274,7 @@ impl App {
 
                                         // Tab background
                                         let tab_bg = if is_active {
-                                            theme::ACCENT
+                                            theme::accent()
                                         } else if tab_response.hovered() {
                                             theme::BG_SURFACE
                                         } else {
@@ -3560,7 +5284,7 @@ impl App {
                                         ui.painter().rect_filled(tab_rect, 4.0, tab_bg);
 
                                         let text_color = if is_active {
-                                            egui::Color32::from_rgb(0x04, 0x2f, 0x2e) // teal-950
+                                            theme::on_accent()
                                         } else {
                                             theme::TEXT_SECONDARY
                                         };
@@ -3597,7 +5321,7 @@ impl App {
                                                 theme::STATUS_ERROR
                                             }
                                         } else if is_active {
-                                            egui::Color32::from_rgba_unmultiplied(0x04, 0x2f, 0x2e, 150)
+                                            theme::with_alpha(theme::on_accent(), 150)
                                         } else {
                                             theme::TEXT_DIM
                                         };
@@ -3617,7 +5341,7 @@ impl App {
                                                 .unwrap_or(tab_rect.center());
 
                                             if close_rect.contains(click_pos) {
-                                                close_tab = Some(i);
+                                                close_tabs = vec![i];
                                             } else if i != self.preview_active_tab {
                                                 self.preview_active_tab = i;
                                                 self.preview_zoom = 1.0;
@@ -3626,10 +5350,44 @@ impl App {
                                             }
                                         }
 
+                                        if tab_response.middle_clicked() {
+                                            close_tabs = vec![i];
+                                        }
+
                                         if tab_response.hovered() {
                                             ui.ctx()
                                                 .set_cursor_icon(egui::CursorIcon::PointingHand);
                                         }
+
+                                        let tab_count = self.preview_maps.len();
+                                        tab_response.context_menu(|ui| {
+                                            theme::set_menu_width(ui, &["Close tabs to the right", "Download this map"]);
+                                            if theme::menu_item(ui, egui_phosphor::regular::X, "Close") {
+                                                close_tabs = vec![i];
+                                                ui.close_menu();
+                                            }
+                                            if tab_count > 1 {
+                                                if theme::menu_item(ui, egui_phosphor::regular::X_SQUARE, "Close others") {
+                                                    close_tabs = (0..tab_count).filter(|&t| t != i).collect();
+                                                    ui.close_menu();
+                                                }
+                                                if i + 1 < tab_count
+                                                    && theme::menu_item(ui, egui_phosphor::regular::ARROW_LINE_RIGHT, "Close tabs to the right")
+                                                {
+                                                    close_tabs = (i + 1..tab_count).collect();
+                                                    ui.close_menu();
+                                                }
+                                            }
+                                            if theme::menu_item(ui, egui_phosphor::regular::X_CIRCLE, "Close all") {
+                                                close = true;
+                                                ui.close_menu();
+                                            }
+                                            ui.separator();
+                                            if theme::menu_item(ui, egui_phosphor::regular::DOWNLOAD_SIMPLE, "Download this map") {
+                                                download_tab = Some(name.clone());
+                                                ui.close_menu();
+                                            }
+                                        });
                                     }
 
                                     ui.add_space(8.0);
@@ -3759,7 +5517,7 @@ impl App {
                         theme::TEXT_PRIMARY,
                     );
                     if full_resp.clicked() {
-                        self.preview_zoom = 1.0;
+                        self.preview_zoom = self.native_preview_zoom(&current_map);
                         self.preview_offset = egui::Vec2::ZERO;
                     }
                     if full_resp.hovered() {
@@ -3775,6 +5533,59 @@ impl App {
                     }
                 });
 
+                // ═══════════════════════════════════════════════════════════
+                // DETAILS (selectable so name/author/URL can be copied)
+                // ═══════════════════════════════════════════════════════════
+                let file_info = self.map_file_info(&ui.ctx().clone(), &current_map).cloned();
+                if let Some(map) = self.maps.iter().find(|m| m.name == current_map) {
+                    let url = self.get_map_url(map);
+                    ui.scope(|ui| {
+                        // Labels are non-selectable app-wide; opt in for this strip only
+                        ui.style_mut().interaction.selectable_labels = true;
+                        ui.set_height(22.0);
+                        ui.horizontal(|ui| {
+                            ui.add_space(12.0);
+                            ui.spacing_mut().item_spacing.x = 6.0;
+                            let dim = |text: &str| egui::RichText::new(text).size(12.0).color(theme::TEXT_DIM);
+                            let value = |text: String| egui::RichText::new(text).size(12.0).color(theme::TEXT_SECONDARY);
+                            ui.label(value(map.name.clone()).strong());
+                            ui.label(dim("by"));
+                            ui.label(value(map.author.clone()));
+                            ui.label(dim("•"));
+                            ui.label(value(format!("{} {}", map.category, render_stars(map.stars))));
+                            ui.label(dim("•"));
+                            ui.label(value(format!("{} pts", map.points)));
+                            ui.label(dim("•"));
+                            let date = ui.label(value(format_release_date(&map.release_date, self.date_format)));
+                            if self.date_format == DateFormat::Relative {
+                                date.on_hover_text(&map.release_date);
+                            }
+                            if let Some(info) = &file_info {
+                                ui.label(dim("•"));
+                                ui.label(value(format!("{}×{} tiles", info.width, info.height)));
+                                ui.label(dim("•"));
+                                ui.label(value(format_bytes(info.file_size)));
+                                let embedded: Vec<String> = [
+                                    ("Author", &info.author),
+                                    ("Version", &info.version),
+                                    ("Credits", &info.credits),
+                                    ("License", &info.license),
+                                ]
+                                .iter()
+                                .filter_map(|(label, v)| v.as_ref().map(|v| format!("{}: {}", label, v)))
+                                .chain((info.images > 0).then(|| format!("Images: {}", info.images)))
+                                .collect();
+                                if !embedded.is_empty() {
+                                    ui.label(dim(egui_phosphor::regular::INFO))
+                                        .on_hover_text(embedded.join("\n"));
+                                }
+                            }
+                            ui.label(dim("•"));
+                            ui.add(egui::Label::new(dim(&url)).truncate());
+                        });
+                    });
+                }
+
                 ui.add_space(4.0);
 
                 // ═══════════════════════════════════════════════════════════
@@ -3787,10 +5598,7 @@ impl App {
                 // Dark background for image area
                 ui.painter().rect_filled(rect, 0.0, theme::BG_BASE);
 
-                let tex_opt = self
-                    .preview_textures
-                    .get(&current_map)
-                    .and_then(|t| t.as_ref());
+                let tex_opt = self.preview_textures.get(&current_map).cloned().flatten();
 
                 if let Some(tex) = tex_opt {
                     if fit_requested {
@@ -3806,9 +5614,14 @@ impl App {
                     let center = rect.center() + self.preview_offset;
                     let img_rect = egui::Rect::from_center_size(center, scaled_size);
 
+                    // Past the image's own pixels, draw the higher-resolution render once
+                    // it's fetched; the geometry above stays in full-preview units
+                    let zoomed_past_native = self.preview_zoom * ui.ctx().pixels_per_point() > 1.0;
+                    let hires = if zoomed_past_native { self.hires_preview(ui.ctx(), &current_map) } else { None };
+
                     ui.set_clip_rect(rect);
                     ui.painter().image(
-                        tex.id(),
+                        hires.as_ref().unwrap_or(&tex).id(),
                         img_rect,
                         egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
                         egui::Color32::WHITE,
@@ -3824,6 +5637,7 @@ impl App {
                         let mut labels = vec![
                             format!("{}  Fit to Window", egui_phosphor::regular::CORNERS_IN),
                             format!("{}  Actual Size", egui_phosphor::regular::FRAME_CORNERS),
+                            format!("{}  Open image in default viewer", egui_phosphor::regular::IMAGE),
                         ];
                         if self.preview_maps.len() > 1 {
                             labels.push(format!("{}  Close Tab", egui_phosphor::regular::X));
@@ -3836,14 +5650,23 @@ impl App {
                             ui.close_menu();
                         }
                         if theme::menu_item(ui, egui_phosphor::regular::FRAME_CORNERS, "Actual Size") {
-                            self.preview_zoom = 1.0;
+                            self.preview_zoom = self.native_preview_zoom(&current_map);
                             self.preview_offset = egui::Vec2::ZERO;
                             ui.close_menu();
                         }
+                        ui.separator();
+                        if theme::menu_item(ui, egui_phosphor::regular::IMAGE, "Open image in default viewer") {
+                            self.open_full_preview_file(ui.ctx(), &current_map, false);
+                            ui.close_menu();
+                        }
+                        if theme::menu_item(ui, egui_phosphor::regular::FOLDER_OPEN, "Show in folder") {
+                            self.open_full_preview_file(ui.ctx(), &current_map, true);
+                            ui.close_menu();
+                        }
                         if self.preview_maps.len() > 1 {
                             ui.separator();
                             if theme::menu_item(ui, egui_phosphor::regular::X, "Close Tab") {
-                                close_tab = Some(self.preview_active_tab);
+                                close_tabs = vec![self.preview_active_tab];
                                 ui.close_menu();
                             }
                         }
@@ -3867,45 +5690,62 @@ impl App {
                     } else {
                         "Preview not available"
                     };
+                    let mut msg_pos = rect.center();
+                    if !is_loading {
+                        if let Some(map) = self.maps.iter().find(|m| m.name == current_map) {
+                            let size = egui::vec2(rect.width().min(480.0), (rect.height() - 60.0).clamp(0.0, 270.0));
+                            let placeholder = egui::Rect::from_center_size(rect.center(), size);
+                            paint_map_placeholder(ui.painter(), placeholder, &map.name, &map.category, theme::RADIUS_DEFAULT);
+                            msg_pos = placeholder.center_bottom() + egui::vec2(0.0, 20.0);
+                        }
+                    }
                     ui.painter().text(
-                        rect.center(),
+                        msg_pos,
                         egui::Align2::CENTER_CENTER,
                         msg,
                         egui::FontId::proportional(14.0),
                         theme::TEXT_DIM,
                     );
+                    if is_loading {
+                        let spinner_rect =
+                            egui::Rect::from_center_size(rect.center() + egui::vec2(0.0, 28.0), egui::vec2(18.0, 18.0));
+                        ui.put(spinner_rect, egui::Spinner::new().color(theme::TEXT_DIM));
+                    }
+
+                    // No full image yet: the thumbnail alone can't be opened, so offer the fetch
+                    response.context_menu(|ui| {
+                        theme::set_menu_width(ui, &["Fetch full image"]);
+                        if is_loading {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(egui::RichText::new("Fetching full image...").color(theme::TEXT_DIM));
+                            });
+                        } else if theme::menu_item(ui, egui_phosphor::regular::DOWNLOAD_SIMPLE, "Fetch full image") {
+                            self.reload_full_preview(ui.ctx(), &current_map);
+                            ui.close_menu();
+                        }
+                    });
                 }
             });
 
-        // Click outside preview to close
-        if let Some(inner) = &win_resp {
-            if ctx.input(|i| i.pointer.any_pressed()) {
-                if let Some(pos) = ctx.input(|i| i.pointer.interact_pos()) {
-                    if !inner.response.rect.contains(pos) {
-                        close = true;
-                    }
-                }
-            }
+        // Clean click outside preview to close
+        if backdrop_clicked {
+            close = true;
         }
 
         // Handle tab close
-        if let Some(tab_idx) = close_tab {
-            let removed_name = self.preview_maps.remove(tab_idx);
-            self.preview_textures.remove(&removed_name);
-            self.preview_loading.remove(&removed_name);
-            if self.preview_active_tab >= self.preview_maps.len() && self.preview_active_tab > 0 {
-                self.preview_active_tab -= 1;
+        if !close_tabs.is_empty() {
+            self.close_preview_tabs(&close_tabs);
+        }
+
+        if let Some(name) = download_tab {
+            if let Some(idx) = self.maps.iter().position(|m| m.name == name) {
+                self.download_indices(ctx, vec![idx]);
             }
-            self.preview_zoom = 1.0;
-            self.preview_offset = egui::Vec2::ZERO;
-            self.preview_needs_fit = true;
         }
 
         if close {
-            self.preview_maps.clear();
-            self.preview_textures.clear();
-            self.preview_loading.clear();
-            self.preview_active_tab = 0;
+            self.close_preview();
         }
     }
 }
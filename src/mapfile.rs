@@ -0,0 +1,249 @@
+//! Reader for Teeworlds/DDNet datafiles (.map), just enough for the preview
+//! details: game layer size, image count and the info item (author, credits...).
+//! Every offset comes from the file itself, so all reads are bounds-checked and a
+//! malformed file yields an error instead of a panic or an oversized allocation.
+
+use flate2::read::ZlibDecoder;
+use std::io::Read;
+use std::path::Path;
+
+/// Metadata read from a downloaded map file
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MapFileInfo {
+    pub width: i32, // game layer, in tiles
+    pub height: i32,
+    pub file_size: u64,
+    pub images: usize,
+    pub author: Option<String>,
+    pub version: Option<String>,
+    pub credits: Option<String>,
+    pub license: Option<String>,
+}
+
+const ITEMTYPE_INFO: i32 = 1;
+const ITEMTYPE_IMAGE: i32 = 2;
+const ITEMTYPE_LAYER: i32 = 5;
+const LAYERTYPE_TILES: i32 = 2;
+const TILESLAYERFLAG_GAME: i32 = 1;
+
+const HEADER_SIZE: usize = 36;
+const MAX_FILE_SIZE: u64 = 64 * 1024 * 1024;
+const MAX_STRING_SIZE: usize = 64 * 1024;
+
+/// Parse the map at `path`
+pub fn read_info(path: &Path) -> Result<MapFileInfo, String> {
+    let file_size = std::fs::metadata(path).map_err(|e| e.to_string())?.len();
+    if file_size > MAX_FILE_SIZE {
+        return Err(format!("File too large ({} bytes)", file_size));
+    }
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    let datafile = Datafile::parse(&bytes)?;
+
+    let mut info = MapFileInfo {
+        width: 0,
+        height: 0,
+        file_size,
+        images: 0,
+        author: None,
+        version: None,
+        credits: None,
+        license: None,
+    };
+    for index in 0..datafile.item_offsets.len() {
+        let (item_type, data) = datafile.item(index)?;
+        let ints: Vec<i32> = data
+            .chunks_exact(4)
+            .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        match item_type {
+            ITEMTYPE_IMAGE => info.images += 1,
+            // Layer header (version, type, flags), then tilemap (version, width, height, flags, ...)
+            ITEMTYPE_LAYER if ints.len() >= 7 && ints[1] == LAYERTYPE_TILES && ints[6] & TILESLAYERFLAG_GAME != 0 => {
+                info.width = ints[4];
+                info.height = ints[5];
+            }
+            // (version, author, map version, credits, license, ...) as data indices, -1 if unset
+            ITEMTYPE_INFO if ints.len() >= 5 => {
+                info.author = datafile.string(ints[1]);
+                info.version = datafile.string(ints[2]);
+                info.credits = datafile.string(ints[3]);
+                info.license = datafile.string(ints[4]);
+            }
+            _ => {}
+        }
+    }
+    if info.width <= 0 || info.height <= 0 {
+        return Err("No game layer".to_string());
+    }
+    Ok(info)
+}
+
+struct Datafile<'a> {
+    version: i32,
+    item_offsets: Vec<usize>,
+    data_offsets: Vec<usize>,
+    data_sizes: Vec<usize>, // uncompressed sizes, version 4 only
+    items: &'a [u8],
+    data: &'a [u8],
+}
+
+fn read_i32(buf: &[u8], pos: usize) -> Result<i32, String> {
+    buf.get(pos..pos.checked_add(4).ok_or("Offset overflow")?)
+        .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| format!("Truncated at {}", pos))
+}
+
+/// A count or size field; negative or larger than the file is corrupt
+fn read_len(buf: &[u8], pos: usize) -> Result<usize, String> {
+    let value = read_i32(buf, pos)?;
+    usize::try_from(value)
+        .ok()
+        .filter(|&v| v <= buf.len())
+        .ok_or_else(|| format!("Invalid length {} at {}", value, pos))
+}
+
+fn read_table(buf: &[u8], pos: &mut usize, count: usize) -> Result<Vec<usize>, String> {
+    let table = (0..count)
+        .map(|i| read_len(buf, *pos + i * 4))
+        .collect::<Result<Vec<_>, _>>()?;
+    *pos += count * 4;
+    Ok(table)
+}
+
+fn slice(buf: &[u8], start: usize, len: usize) -> Result<&[u8], String> {
+    start
+        .checked_add(len)
+        .and_then(|end| buf.get(start..end))
+        .ok_or_else(|| format!("Section {}+{} outside file", start, len))
+}
+
+impl<'a> Datafile<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, String> {
+        let magic = bytes.get(0..4).ok_or("Not a map file")?;
+        if magic != b"DATA" && magic != b"ATAD" {
+            return Err("Not a map file".to_string());
+        }
+        let version = read_i32(bytes, 4)?;
+        if version != 3 && version != 4 {
+            return Err(format!("Unsupported datafile version {}", version));
+        }
+        let num_item_types = read_len(bytes, 16)?;
+        let num_items = read_len(bytes, 20)?;
+        let num_data = read_len(bytes, 24)?;
+        let item_size = read_len(bytes, 28)?;
+        let data_size = read_len(bytes, 32)?;
+
+        let mut pos = HEADER_SIZE + num_item_types * 12; // (type id, start, count) per type
+        let item_offsets = read_table(bytes, &mut pos, num_items)?;
+        let data_offsets = read_table(bytes, &mut pos, num_data)?;
+        let data_sizes = if version == 4 { read_table(bytes, &mut pos, num_data)? } else { Vec::new() };
+
+        let items = slice(bytes, pos, item_size)?;
+        let data = slice(bytes, pos + item_size, data_size)?;
+        Ok(Self { version, item_offsets, data_offsets, data_sizes, items, data })
+    }
+
+    /// Type and payload of item `index`
+    fn item(&self, index: usize) -> Result<(i32, &'a [u8]), String> {
+        let offset = self.item_offsets[index];
+        let type_and_id = read_i32(self.items, offset)?;
+        let size = read_len(self.items, offset + 4)?;
+        Ok(((type_and_id >> 16) & 0xffff, slice(self.items, offset + 8, size)?))
+    }
+
+    /// Contents of data block `index`, inflated for version 4 files
+    fn data(&self, index: usize) -> Option<Vec<u8>> {
+        let start = *self.data_offsets.get(index)?;
+        let end = self.data_offsets.get(index + 1).copied().unwrap_or(self.data.len());
+        let raw = self.data.get(start..end)?;
+        if self.version == 3 {
+            return Some(raw.to_vec());
+        }
+        let size = (*self.data_sizes.get(index)?).min(MAX_STRING_SIZE);
+        let mut out = Vec::with_capacity(size);
+        ZlibDecoder::new(raw).take(size as u64).read_to_end(&mut out).ok()?;
+        Some(out)
+    }
+
+    /// Null-terminated string stored in data block `index` (-1 means unset)
+    fn string(&self, index: i32) -> Option<String> {
+        let data = self.data(usize::try_from(index).ok()?)?;
+        let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+        let text = String::from_utf8_lossy(&data[..end]).trim().to_string();
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A version 3 datafile with a 40x30 game layer and an info item naming the author
+    fn sample() -> Vec<u8> {
+        let ints = |values: &[i32]| values.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<u8>>();
+        let mut layer = ints(&[ITEMTYPE_LAYER << 16, 7 * 4]);
+        layer.extend(ints(&[0, LAYERTYPE_TILES, 0, 0, 40, 30, TILESLAYERFLAG_GAME]));
+        let mut info = ints(&[ITEMTYPE_INFO << 16, 5 * 4]);
+        info.extend(ints(&[1, 0, -1, -1, -1]));
+        let data = b"tester\0".to_vec();
+
+        let mut bytes = b"DATA".to_vec();
+        bytes.extend(ints(&[3, 0, 0, 0, 2, 1, (layer.len() + info.len()) as i32, data.len() as i32]));
+        bytes.extend(ints(&[0, layer.len() as i32])); // item offsets
+        bytes.extend(ints(&[0])); // data offsets
+        bytes.extend(layer);
+        bytes.extend(info);
+        bytes.extend(data);
+        bytes
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Result<MapFileInfo, String> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.map");
+        std::fs::write(&path, bytes).unwrap();
+        read_info(&path)
+    }
+
+    #[test]
+    fn reads_a_well_formed_file() {
+        let info = read_bytes(&sample()).unwrap();
+        assert_eq!((info.width, info.height), (40, 30));
+        assert_eq!(info.author.as_deref(), Some("tester"));
+    }
+
+    #[test]
+    fn truncated_file_is_an_error() {
+        let bytes = sample();
+        for len in 0..bytes.len() {
+            assert!(read_bytes(&bytes[..len]).is_err(), "accepted a file cut to {} bytes", len);
+        }
+    }
+
+    #[test]
+    fn garbage_is_an_error() {
+        assert!(read_bytes(b"").is_err());
+        assert!(read_bytes(&[0xAB; 512]).is_err());
+        let mut bytes = b"DATA".to_vec();
+        bytes.extend((0..508).map(|i| (i * 37 % 251) as u8));
+        assert!(read_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn oversized_counts_are_an_error() {
+        // num_item_types, num_items, num_data, item_size, data_size
+        for field in [16, 20, 24, 28, 32] {
+            for value in [i32::MAX, -1, 1 << 20] {
+                let mut bytes = sample();
+                bytes[field..field + 4].copy_from_slice(&value.to_le_bytes());
+                assert!(read_bytes(&bytes).is_err(), "accepted {} at offset {}", value, field);
+            }
+        }
+    }
+
+    #[test]
+    fn out_of_range_item_offset_is_an_error() {
+        let mut bytes = sample();
+        bytes[HEADER_SIZE..HEADER_SIZE + 4].copy_from_slice(&1000i32.to_le_bytes());
+        assert!(read_bytes(&bytes).is_err());
+    }
+}
@@ -1,8 +1,12 @@
 //! User settings stored as settings.json in the app data directory
 
+use crate::constants::DEFAULT_CATEGORY_STARS;
+use crate::types::{CollisionStrategy, DateFormat, GridInfo, GroupBy, NotificationSound, RowDensity, SortColumn, SortDirection};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
 use std::path::{Path, PathBuf};
-use tracing::{debug, warn};
+use tracing::warn;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -12,6 +16,11 @@ pub struct Settings {
     pub window_y: Option<f32>,
     pub window_w: Option<f32>,
     pub window_h: Option<f32>,
+    pub window_maximized: bool,
+    // Size of the monitor the window was on; egui exposes no monitor name, so the
+    // size identifies it
+    pub window_monitor_w: Option<f32>,
+    pub window_monitor_h: Option<f32>,
 
     // Column visibility
     pub col_category: bool,
@@ -19,6 +28,9 @@ pub struct Settings {
     pub col_points: bool,
     pub col_author: bool,
     pub col_release_date: bool,
+    pub col_star_label: bool, // "3/5" after the star glyphs
+    // Grid card details; None until first saved, then seeded from the columns above
+    pub grid_info: Option<GridInfo>,
 
     // Column widths
     pub col_w_name: f32,
@@ -34,12 +46,82 @@ pub struct Settings {
     // View
     pub compact_view: bool,
     pub large_thumbnails: bool,
+    pub grid_card_scale: f32,
+    pub date_format: DateFormat,
+    pub row_density: RowDensity,
+    pub list_thumbnails: bool, // small thumbnail before each list row's name
+    pub auto_scroll_selection: bool, // arrow keys scroll the new selection into view
+    pub type_to_search: bool,        // typing anywhere goes to the search box
+    pub map_of_the_day: bool,        // featured map banner on the first start of a day
+    pub map_of_the_day_shown: Option<String>, // YYYY-MM-DD it last appeared
+    pub search_case_sensitive: bool,
+    pub search_whole_word: bool,
+    pub accent_color: [u8; 3], // RGB
+    // Animations; None follows the OS reduce-motion preference
+    pub smooth_scroll: Option<bool>,
+    pub button_press_animation: Option<bool>,
+    pub toast_fade: Option<bool>,
+
+    // Sort per view (list, grid); linked = shared between views
+    pub list_sort: (Option<SortColumn>, SortDirection),
+    pub grid_sort: (Option<SortColumn>, SortDirection),
+    pub link_view_sorts: bool,
+    pub group_by: GroupBy, // list view sections
+    pub group_search_matches: bool, // while searching, section results by match tier
+
+    // Filters
+    pub show_filters: bool,
+    pub category_mode_range: bool,
+    pub category_range: (u8, u8),
+    pub filter_categories: [bool; 8],
+    pub stars_mode_range: bool,
+    pub stars_range: (u8, u8),
+    pub filter_stars: [bool; 5],
+    pub per_category_stars: bool,
+    pub category_stars: [(u8, u8); 8], // star range per category, used when per_category_stars
+    pub year_mode_range: bool,
+    pub year_range: Option<(i32, i32)>,
+    pub filter_years: Option<Vec<i32>>, // None = all years
+    pub year_by_month: bool,                // range slider steps through months
+    pub month_range: Option<(i32, i32)>,    // YYYYMM bounds
+    pub include_undated: bool,
 
     // Paths
     pub download_path: Option<String>,
 
     // Audio
-    pub play_sound: bool,
+    pub play_sound: bool, // on batch complete
+    pub sound_on_failure: bool,
+    pub sound_on_db_update: bool,
+    pub notification_sound: NotificationSound,
+    pub custom_sound_path: Option<String>,
+    pub sound_volume: f32, // 0.0–1.0
+
+    // Downloads
+    pub max_retries: u32, // per map per batch before "Retry Failed" skips it
+    pub precheck_urls: bool, // HEAD each map URL before a batch
+    pub minimize_to_tray: bool, // hide to the tray on close while downloading
+    pub open_folder_after_download: bool,
+    pub post_download_command: Option<String>, // program run after each map, {path}/{name} placeholders; None = off
+    pub collision_strategy: CollisionStrategy, // when the map file already exists
+    pub confirm_downloads: bool, // summary dialog before large batches
+    pub download_companion_files: bool, // extra files listed next to a map in the manifest
+
+    // Map database
+    pub purge_removed_months: u32, // forget maps the manifest dropped after this long; 0 = keep
+
+    // Read-only JSON endpoint on 127.0.0.1 for overlays
+    pub local_server_enabled: bool,
+    pub local_server_port: u16,
+
+    // Logging (read by init_logging at startup)
+    pub log_level: String,
+
+    // Star directory per category in map URLs, e.g. {"Solo": ""} to omit it
+    pub url_star_dirs: HashMap<String, String>,
+
+    // Extra author spellings mapped to a canonical name, e.g. {"FauCry": "Fau"}
+    pub author_aliases: HashMap<String, String>,
 }
 
 impl Default for Settings {
@@ -49,11 +131,16 @@ impl Default for Settings {
             window_y: None,
             window_w: None,
             window_h: None,
+            window_maximized: false,
+            window_monitor_w: None,
+            window_monitor_h: None,
             col_category: true,
             col_stars: true,
             col_points: true,
             col_author: true,
             col_release_date: true,
+            col_star_label: false,
+            grid_info: None,
             col_w_name: 200.0,
             col_w_category: 80.0,
             col_w_stars: 90.0,
@@ -63,42 +150,184 @@ impl Default for Settings {
             col_order: vec![0, 1, 2, 3, 4, 5],
             compact_view: false,
             large_thumbnails: true,
+            grid_card_scale: 1.0,
+            date_format: DateFormat::default(),
+            row_density: RowDensity::Normal,
+            list_thumbnails: false,
+            auto_scroll_selection: true,
+            type_to_search: true,
+            map_of_the_day: true,
+            map_of_the_day_shown: None,
+            search_case_sensitive: false,
+            search_whole_word: false,
+            accent_color: [0x2d, 0xd4, 0xbf], // theme::DEFAULT_ACCENT
+            smooth_scroll: None,
+            button_press_animation: None,
+            toast_fade: None,
+            list_sort: (Some(SortColumn::Name), SortDirection::Ascending),
+            grid_sort: (Some(SortColumn::Name), SortDirection::Ascending),
+            link_view_sorts: false,
+            group_by: GroupBy::None,
+            group_search_matches: true,
+            show_filters: true,
+            category_mode_range: true,
+            category_range: (0, 4),
+            filter_categories: [true; 8],
+            stars_mode_range: true,
+            stars_range: (1, 5),
+            filter_stars: [true; 5],
+            per_category_stars: false,
+            category_stars: DEFAULT_CATEGORY_STARS,
+            year_mode_range: true,
+            year_range: None,
+            filter_years: None,
+            year_by_month: false,
+            month_range: None,
+            include_undated: true,
             download_path: None,
             play_sound: true,
+            sound_on_failure: false,
+            sound_on_db_update: false,
+            notification_sound: NotificationSound::default(),
+            custom_sound_path: None,
+            sound_volume: 0.8,
+            max_retries: 3,
+            precheck_urls: false,
+            minimize_to_tray: false,
+            open_folder_after_download: false,
+            post_download_command: None,
+            collision_strategy: CollisionStrategy::Skip,
+            confirm_downloads: true,
+            download_companion_files: true,
+            purge_removed_months: 0,
+            local_server_enabled: false,
+            local_server_port: 17832,
+            log_level: "debug".to_string(),
+            url_star_dirs: HashMap::new(),
+            author_aliases: HashMap::new(),
+        }
+    }
+}
+
+const SETTINGS_FILE: &str = "settings.json";
+const SETTINGS_BACKUP_FILE: &str = "settings.json.bak";
+const SETTINGS_TMP_FILE: &str = "settings.json.tmp";
+/// Copy of a settings.json that failed to parse, so hand edits aren't lost on the next save
+pub const SETTINGS_INVALID_FILE: &str = "settings.json.invalid";
+
+/// Log levels selectable in Settings, least to most verbose
+pub const LOG_LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// Which copy `Settings::load` used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsSource {
+    File,
+    Backup(Option<SettingsError>),     // settings.json missing or unusable (error), settings.json.bak worked
+    Defaults,                          // first launch, no file yet
+    DefaultsAfterError(SettingsError), // both copies unusable
+}
+
+/// Why settings.json couldn't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsError {
+    Parse { error: String, kept: bool }, // not valid settings; kept: copied to settings.json.invalid
+    Read(String),                        // the file itself couldn't be read
+}
+
+impl SettingsError {
+    /// What went wrong, to follow "Settings file"
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::Parse { .. } => "was invalid",
+            Self::Read(_) => "couldn't be read",
+        }
+    }
+
+    /// Where the user's edits went, as a sentence to append (empty if nowhere)
+    pub fn kept_note(&self) -> String {
+        match self {
+            Self::Parse { kept: true, .. } => format!(" Your edits were kept in {}", SETTINGS_INVALID_FILE),
+            _ => String::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse { error, .. } | Self::Read(error) => f.write_str(error),
         }
     }
 }
 
+/// Parse settings JSON as written by hand: a UTF-8 BOM (Notepad) is skipped,
+/// CRLF line endings are plain JSON whitespace, other encodings are rejected.
+fn parse(bytes: &[u8]) -> Result<Settings, String> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let text = std::str::from_utf8(bytes)
+        .map_err(|e| format!("not UTF-8 (invalid byte at offset {}), save it as UTF-8", e.valid_up_to()))?;
+    serde_json::from_str(text).map_err(|e| e.to_string())
+}
+
 impl Settings {
-    pub fn load(data_dir: &Path) -> Self {
-        let path = data_dir.join("settings.json");
-        match std::fs::read_to_string(&path) {
-            Ok(s) => match serde_json::from_str(&s) {
-                Ok(settings) => {
-                    debug!(path = %path.display(), "Settings loaded");
-                    settings
-                }
-                Err(e) => {
-                    warn!(error = %e, "Failed to parse settings, using defaults");
-                    Self::default()
+    /// Load settings.json, falling back to the backup kept by `save`, then defaults.
+    /// A file that fails to parse is copied to settings.json.invalid first.
+    pub fn load(data_dir: &Path) -> (Self, SettingsSource) {
+        // Runs before logging is initialized, so the caller reports the outcome
+        let path = data_dir.join(SETTINGS_FILE);
+        let error = match std::fs::read(&path) {
+            Ok(bytes) => match parse(&bytes) {
+                Ok(settings) => return (settings, SettingsSource::File),
+                Err(error) => {
+                    let kept = std::fs::write(data_dir.join(SETTINGS_INVALID_FILE), &bytes).is_ok();
+                    Some(SettingsError::Parse { error, kept })
                 }
             },
-            Err(_) => {
-                debug!("No settings file found, using defaults");
-                Self::default()
-            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => Some(SettingsError::Read(e.to_string())),
+        };
+
+        // A crash between save's two renames leaves only the backup, so try it even
+        // when the main file is simply missing
+        let backup = data_dir.join(SETTINGS_BACKUP_FILE);
+        if let Some(settings) = std::fs::read(&backup).ok().and_then(|b| parse(&b).ok()) {
+            return (settings, SettingsSource::Backup(error));
+        }
+
+        match error {
+            None => (Self::default(), SettingsSource::Defaults),
+            Some(e) => (Self::default(), SettingsSource::DefaultsAfterError(e)),
         }
     }
 
+    /// Write to a temp file and rename it over settings.json, so a crash mid-write
+    /// can't truncate it. The previous file is kept as settings.json.bak if it was valid.
     pub fn save(&self, data_dir: &Path) {
-        let path = data_dir.join("settings.json");
-        match serde_json::to_string_pretty(self) {
-            Ok(json) => {
-                if let Err(e) = std::fs::write(&path, json) {
-                    warn!(error = %e, "Failed to save settings");
-                }
+        let json = match serde_json::to_string_pretty(self) {
+            Ok(json) => json,
+            Err(e) => {
+                warn!(error = %e, "Failed to serialize settings");
+                return;
+            }
+        };
+        let path = data_dir.join(SETTINGS_FILE);
+        let tmp = data_dir.join(SETTINGS_TMP_FILE);
+        let result = (|| -> std::io::Result<()> {
+            let mut file = std::fs::File::create(&tmp)?;
+            file.write_all(json.as_bytes())?;
+            file.sync_all()?;
+            drop(file);
+
+            // Only a file that parses is worth keeping as the backup
+            let current_valid = std::fs::read(&path).ok().is_some_and(|b| parse(&b).is_ok());
+            if current_valid {
+                std::fs::rename(&path, data_dir.join(SETTINGS_BACKUP_FILE))?;
             }
-            Err(e) => warn!(error = %e, "Failed to serialize settings"),
+            std::fs::rename(&tmp, &path)
+        })();
+        if let Err(e) = result {
+            warn!(error = %e, "Failed to save settings");
+            let _ = std::fs::remove_file(&tmp);
         }
     }
 
@@ -115,3 +344,106 @@ impl Settings {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_default(settings: &Settings) -> bool {
+        serde_json::to_value(settings).unwrap() == serde_json::to_value(Settings::default()).unwrap()
+    }
+
+    #[test]
+    fn malformed_json_falls_back_to_defaults_and_keeps_a_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let bad = br#"{"download_path": "C:\\maps", "compact_view": tru"#;
+        std::fs::write(dir.path().join(SETTINGS_FILE), bad).unwrap();
+
+        let (settings, source) = Settings::load(dir.path());
+
+        assert!(is_default(&settings));
+        assert!(matches!(source, SettingsSource::DefaultsAfterError(SettingsError::Parse { kept: true, .. })));
+        assert_eq!(std::fs::read(dir.path().join(SETTINGS_INVALID_FILE)).unwrap(), bad);
+    }
+
+    #[test]
+    fn malformed_json_restores_the_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut saved = Settings::default();
+        saved.compact_view = !saved.compact_view;
+        std::fs::write(dir.path().join(SETTINGS_BACKUP_FILE), serde_json::to_vec(&saved).unwrap()).unwrap();
+        std::fs::write(dir.path().join(SETTINGS_FILE), b"{ not json").unwrap();
+
+        let (settings, source) = Settings::load(dir.path());
+
+        assert_eq!(settings.compact_view, saved.compact_view);
+        assert!(matches!(source, SettingsSource::Backup(Some(SettingsError::Parse { kept: true, .. }))));
+    }
+
+    #[test]
+    fn unreadable_file_is_a_read_error_without_a_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        // A directory where the file should be fails to read without being "not found"
+        std::fs::create_dir(dir.path().join(SETTINGS_FILE)).unwrap();
+
+        let (settings, source) = Settings::load(dir.path());
+
+        assert!(is_default(&settings));
+        let SettingsSource::DefaultsAfterError(error) = source else {
+            panic!("expected DefaultsAfterError, got {:?}", source);
+        };
+        assert!(matches!(error, SettingsError::Read(_)));
+        assert!(error.kept_note().is_empty());
+        assert!(!dir.path().join(SETTINGS_INVALID_FILE).exists());
+    }
+
+    #[test]
+    fn truncated_file_falls_back_to_the_previous_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut first = Settings::default();
+        first.compact_view = !first.compact_view;
+        first.save(dir.path());
+        let mut second = first.clone();
+        second.download_path = Some("maps".to_string());
+        second.save(dir.path());
+
+        // A write cut off halfway through the second save
+        let path = dir.path().join(SETTINGS_FILE);
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+
+        let (settings, source) = Settings::load(dir.path());
+
+        assert_eq!(settings.compact_view, first.compact_view);
+        assert_eq!(settings.download_path, None);
+        assert!(matches!(source, SettingsSource::Backup(Some(SettingsError::Parse { kept: true, .. }))));
+    }
+
+    #[test]
+    fn garbage_falls_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let garbage: Vec<u8> = (0..=255u8).rev().collect();
+        std::fs::write(dir.path().join(SETTINGS_FILE), &garbage).unwrap();
+
+        let (settings, source) = Settings::load(dir.path());
+
+        assert!(is_default(&settings));
+        assert!(matches!(source, SettingsSource::DefaultsAfterError(SettingsError::Parse { kept: true, .. })));
+        assert_eq!(std::fs::read(dir.path().join(SETTINGS_INVALID_FILE)).unwrap(), garbage);
+    }
+
+    #[test]
+    fn missing_file_uses_the_backup_left_by_an_interrupted_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut saved = Settings::default();
+        saved.compact_view = !saved.compact_view;
+        saved.save(dir.path());
+        saved.save(dir.path());
+        std::fs::remove_file(dir.path().join(SETTINGS_FILE)).unwrap();
+
+        let (settings, source) = Settings::load(dir.path());
+
+        assert_eq!(settings.compact_view, saved.compact_view);
+        assert!(matches!(source, SettingsSource::Backup(None)));
+    }
+}
@@ -0,0 +1,70 @@
+//! Notification sounds (rodio on Windows; a no-op elsewhere).
+//! Each sound opens the audio device on its own short-lived thread,
+//! so no audio resources are held while idle.
+
+use crate::types::NotificationSound;
+use std::path::{Path, PathBuf};
+
+const CHIME: &[u8] = include_bytes!("../assets/sounds/chime.wav");
+const PING: &[u8] = include_bytes!("../assets/sounds/ping.wav");
+const POP: &[u8] = include_bytes!("../assets/sounds/pop.wav");
+const FAILURE: &[u8] = include_bytes!("../assets/sounds/error.wav");
+
+/// Audio to play: a bundled clip or a user-provided .wav/.ogg
+#[cfg_attr(not(windows), allow(dead_code))]
+pub enum SoundSource {
+    Bundled(&'static [u8]),
+    File(PathBuf),
+}
+
+impl SoundSource {
+    /// The sound picked in Settings. A custom choice without a file falls back to the chime.
+    pub fn for_choice(choice: NotificationSound, custom_path: Option<&Path>) -> Self {
+        match choice {
+            NotificationSound::Chime => Self::Bundled(CHIME),
+            NotificationSound::Ping => Self::Bundled(PING),
+            NotificationSound::Pop => Self::Bundled(POP),
+            NotificationSound::Custom => match custom_path {
+                Some(path) => Self::File(path.to_path_buf()),
+                None => Self::Bundled(CHIME),
+            },
+        }
+    }
+
+    /// Distinct sound for failed downloads
+    pub fn failure() -> Self {
+        Self::Bundled(FAILURE)
+    }
+}
+
+/// Play `source` at `volume` (0.0–1.0) without blocking the caller
+pub fn play(source: SoundSource, volume: f32) {
+    #[cfg(windows)]
+    std::thread::spawn(move || {
+        if let Err(e) = play_blocking(source, volume) {
+            tracing::warn!(error = %e, "Failed to play notification sound");
+        }
+    });
+    #[cfg(not(windows))]
+    let _ = (source, volume);
+}
+
+#[cfg(windows)]
+fn play_blocking(source: SoundSource, volume: f32) -> Result<(), String> {
+    let (_stream, handle) = rodio::OutputStream::try_default().map_err(|e| e.to_string())?;
+    let sink = rodio::Sink::try_new(&handle).map_err(|e| e.to_string())?;
+    sink.set_volume(volume.clamp(0.0, 1.0));
+    match source {
+        SoundSource::Bundled(bytes) => {
+            let decoder = rodio::Decoder::new(std::io::Cursor::new(bytes)).map_err(|e| e.to_string())?;
+            sink.append(decoder);
+        }
+        SoundSource::File(path) => {
+            let file = std::fs::File::open(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            let decoder = rodio::Decoder::new(std::io::BufReader::new(file)).map_err(|e| e.to_string())?;
+            sink.append(decoder);
+        }
+    }
+    sink.sleep_until_end();
+    Ok(())
+}
@@ -0,0 +1,105 @@
+//! Windows taskbar progress for download batches (ITaskbarList3).
+//! On other platforms this is a no-op.
+
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+#[cfg(windows)]
+use windows::Win32::{
+    Foundation::HWND,
+    System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED},
+    UI::Shell::{ITaskbarList3, TaskbarList, TBPF_ERROR, TBPF_NOPROGRESS, TBPF_NORMAL},
+};
+
+/// Minimum time between taskbar updates (COM calls aren't free)
+const UPDATE_INTERVAL: Duration = Duration::from_millis(250);
+
+pub struct TaskbarProgress {
+    #[cfg(windows)]
+    taskbar: Option<(ITaskbarList3, HWND)>,
+    last_sent: Option<(u64, bool)>, // (permille, error)
+    last_update: Instant,
+}
+
+impl TaskbarProgress {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        #[cfg(not(windows))]
+        let _ = cc;
+        Self {
+            #[cfg(windows)]
+            taskbar: Self::init(cc),
+            last_sent: None,
+            last_update: Instant::now(),
+        }
+    }
+
+    #[cfg(windows)]
+    fn init(cc: &eframe::CreationContext<'_>) -> Option<(ITaskbarList3, HWND)> {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+        let RawWindowHandle::Win32(handle) = cc.window_handle().ok()?.as_raw() else {
+            return None;
+        };
+        let hwnd = HWND(handle.hwnd.get() as *mut _);
+        unsafe {
+            // winit has usually initialized COM already; either result is fine
+            let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+            let list: ITaskbarList3 = match CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER) {
+                Ok(list) => list,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Taskbar progress unavailable");
+                    return None;
+                }
+            };
+            list.HrInit().ok()?;
+            Some((list, hwnd))
+        }
+    }
+
+    /// Show `done / total` progress, red when `error` is set. Throttled.
+    pub fn set(&mut self, done: u64, total: u64, error: bool, ctx: &egui::Context) {
+        let permille = (done.min(total) * 1000).checked_div(total).unwrap_or(0);
+        let state = Some((permille, error));
+        if state == self.last_sent {
+            return;
+        }
+        // State changes (e.g. first failure) go through immediately
+        let error_changed = self.last_sent.map(|(_, e)| e) != Some(error);
+        if !error_changed && self.last_update.elapsed() < UPDATE_INTERVAL {
+            ctx.request_repaint_after(UPDATE_INTERVAL);
+            return;
+        }
+        self.apply(state);
+        self.last_sent = state;
+        self.last_update = Instant::now();
+    }
+
+    /// Remove the progress indicator
+    pub fn clear(&mut self) {
+        if self.last_sent.is_some() {
+            self.apply(None);
+            self.last_sent = None;
+        }
+    }
+
+    #[cfg(windows)]
+    fn apply(&self, state: Option<(u64, bool)>) {
+        let Some((list, hwnd)) = &self.taskbar else {
+            return;
+        };
+        unsafe {
+            match state {
+                Some((permille, error)) => {
+                    let _ = list.SetProgressState(*hwnd, if error { TBPF_ERROR } else { TBPF_NORMAL });
+                    let _ = list.SetProgressValue(*hwnd, permille, 1000);
+                }
+                None => {
+                    let _ = list.SetProgressState(*hwnd, TBPF_NOPROGRESS);
+                }
+            }
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn apply(&self, _state: Option<(u64, bool)>) {}
+}
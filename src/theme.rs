@@ -2,6 +2,8 @@
 //! All colors, sizes, and styling should reference these constants
 
 use egui::Color32;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
 
 // =============================================================================
 // COLORS - Backgrounds
@@ -10,15 +12,127 @@ pub const BG_BASE: Color32 = Color32::from_rgb(0x09, 0x09, 0x0b); // zinc-950
 pub const BG_ELEVATED: Color32 = Color32::from_rgb(0x18, 0x18, 0x1b); // zinc-900
 pub const BG_INPUT: Color32 = Color32::from_rgb(0x14, 0x14, 0x18); // input field background
 pub const BG_SURFACE: Color32 = Color32::from_rgb(0x27, 0x27, 0x2a); // zinc-800
-pub const BG_HOVER: Color32 = Color32::from_rgb(0x0f, 0x1a, 0x19); // subtle teal hover
 pub const BG_HOVER_SUBTLE: Color32 = Color32::from_rgb(0x1f, 0x1f, 0x22); // subtle hover
 
 // =============================================================================
-// COLORS - Accent (Teal)
+// COLORS - Accent (user-configurable, teal by default)
 // =============================================================================
-pub const ACCENT: Color32 = Color32::from_rgb(0x2d, 0xd4, 0xbf); // teal-400
-pub const ACCENT_MUTED: Color32 = Color32::from_rgba_premultiplied(0x1F, 0x95, 0x86, 0xB3); // teal-400 70% alpha
-pub const ACCENT_LIGHT: Color32 = Color32::from_rgb(0x5e, 0xea, 0xd4); // teal-300
+// The accent family is derived at runtime from one color, so these are
+// functions rather than consts. Change it with `set_accent`.
+pub const DEFAULT_ACCENT: Color32 = Color32::from_rgb(0x2d, 0xd4, 0xbf); // teal-400
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct AccentPalette {
+    pub accent: Color32,    // text highlights, checkboxes, slider heads, accent buttons
+    pub muted: Color32,     // accent @ 70% alpha
+    pub hover: Color32,     // accent button hover
+    pub active: Color32,    // accent button pressed
+    pub on_accent: Color32, // text drawn on accent fills
+    pub strong: Color32,    // selected filter buttons, slider trail
+    pub glow: Color32,      // segmented toggle glow
+    pub tint: Color32,      // very dark tint for row selection/hover
+    pub scroll_active: Color32, // current scroll index marker
+}
+
+/// Hand-picked Tailwind teal shades, used when the accent is left at its default
+const TEAL_PALETTE: AccentPalette = AccentPalette {
+    accent: DEFAULT_ACCENT,
+    muted: Color32::from_rgba_premultiplied(0x1F, 0x95, 0x86, 0xB3), // teal-400 70% alpha
+    hover: Color32::from_rgb(0x14, 0xb8, 0xa6),                      // teal-500
+    active: Color32::from_rgb(0x0d, 0x94, 0x88),                     // teal-600
+    on_accent: Color32::from_rgb(0x04, 0x2f, 0x2e),                  // teal-950
+    strong: Color32::from_rgb(0x11, 0x5e, 0x59),                     // teal-800
+    glow: Color32::from_rgb(0x0f, 0x76, 0x6e),                       // teal-700
+    tint: Color32::from_rgb(0x0f, 0x1a, 0x19),
+    scroll_active: Color32::from_rgba_premultiplied(0x0e, 0x42, 0x3b, 80), // teal-400 @ 31%
+};
+
+static PALETTE: RwLock<AccentPalette> = RwLock::new(TEAL_PALETTE);
+
+/// Derive the accent family from a single color, approximating the
+/// relationships between the default teal shades.
+pub fn accent_palette(accent: Color32) -> AccentPalette {
+    if accent == DEFAULT_ACCENT {
+        return TEAL_PALETTE;
+    }
+    let scale = |f: f32| {
+        Color32::from_rgb(
+            (accent.r() as f32 * f) as u8,
+            (accent.g() as f32 * f) as u8,
+            (accent.b() as f32 * f) as u8,
+        )
+    };
+    let luminance =
+        0.299 * accent.r() as f32 + 0.587 * accent.g() as f32 + 0.114 * accent.b() as f32;
+    AccentPalette {
+        accent,
+        muted: with_alpha(accent, 0xB3),
+        hover: scale(0.87),
+        active: scale(0.70),
+        // Dark text on bright accents, white on dark ones
+        on_accent: if luminance > 140.0 { scale(0.18) } else { TEXT_PRIMARY },
+        strong: scale(0.44),
+        glow: scale(0.56),
+        tint: lighten(scale(0.08), 0.03),
+        scroll_active: with_alpha(accent, 80),
+    }
+}
+
+/// Switch the accent color and re-apply global visuals
+pub fn set_accent(ctx: &egui::Context, accent: Color32) {
+    *PALETTE.write().unwrap() = accent_palette(accent);
+    apply_visuals(ctx);
+    ctx.request_repaint();
+}
+
+fn palette() -> AccentPalette {
+    *PALETTE.read().unwrap()
+}
+
+pub fn accent() -> Color32 {
+    palette().accent
+}
+pub fn accent_muted() -> Color32 {
+    palette().muted
+}
+pub fn on_accent() -> Color32 {
+    palette().on_accent
+}
+pub fn bg_hover() -> Color32 {
+    palette().tint
+}
+pub fn table_row_selected() -> Color32 {
+    palette().tint
+}
+pub fn selection_scroll_active() -> Color32 {
+    palette().scroll_active
+}
+pub fn slider_head() -> Color32 {
+    palette().accent
+}
+pub fn slider_trail() -> Color32 {
+    palette().strong
+}
+pub fn toggle_selected() -> Color32 {
+    palette().strong
+}
+pub fn toggle_glow() -> Color32 {
+    palette().glow
+}
+pub fn btn_accent() -> Color32 {
+    palette().accent
+}
+pub fn btn_accent_hover() -> Color32 {
+    palette().hover
+}
+pub fn btn_accent_active() -> Color32 {
+    palette().active
+}
+
+/// Same color with a straight (unmultiplied) alpha
+pub fn with_alpha(c: Color32, alpha: u8) -> Color32 {
+    Color32::from_rgba_unmultiplied(c.r(), c.g(), c.b(), alpha)
+}
 
 // =============================================================================
 // COLORS - Text
@@ -39,8 +153,6 @@ pub const BORDER_STRONG: Color32 = Color32::from_rgb(0x52, 0x52, 0x5b); // zinc-
 // COLORS - Selection
 // =============================================================================
 // Note: Selection color defined in apply_visuals() due to alpha
-pub const TABLE_ROW_SELECTED: Color32 = Color32::from_rgb(0x0f, 0x1a, 0x19); // teal selection for table rows
-pub const SELECTION_SCROLL_ACTIVE: Color32 = Color32::from_rgba_premultiplied(0x0e, 0x42, 0x3b, 80); // teal-400 @ 31%
 // =============================================================================
 // COLORS - Status
 // =============================================================================
@@ -57,15 +169,11 @@ pub const STAR_EMPTY: Color32 = Color32::from_rgb(0x4b, 0x4b, 0x5c);
 // =============================================================================
 // COLORS - Sliders
 // =============================================================================
-pub const SLIDER_HEAD: Color32 = Color32::from_rgb(0x2d, 0xd4, 0xbf); // teal-400
-pub const SLIDER_TRAIL: Color32 = Color32::from_rgb(0x11, 0x5e, 0x59); // teal-800
 
 // =============================================================================
 // COLORS - Filter/Toggle Selection
 // =============================================================================
-pub const TOGGLE_SELECTED: Color32 = Color32::from_rgb(0x11, 0x5e, 0x59); // teal-800 - selected filter buttons
 pub const TOGGLE_UNSELECTED: Color32 = Color32::from_rgb(0x27, 0x27, 0x2a); // zinc-800 - unselected filter buttons
-pub const TOGGLE_GLOW: Color32 = Color32::from_rgb(0x0f, 0x76, 0x6e); // teal glow for segmented toggles
 
 // =============================================================================
 // COLORS - Buttons
@@ -75,10 +183,7 @@ pub const BTN_DEFAULT: Color32 = Color32::from_rgb(0x3f, 0x3f, 0x46); // zinc-70
 pub const BTN_DEFAULT_HOVER: Color32 = Color32::from_rgb(0x52, 0x52, 0x5b); // zinc-600
 pub const BTN_DEFAULT_ACTIVE: Color32 = Color32::from_rgb(0x27, 0x27, 0x2a); // zinc-800
 
-// Accent (teal) button
-pub const BTN_ACCENT: Color32 = Color32::from_rgb(0x2d, 0xd4, 0xbf); // teal-400
-pub const BTN_ACCENT_HOVER: Color32 = Color32::from_rgb(0x14, 0xb8, 0xa6); // teal-500
-pub const BTN_ACCENT_ACTIVE: Color32 = Color32::from_rgb(0x0d, 0x94, 0x88); // teal-600
+// Accent button colors: see btn_accent() and friends above
 
 // Danger (red) button
 pub const BTN_DANGER: Color32 = Color32::from_rgb(0xdc, 0x26, 0x26); // red-600
@@ -173,6 +278,8 @@ pub const SLIDER_RAIL_HEIGHT: f32 = 4.0;
 // =============================================================================
 pub const CARD_SMALL: (f32, f32) = (180.0, 80.0);
 pub const CARD_LARGE: (f32, f32) = (360.0, 160.0);
+pub const CARD_SCALE_MIN: f32 = 0.6;
+pub const CARD_SCALE_MAX: f32 = 1.6;
 
 // =============================================================================
 // DIMENSIONS - Preview
@@ -216,7 +323,7 @@ pub fn apply_visuals(ctx: &egui::Context) {
         window_fill: Color32::from_rgb(0x1a, 0x1a, 0x1e), // Slightly elevated for popups/menus
         extreme_bg_color: BG_BASE,
         faint_bg_color: BG_ELEVATED,
-        hyperlink_color: ACCENT,
+        hyperlink_color: accent(),
         selection: egui::style::Selection {
             bg_fill: Color32::from_rgb(0x3a, 0x3a, 0x3f), // Neutral gray selection (for text highlighting)
             stroke: egui::Stroke::NONE,
@@ -239,7 +346,7 @@ pub fn apply_visuals(ctx: &egui::Context) {
                 expansion: 0.0,
             },
             hovered: egui::style::WidgetVisuals {
-                bg_fill: BG_HOVER,
+                bg_fill: bg_hover(),
                 weak_bg_fill: Color32::from_rgb(0x30, 0x30, 0x35),
                 bg_stroke: egui::Stroke::NONE,
                 fg_stroke: egui::Stroke::new(STROKE_MEDIUM, TEXT_PRIMARY),
@@ -337,8 +444,8 @@ pub fn button(text: impl Into<String>) -> egui::Button<'static> {
 
 /// Accent teal button (for primary actions like Download)
 pub fn button_accent(text: impl Into<String>) -> egui::Button<'static> {
-    egui::Button::new(egui::RichText::new(text.into()).color(Color32::from_rgb(0x04, 0x2f, 0x2e)))
-        .fill(BTN_ACCENT)
+    egui::Button::new(egui::RichText::new(text.into()).color(on_accent()))
+        .fill(btn_accent())
         .corner_radius(RADIUS_DEFAULT)
 }
 
@@ -396,8 +503,8 @@ pub fn settings_checkbox(ui: &mut egui::Ui, checked: bool, label: &str, enabled:
         egui::vec2(cb_size, cb_size),
     );
     if checked {
-        painter.rect_stroke(cb_rect, 3.0, egui::Stroke::new(1.5, ACCENT), egui::StrokeKind::Inside);
-        painter.rect_filled(cb_rect.shrink(3.0), 2.0, ACCENT);
+        painter.rect_stroke(cb_rect, 3.0, egui::Stroke::new(1.5, accent()), egui::StrokeKind::Inside);
+        painter.rect_filled(cb_rect.shrink(3.0), 2.0, accent());
     } else {
         painter.rect_stroke(cb_rect, 3.0, egui::Stroke::new(1.5, BORDER_DEFAULT), egui::StrokeKind::Inside);
     }
@@ -409,9 +516,178 @@ pub fn settings_checkbox(ui: &mut egui::Ui, checked: bool, label: &str, enabled:
         egui::FontId::proportional(14.0),
         color,
     );
+    focus_ring(ui, &row_resp, cb_rect, 3.0);
+    row_resp.widget_info(|| {
+        egui::WidgetInfo::selected(egui::WidgetType::Checkbox, enabled, checked, label)
+    });
     enabled && row_resp.clicked()
 }
 
+// =============================================================================
+// ACCESSIBILITY - custom-painted widgets
+// =============================================================================
+//
+// Painted controls describe themselves via `widget_info` so accesskit can
+// announce them, and draw `focus_ring` so Tab focus is visible:
+// - Toggle buttons and checkbox rows: Tab to focus, Space/Enter to toggle
+// - Range slider handles: Tab to each handle, arrows step, Home/End jump to the limits
+
+/// Outline a custom-painted widget while it has keyboard focus
+pub fn focus_ring(ui: &egui::Ui, response: &egui::Response, rect: egui::Rect, rounding: f32) {
+    if response.has_focus() {
+        ui.painter().rect_stroke(
+            rect.expand(1.0),
+            rounding,
+            egui::Stroke::new(1.5, accent()),
+            egui::StrokeKind::Outside,
+        );
+    }
+}
+
+/// Small match count at the right edge of a painted filter button
+pub fn filter_count(ui: &egui::Ui, rect: egui::Rect, count: usize) {
+    let text = if count >= 1000 {
+        format!("{:.1}k", count as f32 / 1000.0)
+    } else {
+        count.to_string()
+    };
+    let alpha = if count == 0 { 70 } else { 150 };
+    ui.painter().text(
+        rect.right_center() - egui::vec2(5.0, 0.0),
+        egui::Align2::RIGHT_CENTER,
+        text,
+        egui::FontId::proportional(9.0),
+        with_alpha(Color32::WHITE, alpha),
+    );
+}
+
+/// Describe a painted on/off button (filter toggles, list rows, grid cards)
+pub fn toggle_info(response: &egui::Response, label: &str, selected: bool) {
+    let enabled = response.enabled();
+    response.widget_info(|| {
+        egui::WidgetInfo::selected(egui::WidgetType::SelectableLabel, enabled, selected, label)
+    });
+}
+
+/// Small text toggle inside the search box ("Aa", "ab|"). Returns true if clicked.
+pub fn search_toggle(ui: &mut egui::Ui, text: &str, active: bool, label: &str, tooltip: &str) -> bool {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(22.0, 18.0), egui::Sense::click());
+    if response.hovered() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    }
+    if active {
+        ui.painter().rect_filled(rect, 3.0, with_alpha(accent(), 40));
+    } else if response.hovered() {
+        ui.painter().rect_filled(rect, 3.0, BORDER_SUBTLE);
+    }
+    let color = if active { accent() } else { TEXT_DIM };
+    ui.painter().text(rect.center(), egui::Align2::CENTER_CENTER, text, egui::FontId::proportional(11.0), color);
+    focus_ring(ui, &response, rect, 3.0);
+    toggle_info(&response, label, active);
+    let clicked = response.clicked();
+    response.on_hover_text(tooltip);
+    clicked
+}
+
+/// Keyboard and screen reader access for one handle of a dual-handle range slider.
+/// `step` is the handle's position within `limits`; `value_text` is what gets announced.
+/// Returns the new step when a key moved the handle.
+pub fn range_handle(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    center: egui::Pos2,
+    step: usize,
+    limits: std::ops::RangeInclusive<usize>,
+    label: &str,
+    value_text: &str,
+) -> Option<usize> {
+    let id = ui.id().with(id_salt);
+    let rect = egui::Rect::from_center_size(center, egui::vec2(16.0, 16.0));
+    // Focus only: pointer input keeps going to the track underneath
+    let response = ui.interact(rect, id, egui::Sense::focusable_noninteractive());
+    let enabled = ui.is_enabled();
+    response.widget_info(|| {
+        let mut info = egui::WidgetInfo::slider(enabled, step as f64, label);
+        info.current_text_value = Some(value_text.to_string());
+        info
+    });
+    if !response.has_focus() {
+        return None;
+    }
+
+    // Keep arrow keys from moving focus to a neighbouring widget
+    ui.memory_mut(|mem| {
+        mem.set_focus_lock_filter(
+            id,
+            egui::EventFilter {
+                horizontal_arrows: true,
+                vertical_arrows: true,
+                ..Default::default()
+            },
+        )
+    });
+    ui.painter()
+        .circle_stroke(center, 10.0, egui::Stroke::new(1.5, accent()));
+
+    let (lo, hi) = (*limits.start(), *limits.end());
+    let new_step = ui.input(|i| {
+        if i.key_pressed(egui::Key::Home) {
+            lo
+        } else if i.key_pressed(egui::Key::End) {
+            hi
+        } else if i.key_pressed(egui::Key::ArrowLeft) || i.key_pressed(egui::Key::ArrowDown) {
+            step.saturating_sub(1).max(lo)
+        } else if i.key_pressed(egui::Key::ArrowRight) || i.key_pressed(egui::Key::ArrowUp) {
+            (step + 1).min(hi)
+        } else {
+            step
+        }
+    });
+    (new_step != step).then_some(new_step)
+}
+
+/// Pointer dragging for a dual-handle range slider whose track spans `steps` steps.
+/// The handle is picked when the drag starts (the nearer one; when both sit on the
+/// same step, the direction of the first movement) and stays grabbed for the whole
+/// drag. Pulled past the other handle, it swaps roles and keeps going.
+/// Returns the new (lo, hi) steps when they changed.
+pub fn range_drag(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    track_left: f32,
+    track_width: f32,
+    steps: usize,
+    lo: usize,
+    hi: usize,
+) -> Option<(usize, usize)> {
+    let id = response.id.with("grabbed_max");
+    if !response.dragged() || steps == 0 {
+        ui.data_mut(|d| d.remove::<bool>(id));
+        return None;
+    }
+    let pos = response.interact_pointer_pos()?;
+    let step = (((pos.x - track_left) / track_width).clamp(0.0, 1.0) * steps as f32).round() as usize;
+
+    let grabbed_max = match ui.data(|d| d.get_temp::<bool>(id)) {
+        Some(grabbed) => grabbed,
+        // Stacked handles: wait until the pointer leaves their step
+        None if lo == hi && step == lo => return None,
+        None if lo == hi => step > hi,
+        None => {
+            let x = |s: usize| track_left + s as f32 / steps as f32 * track_width;
+            (pos.x - x(hi)).abs() < (pos.x - x(lo)).abs()
+        }
+    };
+    let (new, grabbed_max) = match grabbed_max {
+        true if step < lo => ((step, lo), false),
+        true => ((lo, step), true),
+        false if step > hi => ((hi, step), true),
+        false => ((step, hi), false),
+    };
+    ui.data_mut(|d| d.insert_temp(id, grabbed_max));
+    (new != (lo, hi)).then_some(new)
+}
+
 /// Danger red button (for destructive actions like Cancel)
 pub fn button_danger(text: impl Into<String>) -> egui::Button<'static> {
     egui::Button::new(egui::RichText::new(text.into()).color(TEXT_PRIMARY))
@@ -420,17 +696,28 @@ pub fn button_danger(text: impl Into<String>) -> egui::Button<'static> {
 }
 
 
+static PRESS_ANIMATION: AtomicBool = AtomicBool::new(true);
+
+/// Toggle the pressed-button shrink (off for reduced motion)
+pub fn set_press_animation(enabled: bool) {
+    PRESS_ANIMATION.store(enabled, Ordering::Relaxed);
+}
+
 /// Returns (fill, draw_rect) for a custom-painted button with hover/press effects.
-/// Lightens on hover, slightly lightens + shrinks on press.
+/// Lightens on hover, slightly lightens + shrinks on press; accent buttons use the
+/// palette's hover and pressed shades instead.
 pub fn button_visual(
     response: &egui::Response,
     base_fill: Color32,
     rect: egui::Rect,
 ) -> (Color32, egui::Rect) {
+    let accent = base_fill == btn_accent();
     if response.is_pointer_button_down_on() {
-        (lighten(base_fill, 0.06), rect.shrink(1.5))
+        let shrink = if PRESS_ANIMATION.load(Ordering::Relaxed) { 1.5 } else { 0.0 };
+        let fill = if accent { btn_accent_active() } else { lighten(base_fill, 0.06) };
+        (fill, rect.shrink(shrink))
     } else if response.hovered() {
-        (lighten(base_fill, 0.12), rect)
+        (if accent { btn_accent_hover() } else { lighten(base_fill, 0.12) }, rect)
     } else {
         (base_fill, rect)
     }
@@ -495,8 +782,8 @@ pub fn segmented_toggle_style1(
     let painter = ui.painter();
 
     let container_color = TOGGLE_UNSELECTED;
-    let active_color = TOGGLE_SELECTED;
-    let glow_color = TOGGLE_GLOW;
+    let active_color = toggle_selected();
+    let glow_color = toggle_glow();
     let inactive_text = TEXT_MUTED;
 
     // Layer 1: Container background
@@ -564,12 +851,28 @@ pub fn segmented_toggle_style1(
     if response.hovered() {
         ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
     }
+    focus_ring(ui, &response, rect, rounding + 2.0);
+    let current = if *left_active { left_label } else { right_label };
+    response.widget_info(|| {
+        egui::WidgetInfo::labeled(
+            egui::WidgetType::Button,
+            true,
+            format!("{} / {}: {}", left_label, right_label, current),
+        )
+    });
 
     if response.clicked() {
-        if let Some(pos) = response.interact_pointer_pos() {
-            let clicked_left = pos.x < rect.min.x + left_width;
-            if clicked_left != *left_active {
-                *left_active = clicked_left;
+        match response.interact_pointer_pos() {
+            Some(pos) => {
+                let clicked_left = pos.x < rect.min.x + left_width;
+                if clicked_left != *left_active {
+                    *left_active = clicked_left;
+                    changed = true;
+                }
+            }
+            // Space/Enter while focused flips to the other side
+            None => {
+                *left_active = !*left_active;
                 changed = true;
             }
         }
@@ -598,8 +901,8 @@ pub fn segmented_toggle_style2(
     let painter = ui.painter();
 
     let container_color = TOGGLE_UNSELECTED;
-    let active_color = TOGGLE_SELECTED;
-    let glow_color = TOGGLE_GLOW;
+    let active_color = toggle_selected();
+    let glow_color = toggle_glow();
     let inactive_text = TEXT_MUTED;
 
     painter.rect_filled(rect, rounding, container_color);
@@ -673,8 +976,8 @@ pub fn segmented_toggle_style3(
     let painter = ui.painter();
 
     let container_color = TOGGLE_UNSELECTED;
-    let active_color = TOGGLE_SELECTED;
-    let glow_color = TOGGLE_GLOW;
+    let active_color = toggle_selected();
+    let glow_color = toggle_glow();
     let inactive_text = TEXT_MUTED;
 
     painter.rect_filled(rect, rounding, container_color);
@@ -0,0 +1,243 @@
+//! System tray icon (Windows) so downloads can keep running with the window hidden.
+//! On other platforms this is a no-op and minimize-to-tray is unavailable.
+
+use std::sync::mpsc::Receiver;
+
+#[cfg(windows)]
+use eframe::egui;
+#[cfg(windows)]
+use tray_icon::{
+    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent,
+};
+
+/// Actions picked from the tray icon or its menu
+#[cfg_attr(not(windows), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayCommand {
+    Open,
+    TogglePause,
+    SyncDatabase,
+    Quit,
+}
+
+#[cfg(windows)]
+struct TrayInner {
+    icon: TrayIcon,
+    pause_item: MenuItem,
+}
+
+/// Fixed menu ids, so the process-wide event handler (settable only once)
+/// still matches after the icon is removed and created again
+#[cfg(windows)]
+const MENU_IDS: [(&str, TrayCommand); 4] = [
+    ("tray_open", TrayCommand::Open),
+    ("tray_pause", TrayCommand::TogglePause),
+    ("tray_sync", TrayCommand::SyncDatabase),
+    ("tray_quit", TrayCommand::Quit),
+];
+
+pub struct Tray {
+    #[cfg(windows)]
+    inner: Option<TrayInner>,
+    commands: Option<Receiver<TrayCommand>>,
+    last_tooltip: String,
+    last_paused: bool,
+}
+
+impl Tray {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        #[cfg(not(windows))]
+        let _ = cc;
+        Self {
+            #[cfg(windows)]
+            inner: None,
+            #[cfg(windows)]
+            commands: Some(Self::install_handlers(&cc.egui_ctx, Self::window_handle(cc))),
+            #[cfg(not(windows))]
+            commands: None,
+            last_tooltip: String::new(),
+            last_paused: false,
+        }
+    }
+
+    #[cfg(windows)]
+    fn window_handle(cc: &eframe::CreationContext<'_>) -> Option<isize> {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+        let RawWindowHandle::Win32(handle) = cc.window_handle().ok()?.as_raw() else {
+            return None;
+        };
+        Some(handle.hwnd.get())
+    }
+
+    /// Events arrive on the event loop thread; forward them and wake the UI.
+    /// "Open" also shows the window right here, since a hidden window may not get frames.
+    #[cfg(windows)]
+    fn install_handlers(ctx: &egui::Context, hwnd: Option<isize>) -> Receiver<TrayCommand> {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let menu_ctx = ctx.clone();
+        let menu_tx = tx.clone();
+        MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+            let Some(&(_, command)) = MENU_IDS.iter().find(|(id, _)| event.id == *id) else {
+                return;
+            };
+            if command == TrayCommand::Open {
+                Self::show_window(hwnd);
+            }
+            let _ = menu_tx.send(command);
+            menu_ctx.request_repaint();
+        }));
+
+        let ctx = ctx.clone();
+        TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+            let open = matches!(
+                event,
+                TrayIconEvent::DoubleClick { .. }
+                    | TrayIconEvent::Click {
+                        button: MouseButton::Left,
+                        button_state: MouseButtonState::Up,
+                        ..
+                    }
+            );
+            if open {
+                Self::show_window(hwnd);
+                let _ = tx.send(TrayCommand::Open);
+                ctx.request_repaint();
+            }
+        }));
+        rx
+    }
+
+    /// Whether this platform can hide the window to a tray icon
+    pub fn supported() -> bool {
+        cfg!(windows)
+    }
+
+    /// Whether the icon currently exists
+    pub fn is_shown(&self) -> bool {
+        #[cfg(windows)]
+        let shown = self.inner.is_some();
+        #[cfg(not(windows))]
+        let shown = false;
+        shown
+    }
+
+    /// Create or remove the tray icon. Cheap to call every frame.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        #[cfg(windows)]
+        if enabled != self.is_shown() {
+            if enabled {
+                self.create();
+            } else {
+                self.inner = None;
+            }
+        }
+        #[cfg(not(windows))]
+        let _ = enabled;
+    }
+
+    #[cfg(windows)]
+    fn create(&mut self) {
+        let [open, pause, sync, quit] = MENU_IDS.map(|(id, command)| {
+            let label = match command {
+                TrayCommand::Open => "Open",
+                TrayCommand::TogglePause => "Pause downloads",
+                TrayCommand::SyncDatabase => "Sync database",
+                TrayCommand::Quit => "Quit",
+            };
+            MenuItem::with_id(id, label, true, None)
+        });
+        let menu = Menu::new();
+        if let Err(e) = menu.append_items(&[
+            &open,
+            &PredefinedMenuItem::separator(),
+            &pause,
+            &sync,
+            &PredefinedMenuItem::separator(),
+            &quit,
+        ]) {
+            tracing::warn!(error = %e, "Failed to build tray menu");
+            return;
+        }
+
+        let icon_img = image::load_from_memory(include_bytes!("../assets/icon.png"))
+            .map(|img| img.resize_exact(32, 32, image::imageops::FilterType::Lanczos3).to_rgba8())
+            .ok();
+        let icon = icon_img.and_then(|img| {
+            let (w, h) = (img.width(), img.height());
+            tray_icon::Icon::from_rgba(img.into_raw(), w, h).ok()
+        });
+
+        let mut builder = TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_menu_on_left_click(false)
+            .with_tooltip("Gores Map Downloader");
+        if let Some(icon) = icon {
+            builder = builder.with_icon(icon);
+        }
+        match builder.build() {
+            Ok(tray) => {
+                self.last_tooltip.clear();
+                self.last_paused = false;
+                self.inner = Some(TrayInner { icon: tray, pause_item: pause });
+            }
+            Err(e) => tracing::warn!(error = %e, "Tray icon unavailable"),
+        }
+    }
+
+    #[cfg(windows)]
+    fn show_window(hwnd: Option<isize>) {
+        use windows::Win32::{
+            Foundation::HWND,
+            UI::WindowsAndMessaging::{SetForegroundWindow, ShowWindow, SW_SHOW},
+        };
+
+        let Some(hwnd) = hwnd else { return };
+        let hwnd = HWND(hwnd as *mut _);
+        unsafe {
+            let _ = ShowWindow(hwnd, SW_SHOW);
+            let _ = SetForegroundWindow(hwnd);
+        }
+    }
+
+    /// Next pending command from the tray, if any
+    pub fn poll(&mut self) -> Option<TrayCommand> {
+        self.commands.as_ref()?.try_recv().ok()
+    }
+
+    /// Show batch progress in the tooltip and keep the pause item's label in sync.
+    /// `progress` is `(finished, total)`, `None` when nothing is downloading.
+    pub fn set_status(&mut self, progress: Option<(usize, usize)>, paused: bool) {
+        if !self.is_shown() {
+            return;
+        }
+        let tooltip = match progress {
+            Some((finished, total)) => format!(
+                "Gores Map Downloader\n{} {}/{} maps ({}%)",
+                if paused { "Paused" } else { "Downloading" },
+                finished,
+                total,
+                (finished * 100).checked_div(total).unwrap_or(0)
+            ),
+            None => "Gores Map Downloader".to_string(),
+        };
+        if tooltip != self.last_tooltip {
+            #[cfg(windows)]
+            if let Some(inner) = &self.inner {
+                let _ = inner.icon.set_tooltip(Some(&tooltip));
+            }
+            self.last_tooltip = tooltip;
+        }
+        if paused != self.last_paused {
+            #[cfg(windows)]
+            if let Some(inner) = &self.inner {
+                inner
+                    .pause_item
+                    .set_text(if paused { "Resume downloads" } else { "Pause downloads" });
+            }
+            self.last_paused = paused;
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Common types and data structures
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
 
 /// Download status for individual map downloads
 #[derive(Clone, PartialEq)]
@@ -10,21 +11,48 @@ pub enum DownloadStatus {
     Complete,
     Skipped,
     Cancelled,
-    Failed(String),
+    Failed(String), // summary line, then details (URL, path, error)
+}
+
+/// Companion file to fetch alongside a map: (url, dest, size)
+pub type CompanionJob = (String, PathBuf, i64);
+
+/// One queued map
+#[derive(Clone)]
+pub struct DownloadJob {
+    pub idx: usize,
+    pub url: String,
+    pub dest: PathBuf,
+    pub size: i64,
+    pub collision: CollisionStrategy,
+    pub companions: Vec<CompanionJob>,
 }
 
 /// State tracking for batch downloads
 pub struct DownloadState {
     pub downloads: HashMap<usize, DownloadStatus>, // map_idx -> status
     pub download_order: Vec<usize>,                // Preserve order for display
+    pub finished_at: HashMap<usize, chrono::DateTime<chrono::Local>>, // map_idx -> completion time
+    pub queue: VecDeque<usize>,                    // Pending map_idx, workers pop from the front
+    pub jobs: HashMap<usize, DownloadJob>,         // map_idx -> what to fetch, shared by all workers
+    pub retry_attempts: HashMap<usize, u32>,       // map_idx -> retries so far in this batch
+    pub saved_as: HashMap<usize, String>,          // map_idx -> file name, when renamed on collision
+    pub paused: bool,                              // workers hold the queue; files in flight finish
     pub active_count: usize,
+    pub workers: usize, // worker tasks still running, post-download commands included
     pub total_queued: usize,
     pub completed_count: usize,
     pub failed_count: usize,
     pub skipped_count: usize,
     pub cancelled_count: usize,
+    pub run_start: (usize, usize), // (completed, cancelled) counts when the latest run (batch or retry) began
     pub total_bytes: u64,
     pub downloaded_bytes: u64,
+    pub companions_total: usize, // extra files queued alongside maps
+    pub companions_done: usize,  // saved or already present
+    pub companions_failed: usize,
+    pub post_download_command: Option<String>, // run after each saved map (see Settings)
+    pub command_failures: Vec<String>,         // post-download command errors, drained by the UI
 }
 
 impl Default for DownloadState {
@@ -32,20 +60,439 @@ impl Default for DownloadState {
         Self {
             downloads: HashMap::new(),
             download_order: Vec::new(),
+            finished_at: HashMap::new(),
+            queue: VecDeque::new(),
+            jobs: HashMap::new(),
+            retry_attempts: HashMap::new(),
+            saved_as: HashMap::new(),
+            paused: false,
             active_count: 0,
+            workers: 0,
             total_queued: 0,
             completed_count: 0,
             failed_count: 0,
             skipped_count: 0,
             cancelled_count: 0,
+            run_start: (0, 0),
             total_bytes: 0,
             downloaded_bytes: 0,
+            companions_total: 0,
+            companions_done: 0,
+            post_download_command: None,
+            command_failures: Vec::new(),
+            companions_failed: 0,
+        }
+    }
+}
+
+impl DownloadState {
+    /// Record a final status for a map along with its completion time
+    pub fn finish(&mut self, idx: usize, status: DownloadStatus) {
+        self.downloads.insert(idx, status);
+        self.finished_at.insert(idx, chrono::Local::now());
+    }
+
+    /// Whether a failed map has used up its retries and is left out of "Retry Failed"
+    pub fn retries_exhausted(&self, idx: usize, max_retries: u32) -> bool {
+        matches!(self.downloads.get(&idx), Some(DownloadStatus::Failed(_)))
+            && self.retry_attempts.get(&idx).copied().unwrap_or(0) >= max_retries
+    }
+
+    /// Move a queued map to the front of the queue
+    pub fn move_to_front(&mut self, idx: usize) {
+        if let Some(pos) = self.queue.iter().position(|&i| i == idx) {
+            self.queue.remove(pos);
+            self.queue.push_front(idx);
+        }
+    }
+
+    /// Drop a map that no worker has picked up yet from the batch. It leaves the totals
+    /// as if never queued; `bytes` and `companions` are what it added to them.
+    pub fn cancel_queued(&mut self, idx: usize, bytes: u64, companions: usize) -> bool {
+        let Some(pos) = self.queue.iter().position(|&i| i == idx) else {
+            return false;
+        };
+        if !matches!(self.downloads.get(&idx), Some(DownloadStatus::Pending)) {
+            return false;
+        }
+        self.queue.remove(pos);
+        self.download_order.retain(|&i| i != idx);
+        self.downloads.insert(idx, DownloadStatus::Cancelled);
+        self.total_queued = self.total_queued.saturating_sub(1);
+        self.total_bytes = self.total_bytes.saturating_sub(bytes);
+        self.companions_total = self.companions_total.saturating_sub(companions);
+        true
+    }
+
+    /// Drop finished maps (and skipped ones if asked) from the log. Only the listing
+    /// changes: counters, byte totals and in-flight or queued maps are untouched.
+    pub fn clear_finished(&mut self, include_skipped: bool) -> usize {
+        let cleared: Vec<usize> = self
+            .download_order
+            .iter()
+            .copied()
+            .filter(|idx| match self.downloads.get(idx) {
+                Some(DownloadStatus::Complete) => true,
+                Some(DownloadStatus::Skipped) => include_skipped,
+                _ => false,
+            })
+            .collect();
+        for idx in &cleared {
+            self.downloads.remove(idx);
+            self.finished_at.remove(idx);
+            self.saved_as.remove(idx);
+        }
+        self.download_order.retain(|idx| self.downloads.contains_key(idx));
+        cleared.len()
+    }
+
+    /// Move a queued map so it sits directly before `target`.
+    /// No-op if either has already been picked up by a worker.
+    pub fn move_before(&mut self, idx: usize, target: usize) {
+        if idx == target || !self.queue.contains(&target) {
+            return;
+        }
+        if let Some(pos) = self.queue.iter().position(|&i| i == idx) {
+            self.queue.remove(pos);
+            let target_pos = self.queue.iter().position(|&i| i == target).unwrap_or(0);
+            self.queue.insert(target_pos, idx);
+        }
+    }
+}
+
+/// A thumbnail on its way to the GPU
+pub enum ThumbnailLoad {
+    Decoding,                             // read and decoded off the UI thread
+    Decoded(eframe::egui::ColorImage),    // waiting for the per-frame upload budget
+    Failed,                               // unreadable file, handed to the cache repair
+}
+
+/// Thumbnail prefetch counters, shared with its workers
+#[derive(Debug, Default)]
+pub struct PrefetchProgress {
+    pub queued: usize,  // thumbnails missing from the cache when the prefetch started
+    pub fetched: usize, // saved, or cached as not on the server
+    pub failed: usize,  // still failing after PREFETCH_RETRIES
+    pub paused: bool,
+}
+
+/// App update download progress, shared with the update thread
+#[derive(Debug, Default)]
+pub struct AppUpdateProgress {
+    pub downloaded: u64,
+    pub total: Option<u64>, // None when the server sends no length
+    pub installing: bool,   // downloaded; extracting and replacing the binary, can't be cancelled
+}
+
+/// Toast severity (controls the border color)
+#[derive(Clone, Copy, PartialEq)]
+pub enum ToastSeverity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+/// Action run when a toast, or the button inside it, is clicked
+#[derive(Clone, PartialEq)]
+pub enum ToastAction {
+    OpenDownloads(Option<&'static str>), // open download modal with log filter
+    RetryFailed,                         // retry the batch's failures, showing them in the modal
+    ShowMaps(Vec<String>),               // select these maps and scroll to the first
+    UndoSelection,
+    CancelPrecheck, // stop the URL precheck and drop its batch
+}
+
+impl ToastAction {
+    /// Text of the toast button carrying this action
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::OpenDownloads(_) | Self::ShowMaps(_) => "View",
+            Self::RetryFailed => "Retry",
+            Self::UndoSelection => "Undo",
+            Self::CancelPrecheck => "Cancel",
+        }
+    }
+}
+
+/// Toast notification entry
+pub struct Toast {
+    pub id: u64,
+    pub message: String,
+    pub severity: ToastSeverity,
+    pub action: Option<ToastAction>, // clicking anywhere on the toast
+    pub button: Option<ToastAction>, // accent button labelled `ToastAction::label`
+    pub shown_at: Option<std::time::Instant>, // timer starts once visible
+}
+
+/// How release dates are displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum DateFormat {
+    Relative, // "3 weeks ago"
+    #[default]
+    Iso, // 2024-01-31
+    Eu,  // 31.01.2024
+    Us,  // 01/31/2024
+}
+
+impl DateFormat {
+    pub const ALL: [DateFormat; 4] = [Self::Relative, Self::Iso, Self::Eu, Self::Us];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Relative => "Relative",
+            Self::Iso => "YYYY-MM-DD",
+            Self::Eu => "DD.MM.YYYY",
+            Self::Us => "MM/DD/YYYY",
+        }
+    }
+}
+
+/// Sound played for notifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum NotificationSound {
+    #[default]
+    Chime,
+    Ping,
+    Pop,
+    Custom, // user-provided .wav/.ogg
+}
+
+impl NotificationSound {
+    pub const ALL: [NotificationSound; 4] = [Self::Chime, Self::Ping, Self::Pop, Self::Custom];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Chime => "Chime",
+            Self::Ping => "Ping",
+            Self::Pop => "Pop",
+            Self::Custom => "Custom file",
+        }
+    }
+}
+
+/// What to do when a map's file already exists in the download folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum CollisionStrategy {
+    #[default]
+    Skip,      // keep the existing file without downloading
+    Overwrite, // replace it
+    Rename,    // keep both, saving as "name (1).map"
+}
+
+impl CollisionStrategy {
+    pub const ALL: [CollisionStrategy; 3] = [Self::Skip, Self::Overwrite, Self::Rename];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Skip => "Skip",
+            Self::Overwrite => "Overwrite",
+            Self::Rename => "Keep both",
+        }
+    }
+}
+
+/// Clearable on-disk data, each with its own row in Settings > Cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    Thumbnails,
+    FullPreviews,
+    Logs,
+}
+
+impl CacheKind {
+    pub const ALL: [CacheKind; 3] = [Self::Thumbnails, Self::FullPreviews, Self::Logs];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Thumbnails => "Thumbnails",
+            Self::FullPreviews => "Full previews",
+            Self::Logs => "Logs",
         }
     }
 }
 
+/// Which of the filtered maps Select All adds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectScope {
+    All,
+    NotDownloaded,
+    Downloaded,
+}
+
+impl SelectScope {
+    pub const ALL: [SelectScope; 3] = [Self::All, Self::NotDownloaded, Self::Downloaded];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "Select all",
+            Self::NotDownloaded => "Select not downloaded",
+            Self::Downloaded => "Select downloaded",
+        }
+    }
+}
+
+/// The paste-a-list modal: a text box to paste names into, or the names that
+/// matched no map after a paste
+#[derive(Debug, Clone)]
+pub enum PasteList {
+    Input(String),
+    Unmatched(Vec<String>),
+}
+
+/// Page of the first-run setup; every page can be skipped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingStep {
+    Folder,
+    Scan,
+    Filters,
+    Thumbnails,
+}
+
+impl OnboardingStep {
+    pub const ALL: [OnboardingStep; 4] = [Self::Folder, Self::Scan, Self::Filters, Self::Thumbnails];
+
+    /// The following page, None after the last
+    pub fn next(self) -> Option<Self> {
+        let pos = Self::ALL.iter().position(|&s| s == self)?;
+        Self::ALL.get(pos + 1).copied()
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Folder => "Where should maps go?",
+            Self::Scan => "Maps you already have",
+            Self::Filters => "What do you want to see?",
+            Self::Thumbnails => "Thumbnails",
+        }
+    }
+}
+
+/// Details drawn on grid cards besides the name, set apart from the list
+/// columns since a card has far less room than a row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GridInfo {
+    pub category: bool,
+    pub stars: bool,
+    pub star_label: bool,
+    pub author: bool, // large thumbnails only
+    pub points: bool,
+    pub release_date: bool,
+}
+
+impl Default for GridInfo {
+    fn default() -> Self {
+        Self {
+            category: true,
+            stars: true,
+            star_label: false,
+            author: true,
+            points: true,
+            release_date: true,
+        }
+    }
+}
+
+/// Row height of the list view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum RowDensity {
+    Compact,
+    #[default]
+    Normal,
+    Comfortable,
+}
+
+impl RowDensity {
+    pub const ALL: [RowDensity; 3] = [Self::Compact, Self::Normal, Self::Comfortable];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Compact => "Compact",
+            Self::Normal => "Normal",
+            Self::Comfortable => "Comfortable",
+        }
+    }
+
+    pub fn row_height(self) -> f32 {
+        match self {
+            Self::Compact => 24.0,
+            Self::Normal => 29.0,
+            Self::Comfortable => 36.0,
+        }
+    }
+
+    /// Row height of the list, taller when rows carry a thumbnail
+    pub fn list_row_height(self, thumbnails: bool) -> f32 {
+        match (self, thumbnails) {
+            (_, false) => self.row_height(),
+            (Self::Compact, true) => 32.0,
+            (Self::Normal, true) => 38.0,
+            (Self::Comfortable, true) => 46.0,
+        }
+    }
+
+    /// Size of secondary text; map names are 2 px larger
+    pub fn font_size(self) -> f32 {
+        match self {
+            Self::Compact => 11.0,
+            Self::Normal => 12.0,
+            Self::Comfortable => 13.0,
+        }
+    }
+
+    pub fn badge_height(self) -> f32 {
+        match self {
+            Self::Compact => 20.0,
+            Self::Normal => 26.0,
+            Self::Comfortable => 28.0,
+        }
+    }
+}
+
+/// Field the list view is split into collapsible sections by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum GroupBy {
+    #[default]
+    None,
+    Category,
+    Author,
+    Year,
+    Stars,
+}
+
+impl GroupBy {
+    pub const ALL: [GroupBy; 5] = [Self::None, Self::Category, Self::Author, Self::Year, Self::Stars];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Category => "Category",
+            Self::Author => "Author",
+            Self::Year => "Year",
+            Self::Stars => "Stars",
+        }
+    }
+}
+
+/// Row of the list view when grouping is active
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListRow {
+    Header(usize), // index into `list_groups`
+    Map(usize),    // position in `filtered_indices`
+}
+
+/// A section of the grouped list view
+#[derive(Debug, Clone)]
+pub struct ListGroup {
+    pub key: String,
+    pub order: i64, // from `group_of`; the match tier while grouping by relevance
+    pub count: usize, // including collapsed maps
+    pub collapsed: bool,
+}
+
 /// Column to sort by in list view
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum SortColumn {
     Name,
     Category,
@@ -56,7 +503,7 @@ pub enum SortColumn {
 }
 
 /// Sort direction for list view
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum SortDirection {
     Ascending,
     Descending,
@@ -69,24 +516,165 @@ pub struct ScrollIndexMarker {
     pub row_index: usize,
 }
 
-/// Manifest structure from remote JSON
+/// Manifest structure from remote JSON.
+/// Unknown fields are ignored so additive changes don't need a new schema.
 #[derive(serde::Deserialize)]
 pub struct Manifest {
+    #[serde(default = "default_manifest_schema")]
+    pub schema: u32,
     pub version: String,
-    #[serde(alias = "count")]
+    #[serde(alias = "count", default)]
     pub map_count: usize,
     pub maps: Vec<ManifestMap>,
 }
 
+fn default_manifest_schema() -> u32 {
+    1
+}
+
 /// Individual map entry in manifest
 #[derive(serde::Deserialize)]
 pub struct ManifestMap {
     pub name: String,
     pub category: String,
     pub stars: i32,
+    #[serde(default)]
     pub points: i32,
+    #[serde(default)]
     pub author: String,
+    #[serde(default)]
     pub release_date: String,
     #[serde(default)]
     pub size: i64,
+    /// SHA-256 of the .map file (hex); maps without it skip the outdated check
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub files: Vec<CompanionFile>,
+}
+
+/// Extra file shipped next to a map (rules, images...), stored beside the .map
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CompanionFile {
+    pub file: String, // relative to the map's folder in the maps repo
+    #[serde(default)]
+    pub size: i64,
+}
+
+/// Why a manifest couldn't be used
+#[derive(Debug)]
+pub enum ManifestError {
+    /// Published with a newer schema than this build understands
+    Unsupported(u32),
+    Invalid(String),
+    Fetch(String),
+}
+
+impl std::fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsupported(_) => {
+                write!(f, "This app version is too old for the current map database — please update")
+            }
+            Self::Invalid(e) => write!(f, "Invalid manifest: {}", e),
+            Self::Fetch(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Manifest {
+    /// Parse manifest JSON, checking the schema before the body so a future
+    /// format is reported as unsupported rather than as a parse error
+    pub fn parse(bytes: &[u8]) -> Result<Self, ManifestError> {
+        #[derive(serde::Deserialize)]
+        struct Header {
+            #[serde(default = "default_manifest_schema")]
+            schema: u32,
+        }
+
+        let header: Header =
+            serde_json::from_slice(bytes).map_err(|e| ManifestError::Invalid(e.to_string()))?;
+        if header.schema > crate::constants::MANIFEST_SCHEMA {
+            return Err(ManifestError::Unsupported(header.schema));
+        }
+        let mut manifest: Self =
+            serde_json::from_slice(bytes).map_err(|e| ManifestError::Invalid(e.to_string()))?;
+        if manifest.map_count == 0 {
+            manifest.map_count = manifest.maps.len();
+        }
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Before `schema` and `map_count` existed: `count`, and maps with only the required fields
+    const OLD_MANIFEST: &str = r#"{
+        "version": "2023-11-02",
+        "count": 2,
+        "maps": [
+            {"name": "Kobra", "category": "Easy", "stars": 1},
+            {"name": "Sunny Side Up", "category": "Main", "stars": 3, "points": 20, "author": "Ravie"}
+        ]
+    }"#;
+
+    const CURRENT_MANIFEST: &str = r#"{
+        "schema": 1,
+        "version": "2024.05.01",
+        "map_count": 1,
+        "maps": [{
+            "name": "Kobra 4", "category": "Insane", "stars": 5, "points": 50, "author": "Welf & Ravie",
+            "release_date": "2024-04-30", "size": 123456, "sha256": "ab12",
+            "files": [{"file": "rules.txt", "size": 42}]
+        }]
+    }"#;
+
+    /// A later schema may reshape the body; it must be refused by its header alone
+    const FUTURE_MANIFEST: &str = r#"{
+        "schema": 2,
+        "version": "3.0",
+        "maps": {"Kobra": {"difficulty": "easy"}}
+    }"#;
+
+    #[test]
+    fn parses_an_old_manifest() {
+        let manifest = Manifest::parse(OLD_MANIFEST.as_bytes()).unwrap();
+        assert_eq!(manifest.schema, 1);
+        assert_eq!(manifest.map_count, 2);
+        assert_eq!(manifest.maps[0].points, 0);
+        assert!(manifest.maps[0].author.is_empty());
+        assert!(manifest.maps[0].sha256.is_none() && manifest.maps[0].files.is_empty());
+        assert_eq!(manifest.maps[1].author, "Ravie");
+    }
+
+    #[test]
+    fn parses_a_current_manifest() {
+        let manifest = Manifest::parse(CURRENT_MANIFEST.as_bytes()).unwrap();
+        assert_eq!(manifest.version, "2024.05.01");
+        let map = &manifest.maps[0];
+        assert_eq!((map.name.as_str(), map.stars, map.size), ("Kobra 4", 5, 123456));
+        assert_eq!(map.sha256.as_deref(), Some("ab12"));
+        assert_eq!(map.files[0].file, "rules.txt");
+    }
+
+    #[test]
+    fn map_count_defaults_to_the_maps_listed() {
+        let json = br#"{"version": "1", "maps": [{"name": "A", "category": "Easy", "stars": 1}]}"#;
+        let manifest = Manifest::parse(json).unwrap();
+        assert_eq!(manifest.map_count, 1);
+    }
+
+    #[test]
+    fn refuses_a_future_schema() {
+        assert!(matches!(Manifest::parse(FUTURE_MANIFEST.as_bytes()), Err(ManifestError::Unsupported(2))));
+    }
+
+    #[test]
+    fn malformed_json_is_invalid() {
+        for bytes in [&b""[..], b"<html>", br#"{"version": "1"}"#, br#"{"version": "1", "maps": [{"name": "A"}]}"#] {
+            assert!(matches!(Manifest::parse(bytes), Err(ManifestError::Invalid(_))));
+        }
+    }
 }
@@ -4,19 +4,62 @@
 //! throughout the application.
 
 use crate::theme;
+use crate::types::DateFormat;
 use eframe::egui;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Hard cap on displayed map name length (in characters) before width-based truncation
+pub const MAX_NAME_CHARS: usize = 64;
 
 /// Render a star rating display
 pub fn render_stars(stars: i32) -> String {
     "★".repeat(stars as usize) + &"☆".repeat((5 - stars) as usize)
 }
 
-/// Format release date, returning "N/A" for invalid dates
-pub fn format_release_date(date: &str) -> &str {
-    if date.len() >= 4 && date.chars().take(4).all(|c| c.is_ascii_digit()) {
-        date
-    } else {
-        "N/A"
+/// Numeric star count for readers who don't know the glyph scale, e.g. "3/5"
+pub fn star_label(stars: i32) -> String {
+    format!("{}/5", stars.clamp(0, 5))
+}
+
+/// Format release date in the chosen style, returning "—" for invalid dates.
+/// Dates that don't parse as YYYY-MM-DD but start with a year are shown as-is.
+pub fn format_release_date(date: &str, format: DateFormat) -> String {
+    if !(date.len() >= 4 && date.chars().take(4).all(|c| c.is_ascii_digit())) {
+        return "—".to_string();
+    }
+    let Some(parsed) = date
+        .get(..10)
+        .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+    else {
+        return date.to_string();
+    };
+    match format {
+        DateFormat::Relative => relative_date(parsed, chrono::Local::now().date_naive()),
+        DateFormat::Iso => parsed.format("%Y-%m-%d").to_string(),
+        DateFormat::Eu => parsed.format("%d.%m.%Y").to_string(),
+        DateFormat::Us => parsed.format("%m/%d/%Y").to_string(),
+    }
+}
+
+/// Describe `date` relative to `today` ("yesterday", "3 weeks ago", "2 years ago")
+pub fn relative_date(date: chrono::NaiveDate, today: chrono::NaiveDate) -> String {
+    let days = (today - date).num_days();
+    let plural = |n: i64, unit: &str| {
+        if n == 1 {
+            format!("1 {} ago", unit)
+        } else {
+            format!("{} {}s ago", n, unit)
+        }
+    };
+    match days {
+        i64::MIN..=-1 => date.format("%Y-%m-%d").to_string(),
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        2..=6 => plural(days, "day"),
+        7..=29 => plural(days / 7, "week"),
+        30..=364 => plural((days / 30).min(11), "month"),
+        _ => plural(days / 365, "year"),
     }
 }
 
@@ -30,7 +73,7 @@ pub fn styled_checkbox(ui: &mut egui::Ui, selected: bool, size: f32) -> egui::Re
 
         if selected {
             // Filled checkbox
-            painter.rect_filled(rect, rounding, theme::ACCENT);
+            painter.rect_filled(rect, rounding, theme::accent());
             painter.text(
                 rect.center(),
                 egui::Align2::CENTER_CENTER,
@@ -51,3 +94,195 @@ pub fn styled_checkbox(ui: &mut egui::Ui, selected: bool, size: f32) -> egui::Re
 
     response
 }
+
+/// Check whether `text` laid out on a single line is wider than `max_width`
+pub fn text_overflows(ctx: &egui::Context, text: &str, font: egui::FontId, max_width: f32) -> bool {
+    let width = ctx.fonts(|f| {
+        f.layout_no_wrap(text.to_string(), font, egui::Color32::WHITE)
+            .size()
+            .x
+    });
+    width > max_width
+}
+
+/// Shorten `text` to at most `max_chars` characters, ending in "…" when cut.
+/// Cuts on char boundaries, so multibyte names can't panic.
+pub fn truncate_chars(text: &str, max_chars: usize) -> Cow<'_, str> {
+    if max_chars == 0 || text.chars().nth(max_chars).is_none() {
+        return Cow::Borrowed(text);
+    }
+    let keep: String = text.chars().take(max_chars - 1).collect();
+    Cow::Owned(format!("{}…", keep))
+}
+
+/// Lay out a single line of text, capped at `MAX_NAME_CHARS` and ellipsized to `max_width`
+pub fn ellipsized_galley(
+    ctx: &egui::Context,
+    text: &str,
+    font: egui::FontId,
+    color: egui::Color32,
+    max_width: f32,
+) -> Arc<egui::Galley> {
+    let mut job = egui::text::LayoutJob::simple_singleline(
+        truncate_chars(text, MAX_NAME_CHARS).into_owned(),
+        font,
+        color,
+    );
+    job.wrap = egui::text::TextWrapping::truncate_at_width(max_width);
+    ctx.fonts(|f| f.layout_job(job))
+}
+
+/// Up to two initials for a map name: "Gores Tower" -> "GT", "Kobra" -> "KO"
+pub fn name_initials(name: &str) -> String {
+    let words: Vec<&str> = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let initials: String = match words.as_slice() {
+        [] => "?".to_string(),
+        [word] => word.chars().take(2).collect(),
+        [first, second, ..] => first.chars().take(1).chain(second.chars().take(1)).collect(),
+    };
+    initials.to_uppercase()
+}
+
+/// Stand-in for a map image the server doesn't have: initials on the category color
+pub fn paint_map_placeholder(painter: &egui::Painter, rect: egui::Rect, name: &str, category: &str, rounding: f32) {
+    let (_, color) = theme::category_colors(category);
+    painter.rect_filled(rect, rounding, theme::BG_ELEVATED);
+    painter.rect_filled(rect, rounding, theme::with_alpha(color, 40));
+    painter.text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        name_initials(name),
+        egui::FontId::proportional((rect.height() * 0.35).clamp(14.0, 96.0)),
+        theme::with_alpha(color, 160),
+    );
+}
+
+/// Card geometry for the grid view. Rendering and hit-testing both go through
+/// this, so what's drawn always matches what's clickable.
+#[derive(Clone, Copy)]
+pub struct GridLayout {
+    pub cols: usize,
+    pub card_size: egui::Vec2,
+    pub spacing: f32,
+}
+
+impl GridLayout {
+    /// Fit at least 3 columns of `base_size` cards into `available_width`,
+    /// stretching cards to fill the row. Sizes are floored so layout is stable.
+    pub fn new(available_width: f32, base_size: egui::Vec2, spacing: f32) -> Self {
+        let cols = ((available_width + spacing) / (base_size.x + spacing))
+            .floor()
+            .max(3.0);
+        let card_w = ((available_width - spacing * (cols - 1.0)) / cols).floor();
+        let card_h = (base_size.y * (card_w / base_size.x)).floor();
+        Self {
+            cols: cols as usize,
+            card_size: egui::vec2(card_w, card_h),
+            spacing,
+        }
+    }
+
+    /// Distance between the tops of two consecutive rows
+    pub fn row_stride(&self) -> f32 {
+        self.card_size.y + self.spacing
+    }
+
+    /// Visual row containing the card at `position` in the filtered list
+    pub fn row_of(&self, position: usize) -> usize {
+        position / self.cols
+    }
+
+    pub fn content_height(&self, count: usize) -> f32 {
+        let rows = count.div_ceil(self.cols);
+        (rows as f32 * self.row_stride() - self.spacing).max(0.0)
+    }
+
+    /// Rect of the card at `position`, relative to the content `origin`
+    pub fn card_rect(&self, origin: egui::Pos2, position: usize) -> egui::Rect {
+        let col = position % self.cols;
+        let row = self.row_of(position);
+        let min = origin
+            + egui::vec2(
+                col as f32 * (self.card_size.x + self.spacing),
+                row as f32 * self.row_stride(),
+            );
+        egui::Rect::from_min_size(min, self.card_size)
+    }
+
+    /// `(map_idx, rect)` for every card intersecting `clip`, in display order
+    pub fn visible_cards(
+        &self,
+        origin: egui::Pos2,
+        indices: &[usize],
+        clip: egui::Rect,
+    ) -> Vec<(usize, egui::Rect)> {
+        let stride = self.row_stride();
+        let first_row = ((clip.top() - origin.y) / stride).floor().max(0.0) as usize;
+        let last_row = ((clip.bottom() - origin.y) / stride).floor().max(0.0) as usize;
+        let start = (first_row * self.cols).min(indices.len());
+        let end = ((last_row + 1) * self.cols).min(indices.len());
+        (start..end)
+            .map(|pos| (indices[pos], self.card_rect(origin, pos)))
+            .filter(|(_, rect)| rect.intersects(clip))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn ago(days: i64) -> String {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
+        relative_date(today - chrono::Duration::days(days), today)
+    }
+
+    #[test]
+    fn relative_date_boundaries() {
+        assert_eq!(ago(0), "today");
+        assert_eq!(ago(1), "yesterday");
+        assert_eq!(ago(2), "2 days ago");
+        assert_eq!(ago(6), "6 days ago");
+        assert_eq!(ago(7), "1 week ago");
+        assert_eq!(ago(29), "4 weeks ago");
+        assert_eq!(ago(30), "1 month ago");
+        assert_eq!(ago(364), "11 months ago");
+        assert_eq!(ago(365), "1 year ago");
+        assert_eq!(ago(730), "2 years ago");
+    }
+
+    #[test]
+    fn future_dates_are_shown_as_dates() {
+        assert_eq!(ago(-1), "2024-06-16");
+        assert_eq!(ago(-400), "2025-07-20");
+    }
+
+    #[test]
+    fn invalid_release_dates_render_as_a_dash() {
+        for date in ["", "unknown", "20x4-01-01", "N/A"] {
+            assert_eq!(format_release_date(date, DateFormat::Iso), "—");
+        }
+        // A year without a parseable day is shown as-is
+        assert_eq!(format_release_date("2019", DateFormat::Eu), "2019");
+        assert_eq!(format_release_date("2019-03-07", DateFormat::Eu), "07.03.2019");
+    }
+
+    #[test]
+    fn truncate_chars_cuts_multibyte_text_on_char_boundaries() {
+        assert_eq!(truncate_chars("🚀🔥🌙✨", 3), "🚀🔥…");
+        assert_eq!(truncate_chars("Café Olé", 4), "Caf…");
+        assert_eq!(truncate_chars("Ünïcödé", 6), "Ünïcö…");
+        assert_eq!(truncate_chars("é🚀", 1), "…");
+    }
+
+    #[test]
+    fn truncate_chars_keeps_text_that_fits() {
+        assert!(matches!(truncate_chars("Olé🚀", 4), Cow::Borrowed("Olé🚀")));
+        assert!(matches!(truncate_chars("Olé🚀", 0), Cow::Borrowed(_)));
+        assert_eq!(truncate_chars("", 3), "");
+    }
+}
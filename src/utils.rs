@@ -1,7 +1,9 @@
 //! Utility functions
 
-use crate::constants::{APP_VERSION, CACHE_REFRESH};
-use std::path::PathBuf;
+use crate::constants::{APP_VERSION, CACHE_REFRESH, IMAGE_EXTENSIONS, POST_COMMAND_TIMEOUT};
+use std::path::{Path, PathBuf};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
 // With stroke — for sidebar logo (large display)
 pub const LOGO_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 251.93 167.71"><defs><style>.c1{fill:#fff;stroke:#09090b;stroke-width:1px}.c2{fill:#2dd4bf;stroke:#09090b;stroke-width:1px}</style></defs><path class="c1" d="m104.54,84.12h-19.01c-2.88,0-4.96.64-6.25,1.93-1.29,1.29-1.93,3.37-1.93,6.24v35.46h-22.04c-3.48,0-6.1-.91-7.84-2.74-1.74-1.81-2.61-4.61-2.61-8.39v-55.8c0-3.79,1.14-16.34,3.41-18.23,2.27-1.89,5.76-2.84,10.45-2.84h47.26c2.88,0,4.96-.64,6.25-1.93,1.29-1.29,1.93-3.37,1.93-6.25V8.18c0-2.88-.64-4.96-1.93-6.25C110.94.64,108.86,0,105.98,0h-56.81C30.24,0,22.91,3.79,13.75,11.36,4.58,18.94,0,26.49,0,42.25v82.08c0,15.77,4.58,24.44,13.75,32.02,9.16,7.57,16.49,11.36,35.43,11.36h66.35c2.88,0,4.96-.64,6.25-1.93,1.29-1.29,1.93-3.37,1.93-6.25v-45.95l-19.16-29.45Z"/><path class="c2" d="m128.23,113.58v45.95c0,2.88.64,4.96,1.93,6.25,1.29,1.29,3.37,1.93,6.25,1.93h66.35c18.94,0,26.26-3.79,35.43-11.36,9.16-7.57,13.75-16.25,13.75-32.02V42.25c0-15.75-4.58-23.31-13.75-30.88C229.02,3.79,221.69,0,202.75,0h-56.81c-2.88,0-4.96.64-6.25,1.93-1.29,1.29-1.93,3.37-1.93,6.25v23.39c0,2.88.64,4.96,1.93,6.25,1.29,1.29,3.37,1.93,6.25,1.93h47.26c4.7,0,8.18.95,10.45,2.84,2.27,1.89,3.41,14.44,3.41,18.23v55.8c0,3.79-.87,6.59-2.61,8.39-1.74,1.83-4.36,2.74-7.84,2.74h-22.04v-35.46c0-2.87-.64-4.95-1.93-6.24-1.29-1.29-3.37-1.93-6.25-1.93h-19.01s-19.16,29.45-19.16,29.45Z"/></svg>"#;
@@ -76,6 +78,25 @@ pub fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// Short label for a YYYYMM month, e.g. "Mar '23"
+pub fn format_month(yyyymm: i32) -> String {
+    const NAMES: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    let month = (yyyymm % 100).clamp(1, 12) as usize;
+    format!("{} '{:02}", NAMES[month - 1], (yyyymm / 100) % 100)
+}
+
+/// Combined size of a batch where 0 means unknown, e.g. "4.2 MB" or
+/// "≥ 4.2 MB (some sizes unknown)"
+pub fn format_batch_size(sizes: &[u64]) -> String {
+    let total: u64 = sizes.iter().sum();
+    let unknown = sizes.contains(&0);
+    match (total, unknown) {
+        (0, true) => "Size unknown".to_string(),
+        (_, true) => format!("≥ {} (some sizes unknown)", format_bytes(total)),
+        _ => format_bytes(total),
+    }
+}
+
 /// Compare two version strings, returns true if a > b
 pub fn version_greater_than(a: &str, b: &str) -> bool {
     let parse = |v: &str| -> Vec<u32> { v.split('.').filter_map(|s| s.parse().ok()).collect() };
@@ -93,13 +114,223 @@ pub fn process_cache_refresh(cache_dir: &std::path::Path) {
     for (ver, files) in CACHE_REFRESH {
         if stored.is_empty() || version_greater_than(ver, &stored) {
             for name in *files {
-                let _ = std::fs::remove_file(
-                    cache_dir.join("thumbnails").join(format!("{}.png", name)),
-                );
-                let _ = std::fs::remove_file(cache_dir.join("full").join(format!("{}.png", name)));
+                for ext in IMAGE_EXTENSIONS {
+                    let _ = std::fs::remove_file(
+                        cache_dir.join("thumbnails").join(format!("{}.{}", name, ext)),
+                    );
+                    let _ = std::fs::remove_file(cache_dir.join("full").join(format!("{}.{}", name, ext)));
+                }
             }
         }
     }
 
     let _ = std::fs::write(&version_file, APP_VERSION);
 }
+
+/// Whether `path` can receive downloads: an existing directory, or a missing one
+/// whose parent exists (it's created on the first download). A removed USB or
+/// network drive fails both.
+pub fn download_path_reachable(path: &Path) -> bool {
+    if path.exists() {
+        return path.is_dir();
+    }
+    path.parent().is_some_and(|parent| parent.is_dir())
+}
+
+/// Write `bytes` to a temp file next to `path`, then rename it into place, so a
+/// crash mid-write never leaves a truncated file under the real name.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp);
+    })
+}
+
+/// The cached image for `base` (a `.png` cache path) in whichever of
+/// `IMAGE_EXTENSIONS` it was saved as
+pub fn cached_image(base: &Path) -> Option<PathBuf> {
+    IMAGE_EXTENSIONS.iter().map(|ext| base.with_extension(ext)).find(|path| path.exists())
+}
+
+/// Existing DDNet map folders for this user, most likely first
+/// (%APPDATA%\DDNet\maps on Windows, ~/.local/share/ddnet/maps on Linux)
+pub fn ddnet_maps_dirs() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(config) = dirs::config_dir() {
+        candidates.push(config.join("DDNet").join("maps"));
+    }
+    if let Some(data) = dirs::data_dir() {
+        candidates.push(data.join("ddnet").join("maps"));
+        candidates.push(data.join("DDNet").join("maps"));
+    }
+    let mut found: Vec<PathBuf> = Vec::new();
+    for dir in candidates {
+        if dir.is_dir() && !found.contains(&dir) {
+            found.push(dir);
+        }
+    }
+    found
+}
+
+/// Split a command line into program and arguments without a shell: whitespace
+/// separates, double quotes group (`"C:\Tools\sync.exe" --file "{path}"`)
+pub fn split_command_line(line: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_part = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_part = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_part {
+                    parts.push(std::mem::take(&mut current));
+                    has_part = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_part = true;
+            }
+        }
+    }
+    if has_part {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Run the post-download command for a saved map. `{path}` and `{name}` are filled
+/// in per argument after splitting, so a path with spaces stays one argument and
+/// nothing is interpreted by a shell. Output is logged; launch failures and
+/// non-zero exits come back as a one-line error. The process is killed once it
+/// runs past `POST_COMMAND_TIMEOUT` or `cancel` fires (e.g. on exit).
+pub fn run_post_download_command(
+    template: &str,
+    path: &Path,
+    name: &str,
+    cancel: &CancellationToken,
+) -> Result<(), String> {
+    use std::process::Stdio;
+
+    let path_str = path.to_string_lossy();
+    let mut args = split_command_line(template)
+        .into_iter()
+        .map(|arg| arg.replace("{path}", &path_str).replace("{name}", name));
+    let Some(program) = args.next() else {
+        return Ok(());
+    };
+    let mut command = std::process::Command::new(&program);
+    command
+        .args(args)
+        .current_dir(path.parent().unwrap_or(Path::new(".")))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let mut child = command.spawn().map_err(|e| format!("Couldn't run {}: {}", program, e))?;
+
+    // Drain the pipes on their own threads so a chatty command can't block on a full pipe
+    let stdout_reader = read_pipe(child.stdout.take());
+    let stderr_reader = read_pipe(child.stderr.take());
+
+    let started = std::time::Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if cancel.is_cancelled() || started.elapsed() >= POST_COMMAND_TIMEOUT => {
+                child.kill().ok();
+                child.wait().ok();
+                warn!(map = %name, "Post-download command killed");
+                return Err(if cancel.is_cancelled() {
+                    format!("{} was stopped", program)
+                } else {
+                    format!("{} timed out after {}s", program, POST_COMMAND_TIMEOUT.as_secs())
+                });
+            }
+            Ok(None) => std::thread::sleep(std::time::Duration::from_millis(50)),
+            Err(e) => return Err(format!("Couldn't wait for {}: {}", program, e)),
+        }
+    };
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+    if status.success() {
+        info!(map = %name, stdout = %stdout.trim(), stderr = %stderr.trim(), "Post-download command finished");
+        Ok(())
+    } else {
+        warn!(map = %name, status = %status, stdout = %stdout.trim(), stderr = %stderr.trim(), "Post-download command failed");
+        let detail = stderr.lines().next().filter(|l| !l.trim().is_empty()).map(|l| format!(": {}", l.trim()));
+        Err(format!("{} exited with {}{}", program, status, detail.unwrap_or_default()))
+    }
+}
+
+/// Read a child's output pipe to the end on a separate thread
+fn read_pipe<R: std::io::Read + Send + 'static>(pipe: Option<R>) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut bytes).ok();
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+/// Open the file manager with `path` selected (Explorer `/select`, Finder `-R`).
+/// Other platforms just open the containing folder.
+pub fn show_in_folder(path: &Path) -> std::io::Result<()> {
+    #[cfg(windows)]
+    {
+        // Explorer parses its own command line; the quotes must reach it as written
+        // for paths with spaces or commas, so the argument isn't escaped
+        use std::os::windows::process::CommandExt;
+        std::process::Command::new("explorer")
+            .raw_arg(format!("/select,\"{}\"", path.display()))
+            .spawn()
+            .map(|_| ())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open").arg("-R").arg(path).spawn().map(|_| ())
+    }
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        open::that(path.parent().unwrap_or(path))
+    }
+}
+
+/// Whether the OS asks apps to minimize animation (Windows "Animation effects" off).
+/// Returns false where the preference can't be detected.
+pub fn os_prefers_reduced_motion() -> bool {
+    #[cfg(windows)]
+    {
+        use windows::Win32::Foundation::BOOL;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+        };
+        let mut enabled = BOOL(1);
+        let ok = unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                Some(&mut enabled as *mut BOOL as *mut std::ffi::c_void),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+        };
+        ok.is_ok() && !enabled.as_bool()
+    }
+    #[cfg(not(windows))]
+    {
+        false
+    }
+}